@@ -0,0 +1,650 @@
+use std::fmt;
+
+/// Lines longer than this are never treated as a cookie or consent banner.
+const BANNER_MAX_WORDS: usize = 60;
+
+/// Lines longer than this are never treated as a subscription or share prompt.
+const PROMPT_MAX_WORDS: usize = 12;
+
+/// Boilerplate phrases for one language, used to drop leftover navigation,
+/// cookie banners, newsletter calls to action and share prompts from the
+/// Markdown of pages in that language.
+///
+/// Each kind of phrase is matched against whole lines, so ordinary prose that
+/// happens to use the same words is kept:
+///
+/// - a **navigation term** drops a line consisting of exactly that term
+/// - a **cookie phrase** drops a line containing it
+/// - a **subscription phrase** or **share prompt** drops a short line starting with it
+///
+/// Matching ignores case, surrounding list and emphasis markers and trailing
+/// punctuation. **Text patterns** are regular expressions removed wherever they
+/// match, like [`ExtractionConfig::text_patterns`](crate::ExtractionConfig::text_patterns).
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::BoilerplatePack;
+///
+/// let pack = BoilerplatePack::new("nl")
+///     .navigation_term("Zoeken")
+///     .cookie_phrase("Wij gebruiken cookies")
+///     .subscription_phrase("Schrijf je in voor onze nieuwsbrief")
+///     .share_prompt("Deel dit artikel");
+///
+/// assert_eq!(pack.language(), "nl");
+/// assert!(pack.is_boilerplate_line("**Deel dit artikel:**"));
+/// assert!(!pack.is_boilerplate_line("Zoeken naar de oorzaak duurde maanden."));
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct BoilerplatePack {
+    language: String,
+    navigation_terms: Vec<String>,
+    cookie_phrases: Vec<String>,
+    subscription_phrases: Vec<String>,
+    share_prompts: Vec<String>,
+    text_patterns: Vec<String>,
+}
+
+impl BoilerplatePack {
+    /// Creates an empty pack for a language, given as a BCP 47 tag such as `es`
+    /// or `es-MX`; only the primary subtag is kept.
+    pub fn new(language: &str) -> Self {
+        Self {
+            language: primary_subtag(language),
+            navigation_terms: Vec::new(),
+            cookie_phrases: Vec::new(),
+            subscription_phrases: Vec::new(),
+            share_prompts: Vec::new(),
+            text_patterns: Vec::new(),
+        }
+    }
+
+    /// Returns the built-in pack for a language, if there is one.
+    ///
+    /// Built-in packs exist for English, Portuguese, Spanish, French, German and
+    /// Italian. They are a good starting point for adding site-specific terms.
+    pub fn builtin(language: &str) -> Option<Self> {
+        let language = primary_subtag(language);
+        builtin_packs()
+            .into_iter()
+            .find(|pack| pack.language == language)
+    }
+
+    /// Adds a term that is dropped when it makes up a whole line.
+    pub fn navigation_term(mut self, term: impl Into<String>) -> Self {
+        push_phrase(&mut self.navigation_terms, term.into());
+        self
+    }
+
+    /// Adds a phrase that marks a line as part of a cookie or consent banner.
+    pub fn cookie_phrase(mut self, phrase: impl Into<String>) -> Self {
+        push_phrase(&mut self.cookie_phrases, phrase.into());
+        self
+    }
+
+    /// Adds a phrase that starts a newsletter or subscription call to action.
+    pub fn subscription_phrase(mut self, phrase: impl Into<String>) -> Self {
+        push_phrase(&mut self.subscription_phrases, phrase.into());
+        self
+    }
+
+    /// Adds a phrase that starts a social sharing prompt.
+    pub fn share_prompt(mut self, phrase: impl Into<String>) -> Self {
+        push_phrase(&mut self.share_prompts, phrase.into());
+        self
+    }
+
+    /// Adds a regular expression removed wherever it matches.
+    ///
+    /// Patterns that fail to compile are ignored at processing time.
+    pub fn text_pattern(mut self, pattern: impl Into<String>) -> Self {
+        let pattern = pattern.into();
+        if !self.text_patterns.contains(&pattern) {
+            self.text_patterns.push(pattern);
+        }
+        self
+    }
+
+    /// Returns the primary language subtag this pack applies to, e.g. `es`.
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn navigation_terms(&self) -> &[String] {
+        &self.navigation_terms
+    }
+
+    pub fn cookie_phrases(&self) -> &[String] {
+        &self.cookie_phrases
+    }
+
+    pub fn subscription_phrases(&self) -> &[String] {
+        &self.subscription_phrases
+    }
+
+    pub fn share_prompts(&self) -> &[String] {
+        &self.share_prompts
+    }
+
+    pub fn text_patterns(&self) -> &[String] {
+        &self.text_patterns
+    }
+
+    /// Returns `true` if the Markdown line is boilerplate according to this pack.
+    pub fn is_boilerplate_line(&self, line: &str) -> bool {
+        self.matching_phrase(line).is_some()
+    }
+
+    /// Returns the term or phrase that makes the line boilerplate, if any.
+    pub(crate) fn matching_phrase(&self, line: &str) -> Option<&str> {
+        let line = normalize_line(line);
+        if line.is_empty() {
+            return None;
+        }
+        let words = line.split_whitespace().count();
+
+        let navigation = self.navigation_terms.iter().find(|term| **term == line);
+        let cookie = || {
+            self.cookie_phrases
+                .iter()
+                .filter(|_| words <= BANNER_MAX_WORDS)
+                .find(|phrase| line.contains(phrase.as_str()))
+        };
+        let prompt = || {
+            self.subscription_phrases
+                .iter()
+                .chain(&self.share_prompts)
+                .filter(|_| words <= PROMPT_MAX_WORDS)
+                .find(|phrase| starts_with_phrase(&line, phrase))
+        };
+
+        navigation
+            .or_else(cookie)
+            .or_else(prompt)
+            .map(String::as_str)
+    }
+
+    /// Adds the phrases and patterns of `other` to this pack.
+    fn merge(&mut self, other: BoilerplatePack) {
+        for term in other.navigation_terms {
+            push_phrase(&mut self.navigation_terms, term);
+        }
+        for phrase in other.cookie_phrases {
+            push_phrase(&mut self.cookie_phrases, phrase);
+        }
+        for phrase in other.subscription_phrases {
+            push_phrase(&mut self.subscription_phrases, phrase);
+        }
+        for phrase in other.share_prompts {
+            push_phrase(&mut self.share_prompts, phrase);
+        }
+        for pattern in other.text_patterns {
+            if !self.text_patterns.contains(&pattern) {
+                self.text_patterns.push(pattern);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for BoilerplatePack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoilerplatePack")
+            .field("language", &self.language)
+            .field("navigation_terms", &self.navigation_terms.len())
+            .field("cookie_phrases", &self.cookie_phrases.len())
+            .field("subscription_phrases", &self.subscription_phrases.len())
+            .field("share_prompts", &self.share_prompts.len())
+            .field("text_patterns", &self.text_patterns.len())
+            .finish()
+    }
+}
+
+/// Boilerplate packs keyed by language.
+///
+/// The default registry holds the built-in packs. Registering a pack for a
+/// language that already has one adds its phrases to the existing pack.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::{BoilerplatePack, BoilerplatePacks};
+///
+/// let mut packs = BoilerplatePacks::builtin();
+/// packs.register(BoilerplatePack::new("es").navigation_term("Portada del día"));
+/// packs.register(BoilerplatePack::new("nl").share_prompt("Deel dit artikel"));
+///
+/// assert!(packs.find("es-MX").unwrap().is_boilerplate_line("Portada del día"));
+/// assert!(packs.find("es").unwrap().is_boilerplate_line("Suscríbete"));
+/// assert!(packs.languages().contains(&"nl"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoilerplatePacks {
+    packs: Vec<BoilerplatePack>,
+}
+
+impl Default for BoilerplatePacks {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl BoilerplatePacks {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self { packs: Vec::new() }
+    }
+
+    /// Creates a registry containing the built-in packs.
+    pub fn builtin() -> Self {
+        Self {
+            packs: builtin_packs(),
+        }
+    }
+
+    /// Registers a pack, merging it into the existing pack for its language.
+    pub fn register(&mut self, pack: BoilerplatePack) {
+        match self
+            .packs
+            .iter_mut()
+            .find(|existing| existing.language == pack.language)
+        {
+            Some(existing) => existing.merge(pack),
+            None => self.packs.push(pack),
+        }
+    }
+
+    /// Returns the pack for a BCP 47 language tag, matched on its primary subtag.
+    pub fn find(&self, language: &str) -> Option<&BoilerplatePack> {
+        let language = primary_subtag(language);
+        self.packs.iter().find(|pack| pack.language == language)
+    }
+
+    /// Returns the languages that have a pack, in registration order.
+    pub fn languages(&self) -> Vec<&str> {
+        self.packs.iter().map(|pack| pack.language()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.packs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.packs.is_empty()
+    }
+}
+
+/// Selects which [`BoilerplatePack`] is applied to a page.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum BoilerplateLanguage {
+    /// Use the pack for the detected page language.
+    #[default]
+    Auto,
+    /// Always use the pack for this language tag, e.g. `"de"`.
+    Fixed(String),
+    /// Apply no pack.
+    Disabled,
+}
+
+/// Lowercases a line and strips Markdown list, quote and emphasis markers and
+/// trailing punctuation.
+fn normalize_line(line: &str) -> String {
+    let line = line
+        .trim()
+        .trim_start_matches(['-', '+', '>', ' '])
+        .trim_matches(['*', '_', '~', ' '])
+        .trim_end_matches([':', '.', '!', '?', '…', '»', '›', '→', ' '])
+        .trim_matches(['*', '_', '~', ' ']);
+    line.to_lowercase()
+}
+
+fn starts_with_phrase(line: &str, phrase: &str) -> bool {
+    line.strip_prefix(phrase)
+        .is_some_and(|rest| !rest.starts_with(char::is_alphanumeric))
+}
+
+fn push_phrase(phrases: &mut Vec<String>, phrase: String) {
+    let phrase = normalize_line(&phrase);
+    if !phrase.is_empty() && !phrases.contains(&phrase) {
+        phrases.push(phrase);
+    }
+}
+
+fn primary_subtag(language: &str) -> String {
+    language
+        .trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+fn pack(
+    language: &str,
+    navigation_terms: &[&str],
+    cookie_phrases: &[&str],
+    subscription_phrases: &[&str],
+    share_prompts: &[&str],
+) -> BoilerplatePack {
+    let mut pack = BoilerplatePack::new(language);
+    for term in navigation_terms {
+        pack = pack.navigation_term(*term);
+    }
+    for phrase in cookie_phrases {
+        pack = pack.cookie_phrase(*phrase);
+    }
+    for phrase in subscription_phrases {
+        pack = pack.subscription_phrase(*phrase);
+    }
+    for phrase in share_prompts {
+        pack = pack.share_prompt(*phrase);
+    }
+    pack
+}
+
+fn builtin_packs() -> Vec<BoilerplatePack> {
+    vec![
+        pack(
+            "en",
+            &[
+                "skip to content",
+                "skip to main content",
+                "back to top",
+                "toggle navigation",
+                "main menu",
+                "sign in",
+                "log in",
+                "sign up",
+                "read next",
+            ],
+            &[
+                "we use cookies",
+                "this website uses cookies",
+                "this site uses cookies",
+                "accept all cookies",
+                "accept cookies",
+                "manage cookies",
+                "cookie settings",
+                "manage consent",
+            ],
+            &[
+                "subscribe to our newsletter",
+                "sign up for our newsletter",
+                "subscribe now",
+                "join our mailing list",
+                "get the latest news",
+            ],
+            &[
+                "share this article",
+                "share this story",
+                "share on facebook",
+                "share on twitter",
+                "share on linkedin",
+                "share via email",
+            ],
+        ),
+        pack(
+            "pt",
+            &[
+                "início",
+                "página inicial",
+                "contato",
+                "contacto",
+                "buscar",
+                "pesquisar",
+                "entrar",
+                "voltar ao topo",
+                "ir para o conteúdo",
+                "leia também",
+                "mais lidas",
+                "anterior",
+                "próximo",
+            ],
+            &[
+                "utilizamos cookies",
+                "usamos cookies",
+                "este site utiliza cookies",
+                "aceitar cookies",
+                "aceitar todos os cookies",
+                "política de cookies",
+                "gerenciar cookies",
+            ],
+            &[
+                "inscreva-se",
+                "assine nossa newsletter",
+                "assine já",
+                "receba as notícias",
+                "mantenha-se informado",
+            ],
+            &[
+                "compartilhe",
+                "compartilhar",
+                "compartilhe no facebook",
+                "compartilhe no whatsapp",
+            ],
+        )
+        .text_pattern(
+            r"(?i)\b(créditos|tópicos|inscreva-se|mantenha-se informado|acesso livre|editor/a)\b",
+        )
+        .text_pattern(r"(?i)\bver tópicos\b")
+        .text_pattern(r"(?i)\bimagem do banner\b")
+        .text_pattern(r"(?i)\bfoto:\b.*$"),
+        pack(
+            "es",
+            &[
+                "inicio",
+                "portada",
+                "contacto",
+                "buscar",
+                "iniciar sesión",
+                "menú",
+                "volver arriba",
+                "ir al contenido",
+                "anterior",
+                "siguiente",
+                "lo más leído",
+            ],
+            &[
+                "utilizamos cookies",
+                "usamos cookies",
+                "este sitio web utiliza cookies",
+                "aceptar cookies",
+                "aceptar todas las cookies",
+                "configurar cookies",
+                "política de cookies",
+            ],
+            &[
+                "suscríbete",
+                "suscríbase",
+                "recibe nuestro boletín",
+                "date de alta",
+            ],
+            &[
+                "compartir",
+                "comparte este artículo",
+                "compartir en facebook",
+                "compartir en twitter",
+                "compartir en whatsapp",
+            ],
+        ),
+        pack(
+            "fr",
+            &[
+                "accueil",
+                "rechercher",
+                "connexion",
+                "se connecter",
+                "retour en haut",
+                "aller au contenu",
+                "précédent",
+                "suivant",
+                "à lire aussi",
+                "lire aussi",
+            ],
+            &[
+                "nous utilisons des cookies",
+                "ce site utilise des cookies",
+                "accepter les cookies",
+                "paramétrer les cookies",
+                "gérer les cookies",
+                "politique de cookies",
+            ],
+            &[
+                "abonnez-vous",
+                "inscrivez-vous à notre newsletter",
+                "recevez notre newsletter",
+                "s'abonner",
+            ],
+            &[
+                "partager",
+                "partager cet article",
+                "partager sur facebook",
+                "partager sur twitter",
+                "partager par e-mail",
+            ],
+        ),
+        pack(
+            "de",
+            &[
+                "startseite",
+                "kontakt",
+                "suche",
+                "suchen",
+                "anmelden",
+                "menü",
+                "nach oben",
+                "zum inhalt springen",
+                "zurück",
+                "weiter",
+                "mehr zum thema",
+            ],
+            &[
+                "wir verwenden cookies",
+                "diese website verwendet cookies",
+                "alle cookies akzeptieren",
+                "cookies akzeptieren",
+                "cookie-einstellungen",
+            ],
+            &[
+                "newsletter abonnieren",
+                "jetzt abonnieren",
+                "abonnieren sie unseren newsletter",
+                "zum newsletter anmelden",
+            ],
+            &[
+                "teilen",
+                "artikel teilen",
+                "auf facebook teilen",
+                "auf twitter teilen",
+                "per e-mail teilen",
+            ],
+        ),
+        pack(
+            "it",
+            &[
+                "contatti",
+                "cerca",
+                "accedi",
+                "torna su",
+                "vai al contenuto",
+                "precedente",
+                "successivo",
+                "leggi anche",
+            ],
+            &[
+                "utilizziamo i cookie",
+                "questo sito utilizza cookie",
+                "accetta tutti i cookie",
+                "accetta i cookie",
+                "gestisci i cookie",
+                "informativa sui cookie",
+            ],
+            &[
+                "iscriviti alla newsletter",
+                "iscriviti",
+                "abbonati",
+                "ricevi la nostra newsletter",
+            ],
+            &[
+                "condividi",
+                "condividi su facebook",
+                "condividi su twitter",
+                "condividi su whatsapp",
+            ],
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_packs_match_whole_lines_only() {
+        let german = BoilerplatePack::builtin("de-AT").unwrap();
+        assert_eq!(german.language(), "de");
+
+        assert!(german.is_boilerplate_line("Startseite"));
+        assert!(german.is_boilerplate_line("- **Newsletter abonnieren**"));
+        assert!(german.is_boilerplate_line("Teilen:"));
+        assert!(german.is_boilerplate_line(
+            "Wir verwenden Cookies, um Ihnen das beste Nutzererlebnis zu bieten."
+        ));
+
+        assert!(!german.is_boilerplate_line("Startseiten werden oft überladen."));
+        assert!(!german.is_boilerplate_line("Teilnehmer diskutierten bis spät in die Nacht."));
+        assert!(!german.is_boilerplate_line(
+            "Teilen wir die Ergebnisse, so zeigt sich, dass die Studie in fast allen \
+             Regionen Europas zu vergleichbaren Schlüssen kommt."
+        ));
+    }
+
+    #[test]
+    fn test_empty_lines_and_phrases() {
+        let english = BoilerplatePack::builtin("en").unwrap();
+        for line in ["", "   ", "- ", "**", "> _ _"] {
+            assert!(!english.is_boilerplate_line(line), "{:?}", line);
+        }
+
+        // A cookie phrase inside a long paragraph is content, not a banner
+        let paragraph = format!("We use cookies {}", "and more words ".repeat(30));
+        assert!(!english.is_boilerplate_line(&paragraph));
+
+        let pack = BoilerplatePack::new("")
+            .navigation_term("")
+            .cookie_phrase("  ** ")
+            .share_prompt("…");
+        assert_eq!(pack.language(), "");
+        assert!(pack.navigation_terms().is_empty());
+        assert!(pack.cookie_phrases().is_empty());
+        assert!(pack.share_prompts().is_empty());
+        assert!(!pack.is_boilerplate_line("Home"));
+    }
+
+    #[test]
+    fn test_builtin_pack_languages() {
+        let packs = BoilerplatePacks::builtin();
+        assert_eq!(packs.languages(), vec!["en", "pt", "es", "fr", "de", "it"]);
+        assert!(packs.find("ja").is_none());
+        assert!(packs.find("").is_none());
+        assert_eq!(packs.find("pt_BR").unwrap().language(), "pt");
+        assert!(BoilerplatePack::builtin("ja").is_none());
+        assert!(!packs.find("pt-BR").unwrap().text_patterns().is_empty());
+    }
+
+    #[test]
+    fn test_register_merges_into_existing_pack() {
+        let mut packs = BoilerplatePacks::new();
+        packs.register(BoilerplatePack::new("fr").navigation_term("Accueil"));
+        packs.register(
+            BoilerplatePack::new("FR-ca")
+                .navigation_term("accueil")
+                .share_prompt("Partagez"),
+        );
+
+        assert_eq!(packs.len(), 1);
+        let french = packs.find("fr").unwrap();
+        assert_eq!(french.navigation_terms(), ["accueil"]);
+        assert_eq!(french.share_prompts(), ["partagez"]);
+    }
+}
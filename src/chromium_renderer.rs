@@ -0,0 +1,567 @@
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, ErrorKind};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::{Value, json};
+use tokio_util::sync::CancellationToken;
+use tungstenite::{Message, WebSocket};
+
+use crate::http_client::FetchedPage;
+use crate::renderer::{RenderError, Renderer};
+
+/// Executables looked up on the `PATH` when no executable is configured.
+const CHROMIUM_EXECUTABLES: [&str; 5] = [
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+    "chrome",
+];
+
+/// Environment variable naming the browser executable.
+const CHROME_PATH_VARIABLE: &str = "CHROME_PATH";
+
+/// Default time allowed for launching the browser and rendering a page.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time without network requests after which the network counts as idle.
+const NETWORK_IDLE_QUIET: Duration = Duration::from_millis(500);
+
+/// Interval between checks for the selector of [`WaitCondition::Selector`].
+const SELECTOR_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Longest wait on the browser before checking whether rendering was cancelled.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Distinguishes the profile directories of renderings running at once.
+static PROFILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// When a page counts as rendered.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum WaitCondition {
+    /// The page's `load` event has fired.
+    Load,
+    /// The page has loaded and made no network request for 500 ms.
+    #[default]
+    NetworkIdle,
+    /// An element matches this CSS selector.
+    Selector(String),
+}
+
+/// A [`Renderer`] driving a locally installed headless Chromium or Chrome
+/// through the DevTools protocol.
+///
+/// Each page is rendered in a fresh browser with a temporary profile, which is
+/// closed and removed afterwards. The browser is the one named by the
+/// `CHROME_PATH` environment variable, or the first of `chromium`,
+/// `chromium-browser`, `google-chrome`, `google-chrome-stable` and `chrome`
+/// found on the `PATH`, unless set with [`executable`](Self::executable).
+///
+/// Requires the `chromium` feature.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use markdown_harvest::{ChromiumRenderer, HarvestConfig, MarkdownHarvester, RenderPolicy, WaitCondition};
+/// use std::time::Duration;
+///
+/// let renderer = ChromiumRenderer::new()
+///     .wait_for(WaitCondition::Selector("main article".to_string()))
+///     .timeout(Duration::from_secs(15));
+/// let config = HarvestConfig::builder()
+///     .renderer(renderer)
+///     .render_policy(RenderPolicy::IfThin(300))
+///     .build();
+///
+/// let text = "https://spa.example.com/posts/42";
+/// for document in MarkdownHarvester::get_hyperlinks_documents(text.to_string(), config).into_iter().flatten() {
+///     println!("{:?}: {}", document.rendered_by, document.markdown);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChromiumRenderer {
+    executable: Option<PathBuf>,
+    wait_condition: WaitCondition,
+    timeout: Duration,
+}
+
+impl Default for ChromiumRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChromiumRenderer {
+    /// Creates a renderer waiting for the network to go idle, for at most 30 seconds.
+    pub fn new() -> Self {
+        Self {
+            executable: None,
+            wait_condition: WaitCondition::default(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sets the browser executable.
+    pub fn executable(mut self, executable: impl Into<PathBuf>) -> Self {
+        self.executable = Some(executable.into());
+        self
+    }
+
+    /// Sets when a page counts as rendered.
+    pub fn wait_for(mut self, wait_condition: WaitCondition) -> Self {
+        self.wait_condition = wait_condition;
+        self
+    }
+
+    /// Sets the time allowed for launching the browser and rendering a page.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn wait_condition(&self) -> &WaitCondition {
+        &self.wait_condition
+    }
+
+    fn find_executable(&self) -> Option<PathBuf> {
+        if let Some(executable) = &self.executable {
+            return Some(executable.clone());
+        }
+        if let Some(executable) = env::var_os(CHROME_PATH_VARIABLE) {
+            return Some(PathBuf::from(executable));
+        }
+        let path = env::var_os("PATH")?;
+        env::split_paths(&path)
+            .flat_map(|directory| CHROMIUM_EXECUTABLES.map(|name| directory.join(name)))
+            .find(|candidate| candidate.is_file())
+    }
+}
+
+impl Renderer for ChromiumRenderer {
+    fn name(&self) -> &str {
+        "chromium"
+    }
+
+    fn render(
+        &self,
+        page: &FetchedPage,
+        cancel: &CancellationToken,
+    ) -> Result<Option<String>, RenderError> {
+        let deadline = Instant::now() + self.timeout;
+        let executable = self
+            .find_executable()
+            .ok_or_else(|| RenderError::Launch("no Chromium executable found".to_string()))?;
+
+        let browser = Browser::launch(&executable, deadline, cancel)?;
+        let mut devtools = DevTools::connect(&browser.websocket_url, deadline, cancel.clone())?;
+        let html = devtools.render(&page.final_url, &self.wait_condition);
+        // Best effort: the process is killed when `browser` is dropped anyway
+        let _ = devtools.call("Browser.close", json!({}));
+        html.map(Some)
+    }
+}
+
+/// A running browser process and its temporary profile, both cleaned up on drop.
+struct Browser {
+    process: Child,
+    profile: PathBuf,
+    websocket_url: String,
+}
+
+impl Browser {
+    fn launch(
+        executable: &Path,
+        deadline: Instant,
+        cancel: &CancellationToken,
+    ) -> Result<Self, RenderError> {
+        let profile = env::temp_dir().join(format!(
+            "markdown-harvest-chromium-{}-{}",
+            std::process::id(),
+            PROFILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let mut process = Command::new(executable)
+            .arg("--headless=new")
+            .arg("--disable-gpu")
+            .arg("--no-first-run")
+            .arg("--no-default-browser-check")
+            .arg("--remote-debugging-port=0")
+            .arg(format!("--user-data-dir={}", profile.display()))
+            .arg("about:blank")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| RenderError::Launch(format!("{}: {}", executable.display(), e)))?;
+
+        // The DevTools endpoint is announced on stderr, which must keep being
+        // drained so the browser never blocks on a full pipe
+        let (sender, receiver) = mpsc::channel();
+        if let Some(stderr) = process.stderr.take() {
+            thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    if let Some(url) = devtools_url(&line) {
+                        let _ = sender.send(url.to_string());
+                    }
+                }
+            });
+        }
+
+        let mut browser = Browser {
+            process,
+            profile,
+            websocket_url: String::new(),
+        };
+        browser.websocket_url = loop {
+            if cancel.is_cancelled() {
+                return Err(RenderError::Cancelled);
+            }
+            let wait = deadline.saturating_duration_since(Instant::now());
+            match receiver.recv_timeout(wait.min(CANCEL_POLL_INTERVAL)) {
+                Ok(url) => break url,
+                Err(RecvTimeoutError::Timeout) if Instant::now() < deadline => continue,
+                Err(_) => {
+                    return Err(match browser.process.try_wait() {
+                        Ok(Some(status)) => {
+                            RenderError::Launch(format!("browser exited with {}", status))
+                        }
+                        _ => RenderError::Timeout,
+                    });
+                }
+            }
+        };
+        Ok(browser)
+    }
+}
+
+impl Drop for Browser {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+        let _ = fs::remove_dir_all(&self.profile);
+    }
+}
+
+/// Returns the WebSocket URL from the line the browser prints once its
+/// DevTools endpoint is listening.
+fn devtools_url(line: &str) -> Option<&str> {
+    line.trim()
+        .strip_prefix("DevTools listening on ")
+        .filter(|url| url.starts_with("ws://"))
+}
+
+/// A DevTools protocol connection, attached to one page once rendering starts.
+struct DevTools {
+    socket: WebSocket<TcpStream>,
+    deadline: Instant,
+    cancel: CancellationToken,
+    next_id: u64,
+    session_id: Option<String>,
+    /// Events received while waiting for a command's response.
+    events: Vec<Value>,
+}
+
+impl DevTools {
+    fn connect(
+        websocket_url: &str,
+        deadline: Instant,
+        cancel: CancellationToken,
+    ) -> Result<Self, RenderError> {
+        let address = websocket_url
+            .strip_prefix("ws://")
+            .and_then(|rest| rest.split('/').next())
+            .ok_or_else(|| RenderError::Protocol(format!("invalid endpoint {}", websocket_url)))?;
+        let stream = TcpStream::connect(address).map_err(protocol_error)?;
+        stream
+            .set_read_timeout(Some(remaining(deadline)?))
+            .map_err(protocol_error)?;
+        let (socket, _) = tungstenite::client(websocket_url, stream).map_err(protocol_error)?;
+
+        Ok(Self {
+            socket,
+            deadline,
+            cancel,
+            next_id: 0,
+            session_id: None,
+            events: Vec::new(),
+        })
+    }
+
+    /// Opens `url` in a new page, waits for `wait_condition` and returns the
+    /// page's HTML.
+    fn render(&mut self, url: &str, wait_condition: &WaitCondition) -> Result<String, RenderError> {
+        let target = self.call("Target.createTarget", json!({ "url": "about:blank" }))?;
+        let target_id = target["targetId"].clone();
+        let session = self.call(
+            "Target.attachToTarget",
+            json!({ "targetId": target_id, "flatten": true }),
+        )?;
+        self.session_id = session["sessionId"].as_str().map(str::to_string);
+
+        self.call("Page.enable", json!({}))?;
+        if *wait_condition == WaitCondition::NetworkIdle {
+            self.call("Network.enable", json!({}))?;
+        }
+        self.events.clear();
+        let navigation = self.call("Page.navigate", json!({ "url": url }))?;
+        if let Some(error) = navigation["errorText"].as_str() {
+            return Err(RenderError::Protocol(format!("{}: {}", url, error)));
+        }
+
+        match wait_condition {
+            WaitCondition::Load => self.wait_for_event("Page.loadEventFired")?,
+            WaitCondition::NetworkIdle => self.wait_for_network_idle()?,
+            WaitCondition::Selector(selector) => self.wait_for_selector(selector)?,
+        }
+
+        self.evaluate("document.documentElement.outerHTML")?
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| RenderError::Protocol("page returned no HTML".to_string()))
+    }
+
+    /// Sends a command and returns its result.
+    fn call(&mut self, method: &str, params: Value) -> Result<Value, RenderError> {
+        self.next_id += 1;
+        let id = self.next_id;
+        let mut command = json!({ "id": id, "method": method, "params": params });
+        if let Some(session_id) = &self.session_id {
+            command["sessionId"] = json!(session_id);
+        }
+        self.socket
+            .send(Message::text(command.to_string()))
+            .map_err(protocol_error)?;
+
+        loop {
+            let message = self
+                .next_message(self.deadline)?
+                .ok_or(RenderError::Timeout)?;
+            if message["id"].as_u64() != Some(id) {
+                self.events.push(message);
+                continue;
+            }
+            if let Some(error) = message.get("error") {
+                let error = error["message"].as_str().unwrap_or("unknown error");
+                return Err(RenderError::Protocol(format!("{}: {}", method, error)));
+            }
+            return Ok(message["result"].clone());
+        }
+    }
+
+    /// Reads the next message, or `None` once `until` has passed.
+    fn next_message(&mut self, until: Instant) -> Result<Option<Value>, RenderError> {
+        loop {
+            if self.cancel.is_cancelled() {
+                return Err(RenderError::Cancelled);
+            }
+            let Ok(wait) = remaining(until) else {
+                return Ok(None);
+            };
+            self.socket
+                .get_ref()
+                .set_read_timeout(Some(wait.min(CANCEL_POLL_INTERVAL)))
+                .map_err(protocol_error)?;
+            match self.socket.read() {
+                Ok(Message::Text(text)) => {
+                    return serde_json::from_str(&text)
+                        .map(Some)
+                        .map_err(protocol_error);
+                }
+                Ok(_) => continue,
+                // Checks for cancellation, then waits again until `until`
+                Err(tungstenite::Error::Io(error))
+                    if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+                {
+                    continue;
+                }
+                Err(error) => return Err(protocol_error(error)),
+            }
+        }
+    }
+
+    /// Takes the next event, buffered or received, before the deadline.
+    fn next_event(&mut self, until: Instant) -> Result<Option<Value>, RenderError> {
+        if !self.events.is_empty() {
+            return Ok(Some(self.events.remove(0)));
+        }
+        self.next_message(until)
+    }
+
+    fn wait_for_event(&mut self, method: &str) -> Result<(), RenderError> {
+        loop {
+            let event = self
+                .next_event(self.deadline)?
+                .ok_or(RenderError::Timeout)?;
+            if event["method"] == method {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Waits for the `load` event, then for no request to be in flight for
+    /// [`NETWORK_IDLE_QUIET`].
+    fn wait_for_network_idle(&mut self) -> Result<(), RenderError> {
+        let mut in_flight = HashSet::new();
+        let mut loaded = false;
+        let mut quiet_since = Instant::now();
+
+        loop {
+            let idle_at = quiet_since + NETWORK_IDLE_QUIET;
+            if loaded && in_flight.is_empty() && Instant::now() >= idle_at {
+                return Ok(());
+            }
+            if Instant::now() >= self.deadline {
+                return Err(RenderError::Timeout);
+            }
+            let until = if loaded && in_flight.is_empty() {
+                idle_at.min(self.deadline)
+            } else {
+                self.deadline
+            };
+            let Some(event) = self.next_event(until)? else {
+                continue;
+            };
+
+            let request_id = event["params"]["requestId"].as_str().map(str::to_string);
+            match (event["method"].as_str(), request_id) {
+                (Some("Page.loadEventFired"), _) => loaded = true,
+                (Some("Network.requestWillBeSent"), Some(request_id)) => {
+                    in_flight.insert(request_id);
+                }
+                (Some("Network.loadingFinished" | "Network.loadingFailed"), Some(request_id)) => {
+                    in_flight.remove(&request_id);
+                }
+                _ => continue,
+            }
+            quiet_since = Instant::now();
+        }
+    }
+
+    fn wait_for_selector(&mut self, selector: &str) -> Result<(), RenderError> {
+        let expression = format!("document.querySelector({}) !== null", json!(selector));
+        loop {
+            if self.evaluate(&expression)? == Value::Bool(true) {
+                return Ok(());
+            }
+            let poll_until = (Instant::now() + SELECTOR_POLL_INTERVAL).min(self.deadline);
+            while self.next_message(poll_until)?.is_some() {}
+            if Instant::now() >= self.deadline {
+                return Err(RenderError::Timeout);
+            }
+        }
+    }
+
+    /// Evaluates a JavaScript expression in the page and returns its value.
+    fn evaluate(&mut self, expression: &str) -> Result<Value, RenderError> {
+        let result = self.call(
+            "Runtime.evaluate",
+            json!({ "expression": expression, "returnByValue": true }),
+        )?;
+        if let Some(exception) = result.get("exceptionDetails") {
+            let text = exception["text"].as_str().unwrap_or("exception");
+            return Err(RenderError::Protocol(text.to_string()));
+        }
+        Ok(result["result"]["value"].clone())
+    }
+}
+
+/// Time left until `deadline`, or a timeout error once it has passed.
+fn remaining(deadline: Instant) -> Result<Duration, RenderError> {
+    let left = deadline.saturating_duration_since(Instant::now());
+    if left.is_zero() {
+        Err(RenderError::Timeout)
+    } else {
+        Ok(left)
+    }
+}
+
+fn protocol_error(error: impl std::fmt::Display) -> RenderError {
+    RenderError::Protocol(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_client::test_page;
+
+    #[test]
+    fn test_devtools_url() {
+        assert_eq!(
+            devtools_url("DevTools listening on ws://127.0.0.1:40321/devtools/browser/8f2c\n"),
+            Some("ws://127.0.0.1:40321/devtools/browser/8f2c")
+        );
+        assert_eq!(
+            devtools_url("[0101/000000.000:WARNING] something else"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_chromium_renderer_reports_launch_errors() {
+        let renderer = ChromiumRenderer::new()
+            .executable("/nonexistent/chromium")
+            .wait_for(WaitCondition::Load)
+            .timeout(Duration::from_secs(2));
+        assert_eq!(renderer.wait_condition(), &WaitCondition::Load);
+
+        let page = test_page("https://example.com/", "text/html", "");
+        match renderer.render(&page, &CancellationToken::new()) {
+            Err(RenderError::Launch(message)) => assert!(message.contains("/nonexistent/chromium")),
+            other => panic!("expected a launch error, got {:?}", other),
+        }
+    }
+
+    /// Answers DevTools commands like a browser whose page loads at once.
+    fn serve_devtools(listener: std::net::TcpListener) {
+        let (stream, _) = listener.accept().unwrap();
+        let mut socket = tungstenite::accept(stream).unwrap();
+        while let Ok(Message::Text(text)) = socket.read() {
+            let command: Value = serde_json::from_str(&text).unwrap();
+            let result = match command["method"].as_str().unwrap() {
+                "Target.createTarget" => json!({ "targetId": "T1" }),
+                "Target.attachToTarget" => json!({ "sessionId": "S1" }),
+                "Page.navigate" => {
+                    let event = json!({ "method": "Page.loadEventFired", "sessionId": "S1" });
+                    socket.send(Message::text(event.to_string())).unwrap();
+                    json!({ "frameId": "F1" })
+                }
+                "Runtime.evaluate" => {
+                    json!({ "result": { "type": "string", "value": "<html><body>Rendered</body></html>" } })
+                }
+                _ => json!({}),
+            };
+            let response = json!({ "id": command["id"], "result": result });
+            socket.send(Message::text(response.to_string())).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_devtools_renders_page() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || serve_devtools(listener));
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let websocket_url = format!("ws://{}/devtools/browser/test", address);
+        let cancel = CancellationToken::new();
+        let mut devtools = DevTools::connect(&websocket_url, deadline, cancel.clone()).unwrap();
+        let html = devtools.render("https://app.example.com/", &WaitCondition::Load);
+        assert_eq!(html.unwrap(), "<html><body>Rendered</body></html>");
+        assert_eq!(devtools.session_id.as_deref(), Some("S1"));
+
+        // A cancelled rendering stops waiting on the browser
+        cancel.cancel();
+        let html = devtools.render("https://app.example.com/", &WaitCondition::Load);
+        assert_eq!(html, Err(RenderError::Cancelled));
+
+        drop(devtools);
+        server.join().unwrap();
+    }
+}
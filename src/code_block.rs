@@ -0,0 +1,264 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use scraper::node::Text;
+use scraper::{ElementRef, Html, Node, Selector};
+
+/// Prefix of the placeholder that stands in for a code block while the page goes
+/// through Markdown conversion and text cleanup. It contains no characters that
+/// html2md escapes and survives every line filter.
+const PLACEHOLDER_PREFIX: &str = "MDHARVESTCODEBLOCK";
+
+/// Matches a placeholder together with the fence html2md may have put around it.
+static PLACEHOLDER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"(?:```[^\n]*\n)?{}(\d+)(?:\n```)?",
+        PLACEHOLDER_PREFIX
+    ))
+    .unwrap()
+});
+
+/// Class name patterns that carry the language of a highlighted code block.
+static LANGUAGE_CLASS_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:language|lang|highlight-source|highlight|brush)-([A-Za-z0-9_+#.-]+)$").unwrap()
+});
+
+/// Class tokens of line-number gutters emitted by syntax highlighters.
+const GUTTER_CLASSES: [&str; 6] = [
+    "lineno",
+    "linenos",
+    "line-number",
+    "line-numbers",
+    "line-numbers-rows",
+    "gutter",
+];
+
+/// A code block lifted out of the document before Markdown conversion.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CodeBlock {
+    language: Option<String>,
+    code: String,
+}
+
+impl CodeBlock {
+    /// Renders the block as a fenced Markdown code block.
+    ///
+    /// The fence is made longer than any run of backticks inside the code.
+    fn to_markdown(&self) -> String {
+        let longest_backtick_run = self
+            .code
+            .split(|c| c != '`')
+            .map(str::len)
+            .max()
+            .unwrap_or(0);
+        let fence = "`".repeat(longest_backtick_run.max(2) + 1);
+
+        format!(
+            "{}{}\n{}\n{}",
+            fence,
+            self.language.as_deref().unwrap_or(""),
+            self.code,
+            fence
+        )
+    }
+}
+
+/// Replaces the contents of every `<pre>` element with a placeholder and returns
+/// the code they contained.
+///
+/// Syntax-highlighter markup is flattened to plain text, line-number gutters are
+/// dropped and the language is detected from `language-*`, `lang-*` and
+/// `highlight-*` classes or a `data-lang` attribute on the block or its wrapper.
+/// The `<pre>` element itself is kept so content selection still finds it.
+pub(crate) fn extract_code_blocks(document: &mut Html) -> Vec<CodeBlock> {
+    let pre_selector = Selector::parse("pre").unwrap();
+
+    let found: Vec<_> = document
+        .root_element()
+        .select(&pre_selector)
+        // Nested <pre> elements are flattened as part of their outermost ancestor
+        .filter(|pre| {
+            !pre.ancestors()
+                .filter_map(ElementRef::wrap)
+                .any(|ancestor| ancestor.value().name() == "pre")
+        })
+        .map(|pre| {
+            let block = CodeBlock {
+                language: detect_language(pre),
+                code: flatten_code_text(pre).trim_matches('\n').to_string(),
+            };
+            let children: Vec<_> = pre.children().map(|child| child.id()).collect();
+            (pre.id(), children, block)
+        })
+        .collect();
+
+    let mut blocks = Vec::with_capacity(found.len());
+    for (pre_id, children, block) in found {
+        for child_id in children {
+            if let Some(mut child) = document.tree.get_mut(child_id) {
+                child.detach();
+            }
+        }
+        if let Some(mut pre) = document.tree.get_mut(pre_id) {
+            let placeholder = format!("{}{}", PLACEHOLDER_PREFIX, blocks.len());
+            pre.append(Node::Text(Text {
+                text: placeholder.as_str().into(),
+            }));
+        }
+        blocks.push(block);
+    }
+
+    blocks
+}
+
+/// Substitutes the placeholders in `markdown` with fenced code blocks.
+///
+/// Placeholders whose block index is unknown are removed.
+pub(crate) fn restore_code_blocks(markdown: &str, blocks: &[CodeBlock]) -> String {
+    if blocks.is_empty() {
+        return markdown.to_string();
+    }
+
+    PLACEHOLDER_REGEX
+        .replace_all(markdown, |caps: &Captures| {
+            caps[1]
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| blocks.get(index))
+                .map(CodeBlock::to_markdown)
+                .unwrap_or_default()
+        })
+        .to_string()
+}
+
+/// Looks for a language class on the block, its `<code>` child and its wrapper.
+fn detect_language(pre: ElementRef) -> Option<String> {
+    let code_selector = Selector::parse("code").unwrap();
+
+    let candidates = std::iter::once(pre)
+        .chain(pre.select(&code_selector).take(1))
+        .chain(pre.parent().and_then(ElementRef::wrap));
+
+    for element in candidates {
+        if let Some(language) = element.value().attr("data-lang") {
+            let language = language.trim().to_lowercase();
+            if !language.is_empty() {
+                return Some(language);
+            }
+        }
+
+        for class in element.value().classes() {
+            if let Some(caps) = LANGUAGE_CLASS_REGEX.captures(class) {
+                let language = caps[1].to_lowercase();
+                if language != "rouge" && language != "none" && language != "plaintext" {
+                    return Some(language);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Concatenates the text of a code element, skipping line-number gutters and
+/// turning `<br>` into line breaks.
+fn flatten_code_text(element: ElementRef) -> String {
+    let mut code = String::new();
+    append_code_text(element, &mut code);
+    code
+}
+
+fn append_code_text(element: ElementRef, code: &mut String) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => code.push_str(text),
+            Node::Element(child_element) => {
+                if child_element.name() == "br" {
+                    code.push('\n');
+                } else if !child_element
+                    .classes()
+                    .any(|class| GUTTER_CLASSES.contains(&class))
+                    && let Some(child_ref) = ElementRef::wrap(child)
+                {
+                    append_code_text(child_ref, code);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(html: &str) -> (Vec<CodeBlock>, String) {
+        let mut document = Html::parse_document(html);
+        let blocks = extract_code_blocks(&mut document);
+        (blocks, document.root_element().html())
+    }
+
+    #[test]
+    fn test_extract_code_blocks_flattens_highlighter_spans() {
+        let (blocks, html) = extract(
+            r#"<pre><code class="language-rust"><span class="k">fn</span> <span class="nf">main</span>() {
+    <span class="k">let</span> x_y = <span class="mi">1</span> * 2;
+}</code></pre>"#,
+        );
+
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].language.as_deref(), Some("rust"));
+        assert_eq!(blocks[0].code, "fn main() {\n    let x_y = 1 * 2;\n}");
+        assert!(html.contains("<pre>MDHARVESTCODEBLOCK0</pre>"));
+    }
+
+    #[test]
+    fn test_extract_code_blocks_detects_wrapper_language() {
+        let (blocks, _) = extract(
+            r#"<div class="highlight highlight-source-python"><pre>print("hi")</pre></div>
+               <div class="language-ruby highlighter-rouge"><pre class="highlight"><code>puts 1</code></pre></div>
+               <pre data-lang="Go">fmt.Println()</pre>
+               <pre>plain text</pre>"#,
+        );
+
+        let languages: Vec<_> = blocks.iter().map(|b| b.language.as_deref()).collect();
+        assert_eq!(
+            languages,
+            vec![Some("python"), Some("ruby"), Some("go"), None]
+        );
+    }
+
+    #[test]
+    fn test_extract_code_blocks_skips_gutters_and_handles_br() {
+        let (blocks, _) = extract(
+            r#"<pre><span class="lineno">1</span>first<br><span class="lineno">2</span>second</pre>"#,
+        );
+        assert_eq!(blocks[0].code, "first\nsecond");
+    }
+
+    #[test]
+    fn test_restore_code_blocks() {
+        let blocks = vec![
+            CodeBlock {
+                language: Some("rust".to_string()),
+                code: "let a = 1;".to_string(),
+            },
+            CodeBlock {
+                language: None,
+                code: "uses ``` inside".to_string(),
+            },
+        ];
+
+        let markdown = "Intro\n\n```rust\nMDHARVESTCODEBLOCK0\n```\n\nMDHARVESTCODEBLOCK1\n\nMDHARVESTCODEBLOCK7";
+        let restored = restore_code_blocks(markdown, &blocks);
+
+        assert_eq!(
+            restored,
+            "Intro\n\n```rust\nlet a = 1;\n```\n\n````\nuses ``` inside\n````\n\n"
+        );
+    }
+
+    #[test]
+    fn test_restore_code_blocks_without_blocks() {
+        assert_eq!(restore_code_blocks("text", &[]), "text");
+    }
+}
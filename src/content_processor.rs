@@ -1,103 +1,363 @@
-use crate::patterns::{
-    additional_cleanup, content_selectors, media_elements, text_selectors, unwanted_elements,
-    unwanted_text_patterns,
+use crate::boilerplate::{BoilerplateLanguage, BoilerplatePack};
+use crate::code_block::{extract_code_blocks, restore_code_blocks};
+use crate::content_recovery::recover_hidden_content;
+use crate::embedded_content::{THIN_CONTENT_CHARS, extract_embedded_content, text_chars};
+use crate::extraction_config::ExtractionConfig;
+use crate::extraction_report::{
+    CleaningReport, CleaningRule, ExtractionReport, ExtractionStrategy, record_removal,
 };
+use crate::image_handler::{ImageMode, extract_images, restore_images};
+use crate::language::{DetectedLanguage, detect_language};
+use crate::link_handler::{
+    LinkMode, content_links, document_base_url, reference_definitions, rewrite_links,
+};
+use crate::page_metadata::{PageMetadata, extract_page_metadata};
+#[cfg(feature = "pdf")]
+use crate::pdf_converter::{PdfError, convert_pdf};
+use crate::plain_text::markdown_to_plain_text;
+use crate::site_extractor::{SiteExtractor, SiteExtractorRegistry};
+use crate::table_converter::{convert_tables, restore_tables, rewrite_around_tables};
+use crate::text_passthrough::{BodyKind, convert_text};
+
+use regex::{Captures, Regex};
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+
+/// Removed elements are reported by at most this many characters of their text.
+const REMOVAL_SUMMARY_CHARS: usize = 80;
 
-use regex::Regex;
-use scraper::{Html, Selector};
+/// Minimum share of a line's letters that text pattern matches must cover for
+/// the line to be removed.
+const PATTERN_MIN_COVERAGE: f64 = 0.4;
+
+/// Lines with more words than this are never removed by text patterns, so
+/// sentences that merely use a boilerplate word are kept intact.
+const PATTERN_MAX_WORDS: usize = 12;
 
 /// Component responsible for HTML cleaning and Markdown conversion.
 ///
 /// `ContentProcessor` handles all aspects of content processing including HTML parsing,
 /// content extraction, cleaning unwanted elements, and converting to Markdown format.
-/// This component reuses the original functions from MarkdownHarvester to maintain
-/// compatibility and behavior.
+/// The selectors and text filters it applies come from its [`ExtractionConfig`].
+/// When the page URL is known, registered [`SiteExtractor`]s are consulted before
+/// the generic main-content heuristics.
 #[derive(Default, Clone)]
-pub struct ContentProcessor {}
+pub struct ContentProcessor {
+    config: ExtractionConfig,
+    site_extractors: SiteExtractorRegistry,
+}
 
 impl ContentProcessor {
-    /// Creates a new ContentProcessor instance.
+    /// Creates a new ContentProcessor instance with the default extraction rules
+    /// and the built-in site extractors.
     pub fn new() -> Self {
-        Self {}
+        Self::with_config(ExtractionConfig::default())
+    }
+
+    /// Creates a ContentProcessor that applies the given extraction rules.
+    pub fn with_config(config: ExtractionConfig) -> Self {
+        Self {
+            config,
+            site_extractors: SiteExtractorRegistry::builtin(),
+        }
+    }
+
+    /// Returns the extraction rules used by this processor.
+    pub fn config(&self) -> &ExtractionConfig {
+        &self.config
+    }
+
+    /// Returns the site extractors consulted by this processor.
+    pub fn site_extractors(&self) -> &SiteExtractorRegistry {
+        &self.site_extractors
+    }
+
+    /// Replaces the site extractors, e.g. with [`SiteExtractorRegistry::new`] to
+    /// disable the built-in ones.
+    pub fn set_site_extractors(&mut self, site_extractors: SiteExtractorRegistry) {
+        self.site_extractors = site_extractors;
+    }
+
+    /// Registers a site extractor, giving it precedence over those already registered.
+    pub fn register_site_extractor(&mut self, extractor: impl SiteExtractor + 'static) {
+        self.site_extractors.register(extractor);
     }
 
     /// Converts HTML content to clean Markdown format.
     pub fn html_to_markdown(&self, html: &str) -> String {
-        extract_and_clean_content(html)
+        self.extract(html, None).markdown
     }
-}
 
-/// Extracts the main content from HTML using a priority-based strategy.
-///
-/// Priority order:
-/// 1. Semantic HTML5 tags (article, main, [role='main'])
-/// 2. Content-specific class selectors (.content, .article, .post, .entry)
-/// 3. Fallback to body tag
-///
-/// # Arguments
-/// * `document` - Parsed HTML document
-///
-/// # Returns
-/// * String containing the extracted HTML content
-fn extract_main_content(document: &Html) -> String {
-    // Priority 1: Semantic HTML5 tags
-    if let Some(content) = try_semantic_tags(document) {
-        return content;
+    /// Converts HTML content fetched from `url` to clean Markdown format.
+    ///
+    /// If a registered site extractor matches the URL, it selects the main content;
+    /// otherwise this behaves like [`html_to_markdown`](Self::html_to_markdown).
+    /// Relative links are resolved against the document's `<base href>` or `url`,
+    /// which should be the final URL after redirects.
+    pub fn html_to_markdown_with_url(&self, html: &str, url: &str) -> String {
+        self.extract(html, Some(url)).markdown
+    }
+
+    /// Converts HTML content to the [`OutputFormat`](crate::OutputFormat) set in the configuration.
+    pub fn html_to_output(&self, html: &str) -> String {
+        self.extract(html, None).content
+    }
+
+    /// Converts HTML content fetched from `url` to the configured [`OutputFormat`](crate::OutputFormat),
+    /// as [`html_to_markdown_with_url`](Self::html_to_markdown_with_url) does for Markdown.
+    pub fn html_to_output_with_url(&self, html: &str, url: &str) -> String {
+        self.extract(html, Some(url)).content
+    }
+
+    /// Converts HTML content to clean Markdown and collects the page metadata.
+    ///
+    /// `url`, when known, selects the site extractor and is used to resolve relative
+    /// links and metadata URLs, as in [`html_to_markdown_with_url`](Self::html_to_markdown_with_url).
+    pub fn extract(&self, html: &str, url: Option<&str>) -> ExtractedContent {
+        self.extract_with_content_language(html, url, None)
+    }
+
+    /// Converts a PDF document to Markdown.
+    ///
+    /// Headings, list items and paragraphs are rebuilt from the layout of the
+    /// text, and [`ExtractedContent::pages`] records where each page's content
+    /// lies in the Markdown. The title and author come from the document
+    /// information dictionary. Requires the `pdf` feature.
+    #[cfg(feature = "pdf")]
+    pub fn extract_pdf(&self, pdf: &[u8]) -> Result<ExtractedContent, PdfError> {
+        self.extract_pdf_with_content_language(pdf, None)
+    }
+
+    /// Like [`extract_pdf`](Self::extract_pdf), also taking the
+    /// `Content-Language` response header into account when detecting the
+    /// document language.
+    #[cfg(feature = "pdf")]
+    pub(crate) fn extract_pdf_with_content_language(
+        &self,
+        pdf: &[u8],
+        content_language: Option<&str>,
+    ) -> Result<ExtractedContent, PdfError> {
+        let pdf = convert_pdf(pdf)?;
+        let language = detect_language(
+            None,
+            content_language,
+            &markdown_to_plain_text(&pdf.markdown),
+        );
+        let metadata = PageMetadata {
+            title: pdf.title,
+            author: pdf.author,
+            ..PageMetadata::default()
+        };
+        Ok(self.converted_content(
+            pdf.markdown,
+            pdf.pages,
+            metadata,
+            language,
+            ExtractionStrategy::Pdf,
+        ))
     }
 
-    // Priority 2: Content selectors
-    if let Some(content) = try_content_selectors_direct(document) {
-        return content;
+    /// Converts a body of the given [`BodyKind`] to Markdown.
+    ///
+    /// HTML is extracted as by [`extract`](Self::extract). Markdown is passed
+    /// through with light normalization, plain text is wrapped as paragraphs
+    /// and source files are wrapped in a fenced code block, without any of the
+    /// HTML cleaning that would strip their code blocks and links. The title
+    /// comes from the front matter or first heading of Markdown, or from the
+    /// file name in `url` for source files.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_harvest::{BodyKind, ContentProcessor};
+    ///
+    /// let processor = ContentProcessor::new();
+    /// let extracted = processor.extract_as(
+    ///     "# Notes\n\nSee [the guide](https://example.com/guide).",
+    ///     BodyKind::Markdown,
+    ///     None,
+    /// );
+    ///
+    /// assert_eq!(extracted.markdown, "# Notes\n\nSee [the guide](https://example.com/guide).");
+    /// assert_eq!(extracted.metadata.title.as_deref(), Some("Notes"));
+    /// ```
+    pub fn extract_as(&self, body: &str, kind: BodyKind, url: Option<&str>) -> ExtractedContent {
+        self.extract_as_with_content_language(body, kind, url, None)
     }
 
-    // Priority 3: Fallback to body
-    fallback_to_body_tag(document)
+    /// Like [`extract_as`](Self::extract_as), also taking the
+    /// `Content-Language` response header into account when detecting the
+    /// document language.
+    pub(crate) fn extract_as_with_content_language(
+        &self,
+        body: &str,
+        kind: BodyKind,
+        url: Option<&str>,
+        content_language: Option<&str>,
+    ) -> ExtractedContent {
+        let strategy = match kind {
+            BodyKind::Html => {
+                return self.extract_with_content_language(body, url, content_language);
+            }
+            BodyKind::Markdown => ExtractionStrategy::Markdown,
+            BodyKind::PlainText => ExtractionStrategy::PlainText,
+            BodyKind::Source(_) => ExtractionStrategy::SourceCode,
+        };
+        let converted = convert_text(body, kind, url);
+        // Code says nothing about the language of the reader
+        let text = match kind {
+            BodyKind::Source(_) => String::new(),
+            _ => markdown_to_plain_text(&converted.markdown),
+        };
+        let language = detect_language(None, content_language, &text);
+        let metadata = PageMetadata {
+            title: converted.title,
+            ..PageMetadata::default()
+        };
+        let base_url = url.and_then(|url| Url::parse(url).ok());
+        let links = match kind {
+            BodyKind::Markdown => content_links(&converted.markdown, base_url.as_ref()),
+            _ => Vec::new(),
+        };
+        ExtractedContent {
+            links,
+            ..self.converted_content(converted.markdown, Vec::new(), metadata, language, strategy)
+        }
+    }
+
+    /// Wraps Markdown converted from a document that is not HTML, leading it
+    /// with the title when configured and shifting the page spans to match.
+    fn converted_content(
+        &self,
+        mut markdown: String,
+        mut pages: Vec<PageSpan>,
+        metadata: PageMetadata,
+        language: Option<DetectedLanguage>,
+        strategy: ExtractionStrategy,
+    ) -> ExtractedContent {
+        if self.config.inject_title()
+            && let Some(title) = &metadata.title
+        {
+            let length = markdown.len();
+            markdown = inject_title(markdown, title);
+            let shift = markdown.len() - length;
+            for page in &mut pages {
+                page.start += shift;
+                page.end += shift;
+            }
+        }
+
+        let report = ExtractionReport {
+            strategy: if markdown.is_empty() {
+                ExtractionStrategy::Empty
+            } else {
+                strategy
+            },
+            ..ExtractionReport::default()
+        };
+        ExtractedContent {
+            content: self.config.output_format().render(&markdown),
+            markdown,
+            metadata,
+            language,
+            report,
+            pages,
+            links: Vec::new(),
+        }
+    }
+
+    /// Like [`extract`](Self::extract), also taking the `Content-Language`
+    /// response header into account when detecting the page language.
+    pub(crate) fn extract_with_content_language(
+        &self,
+        html: &str,
+        url: Option<&str>,
+        content_language: Option<&str>,
+    ) -> ExtractedContent {
+        let site_extractor = url.and_then(|url| self.site_extractors.find_for_url(url));
+        extract_and_clean_content(html, &self.config, site_extractor, url, content_language)
+    }
 }
 
-/// Attempts to extract content from semantic HTML5 tags.
+/// The result of [`ContentProcessor::extract`]: the cleaned Markdown together
+/// with the metadata found in the page, its language and a report of how it
+/// was extracted.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExtractedContent {
+    pub markdown: String,
+    /// The content in the configured [`OutputFormat`](crate::OutputFormat); the same as `markdown`
+    /// for the Markdown formats.
+    pub content: String,
+    pub metadata: PageMetadata,
+    /// Detected from the page markup and the extracted text; `None` when the
+    /// page declares no language and has too little text to analyse.
+    pub language: Option<DetectedLanguage>,
+    pub report: ExtractionReport,
+    /// Where each page of a PDF document lies in `markdown`; empty otherwise.
+    pub pages: Vec<PageSpan>,
+    /// Absolute URLs of the links in the extracted content, whatever the
+    /// [`LinkMode`], in document order and without duplicates.
+    pub links: Vec<String>,
+}
+
+/// The part of the Markdown taken from one page of a paged document, such as a
+/// PDF, as a byte range. Lets chunks of the Markdown cite the page they come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageSpan {
+    /// Page number, starting at 1.
+    pub page: u32,
+    /// Byte offset where the page's content starts.
+    pub start: usize,
+    /// Byte offset just past the page's content.
+    pub end: usize,
+}
+
+/// Extracts the main content from HTML using a priority-based strategy.
 ///
-/// Tries in order: <article>, <main>, [role='main']
-/// Returns the first match found.
+/// Priority order:
+/// 1. Configured content selectors, in order. By default these are the semantic
+///    HTML5 tags (article, main, [role='main']) followed by content-specific class
+///    selectors (.content, .article, .post, .entry)
+/// 2. Fallback to body tag
 ///
 /// # Arguments
 /// * `document` - Parsed HTML document
+/// * `config` - Extraction rules providing the content selectors
 ///
 /// # Returns
-/// * Some(String) if a semantic tag is found, None otherwise
-fn try_semantic_tags(document: &Html) -> Option<String> {
-    // Define semantic selectors in priority order
-    let semantic_selectors = ["article", "main", "[role='main']"];
-
-    for selector_str in semantic_selectors.iter() {
-        if let Ok(selector) = Selector::parse(selector_str) {
-            if let Some(element) = document.select(&selector).next() {
-                // Found semantic tag, return its HTML
-                return Some(element.html());
-            }
-        }
+/// * String containing the extracted HTML content, with the content selector that
+///   matched (`None` when the body was used)
+pub(crate) fn extract_main_content<'a>(
+    document: &Html,
+    config: &'a ExtractionConfig,
+) -> (String, Option<&'a str>) {
+    // Priority 1: Content selectors (semantic tags first by default)
+    if let Some((content, selector)) = try_content_selectors(document, config.content_selectors())
+    {
+        return (content, Some(selector));
     }
 
-    None // No semantic tags found
+    // Priority 2: Fallback to body
+    (fallback_to_body_tag(document), None)
 }
 
-/// Attempts to extract content using content-specific class selectors.
+/// Attempts to extract content using the given selectors.
 ///
-/// Tries: .content, .article, .post, .entry
-/// Returns the first match found.
+/// Selectors are tried in order and the first element matched is returned.
 ///
 /// # Arguments
 /// * `document` - Parsed HTML document
+/// * `selectors` - CSS selectors in priority order
 ///
 /// # Returns
-/// * Some(String) if a content selector matches, None otherwise
-fn try_content_selectors_direct(document: &Html) -> Option<String> {
-    let class_selectors = [".content", ".article", ".post", ".entry"];
-
-    for selector_str in class_selectors.iter() {
-        if let Ok(selector) = Selector::parse(selector_str) {
-            if let Some(element) = document.select(&selector).next() {
-                return Some(element.html());
-            }
+/// * Some((String, selector)) if a selector matches, None otherwise
+fn try_content_selectors<'a>(document: &Html, selectors: &'a [String]) -> Option<(String, &'a str)> {
+    for selector_str in selectors.iter() {
+        if let Ok(selector) = Selector::parse(selector_str)
+            && let Some(element) = document.root_element().select(&selector).next()
+        {
+            return Some((element.html(), selector_str));
         }
     }
 
@@ -122,88 +382,213 @@ fn fallback_to_body_tag(document: &Html) -> String {
     }
 }
 
-fn extract_and_clean_content(html: &str) -> String {
-    // Step 1: Parse document
-    let document = Html::parse_document(html);
-
-    // Step 2: Smart content extraction (NEW!)
-    let extracted_html = extract_main_content(&document);
-
-    // Check if extraction was successful
-    if extracted_html.is_empty() {
-        return String::new();
+fn extract_and_clean_content(
+    html: &str,
+    config: &ExtractionConfig,
+    site_extractor: Option<&dyn SiteExtractor>,
+    page_url: Option<&str>,
+    content_language: Option<&str>,
+) -> ExtractedContent {
+    // Step 1: Parse document, reveal content hidden behind scripts, read its
+    // metadata and set code blocks and images aside so cleanup cannot mangle them
+    let mut document = Html::parse_document(html);
+    if config.recover_hidden_content() {
+        recover_hidden_content(&mut document);
     }
-
-    // Step 3: Clean the extracted content
-    let relevant_html = clear_content(extracted_html);
-
-    // Step 4: Convert to Markdown
-    let markdown_content = html2md::parse_html(&relevant_html);
-
-    // Step 5: Final cleanup
-    final_clean_from_markdown(markdown_content)
-}
-
-// DEPRECATED: Kept for backwards compatibility during transition
-// Will be removed in future version
-#[allow(dead_code)]
-fn extract_and_clean_body(html: &str) -> String {
-    // Step 1: Extract only the body content from the HTML
-    let document = Html::parse_document(html);
-    let body_selector = Selector::parse("body").unwrap();
-
-    let body_html = match document.select(&body_selector).next() {
-        Some(body_element) => body_element.html(),
-        None => return String::new(), // Return empty if no body found
+    let base_url = document_base_url(&document, page_url);
+    let metadata = extract_page_metadata(&document, base_url.as_ref());
+    let code_blocks = if config.preserve_code_blocks() {
+        extract_code_blocks(&mut document)
+    } else {
+        Vec::new()
+    };
+    let images = match config.image_mode() {
+        ImageMode::Remove => Vec::new(),
+        mode => extract_images(&mut document, mode, base_url.as_ref()),
+    };
+    let mut report = ExtractionReport {
+        code_blocks: code_blocks.len(),
+        ..ExtractionReport::default()
+    };
+    let mut cleaning = config.cleaning_report().then(CleaningReport::default);
+
+    // Step 2: Site-specific extraction, when an extractor handles this page
+    let site_html = site_extractor.and_then(|extractor| {
+        let site_html = extractor.extract(&document)?;
+        report.strategy = ExtractionStrategy::SiteExtractor;
+        report.site_extractor = Some(extractor.name().to_string());
+        Some(site_html)
+    });
+
+    let relevant_html = match site_html {
+        // Step 3a: The extractor already picked the content, so only clean it
+        Some(site_html) => body_inner_html(&strip_unwanted_elements(
+            &site_html,
+            config,
+            &mut cleaning,
+        )),
+        None => {
+            // Step 2b: Smart content extraction
+            let (extracted_html, content_selector) = extract_main_content(&document, config);
+
+            // Step 3b: Clean the extracted content
+            let dom_html = (!extracted_html.is_empty())
+                .then(|| clear_content(extracted_html, config, &mut cleaning));
+
+            // Step 3c: Thin markup may be a client-side rendered page whose
+            // article ships as embedded JSON state
+            let dom_chars = dom_html.as_deref().map_or(0, text_chars);
+            let embedded = (config.embedded_content() && dom_chars < THIN_CONTENT_CHARS)
+                .then(|| extract_embedded_content(&document))
+                .flatten()
+                .filter(|embedded| embedded.text_chars > dom_chars);
+
+            match (embedded, dom_html) {
+                (Some(embedded), _) => {
+                    report.strategy = ExtractionStrategy::EmbeddedData;
+                    report.embedded_source = Some(embedded.source);
+                    cleaning = config.cleaning_report().then(CleaningReport::default);
+                    body_inner_html(&strip_unwanted_elements(
+                        &embedded.html,
+                        config,
+                        &mut cleaning,
+                    ))
+                }
+                (None, Some(dom_html)) => {
+                    report.strategy = match content_selector {
+                        Some(_) => ExtractionStrategy::ContentSelector,
+                        None => ExtractionStrategy::Body,
+                    };
+                    report.content_selector = content_selector.map(str::to_string);
+                    dom_html
+                }
+                // Nothing could be extracted
+                (None, None) => {
+                    report.cleaning = cleaning;
+                    return ExtractedContent {
+                        language: detect_language(
+                            metadata.language.as_deref(),
+                            content_language,
+                            "",
+                        ),
+                        metadata,
+                        report,
+                        ..ExtractedContent::default()
+                    };
+                }
+            }
+        }
     };
 
-    // Step 2: Clean the body content by removing unwanted elements
-    let relevant_html = clear_content(body_html);
-
-    // Step 3: Convert the cleaned HTML to Markdown
+    // Step 4: Convert tables, then the rest of the content, to Markdown. The
+    // tables stay placeholders until the cleanup is done, so that its line
+    // filters cannot drop their rows
+    let (relevant_html, mut tables) = if config.convert_tables() {
+        convert_tables(&relevant_html, config.output_format().uses_gfm_tables())
+    } else {
+        (relevant_html, Vec::new())
+    };
+    report.tables = tables.len();
     let markdown_content = html2md::parse_html(&relevant_html);
-
-    // Step 4: Final cleanup
-    // Remove unwanted elements while preserving Markdown structure
-    final_clean_from_markdown(markdown_content)
+    let with_tables = restore_tables(&markdown_content, &tables);
+    let links = content_links(&with_tables, base_url.as_ref());
+
+    // Step 5: Detect the language, which selects the boilerplate pack
+    let language = detect_language(
+        metadata.language.as_deref(),
+        content_language,
+        &markdown_to_plain_text(&with_tables),
+    );
+    let boilerplate = boilerplate_pack(config, language.as_ref());
+    report.boilerplate_pack = boilerplate.map(|pack| pack.language().to_string());
+
+    // Step 6: Final cleanup
+    let markdown_content = final_clean_from_markdown(
+        markdown_content,
+        &mut tables,
+        config,
+        base_url.as_ref(),
+        boilerplate,
+        &mut cleaning,
+    );
+
+    // Step 7: Put the tables and the preserved code blocks back, then the images
+    let markdown = restore_tables(&markdown_content, &tables);
+    let newline_regex = Regex::new(r"\n{3,}").unwrap();
+    let markdown = newline_regex.replace_all(markdown.trim(), "\n\n");
+    let markdown = restore_code_blocks(&markdown, &code_blocks);
+    let (mut markdown, restored_images) = restore_images(&markdown, &images);
+    report.images = restored_images;
+
+    // Step 8: Optionally lead with the page title
+    if config.inject_title()
+        && let Some(title) = &metadata.title
+    {
+        markdown = inject_title(markdown, title);
+    }
+    if markdown.is_empty() {
+        report.strategy = ExtractionStrategy::Empty;
+    }
+    report.cleaning = cleaning;
+
+    // Step 9: Render the requested output format
+    let content = config.output_format().render(&markdown);
+
+    ExtractedContent {
+        markdown,
+        content,
+        metadata,
+        language,
+        report,
+        pages: Vec::new(),
+        links,
+    }
 }
 
-fn clear_content(content_html: String) -> String {
-    let mut cleaned_body = content_html;
-
-    // Remove script blocks
-    let script_regex = Regex::new(r"(?i)<script[^>]*>[\s\S]*?</script>").unwrap();
-    cleaned_body = script_regex.replace_all(&cleaned_body, "").to_string();
-
-    // Remove style blocks
-    let style_regex = Regex::new(r"(?i)<style[^>]*>[\s\S]*?</style>").unwrap();
-    cleaned_body = style_regex.replace_all(&cleaned_body, "").to_string();
-
-    // Remove images, iframes, and other non-textual elements
-    for pattern in media_elements().iter() {
-        let regex = Regex::new(pattern).unwrap();
-        cleaned_body = regex.replace_all(&cleaned_body, "").to_string();
+/// Returns the boilerplate pack selected by the configuration for a page in
+/// `language`.
+fn boilerplate_pack<'a>(
+    config: &'a ExtractionConfig,
+    language: Option<&DetectedLanguage>,
+) -> Option<&'a BoilerplatePack> {
+    match config.boilerplate_language() {
+        BoilerplateLanguage::Auto => config.boilerplate_packs().find(&language?.tag),
+        BoilerplateLanguage::Fixed(tag) => config.boilerplate_packs().find(tag),
+        BoilerplateLanguage::Disabled => None,
     }
+}
 
-    // Remove navigation, header, footer, sidebar and advertising elements
-    for pattern in unwanted_elements().iter() {
-        let regex = Regex::new(pattern).unwrap();
-        cleaned_body = regex.replace_all(&cleaned_body, "").to_string();
+/// Prepends `title` as a top-level heading unless the content is empty or
+/// already starts with it.
+fn inject_title(markdown: String, title: &str) -> String {
+    let starts_with_title = markdown
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .is_some_and(|line| line.trim_matches(|c: char| c == '#' || c.is_whitespace()) == title);
+
+    if markdown.is_empty() || starts_with_title {
+        markdown
+    } else {
+        format!("# {}\n\n{}", title, markdown)
     }
+}
 
-    // Parse the cleaned body HTML and use scraper to extract only text content elements
-    let cleaned_document =
-        Html::parse_document(&format!("<html><body>{}</body></html>", cleaned_body));
+fn clear_content(
+    content_html: String,
+    config: &ExtractionConfig,
+    cleaning: &mut Option<CleaningReport>,
+) -> String {
+    let cleaned_document = strip_unwanted_elements(&content_html, config, cleaning);
 
-    // Select only content-relevant elements and extract their inner HTML
+    // Select only content-relevant elements and extract their HTML
 
     let mut relevant_html = String::new();
     let mut found_main_content = false;
 
     // First try to find main content containers
-    for selector_str in content_selectors().iter() {
+    for selector_str in config.content_selectors().iter() {
         if let Ok(selector) = Selector::parse(selector_str) {
-            for element in cleaned_document.select(&selector) {
+            for element in cleaned_document.root_element().select(&selector) {
                 relevant_html.push_str(&element.html());
                 relevant_html.push('\n');
                 found_main_content = true;
@@ -213,9 +598,9 @@ fn clear_content(content_html: String) -> String {
 
     // If no main content containers found, extract individual text elements
     if !found_main_content {
-        for selector_str in text_selectors().iter() {
+        for selector_str in config.text_selectors().iter() {
             if let Ok(selector) = Selector::parse(selector_str) {
-                for element in cleaned_document.select(&selector) {
+                for element in cleaned_document.root_element().select(&selector) {
                     relevant_html.push_str(&element.html());
                     relevant_html.push('\n');
                 }
@@ -225,37 +610,133 @@ fn clear_content(content_html: String) -> String {
 
     // If still no content found, fallback to the entire cleaned body
     if relevant_html.trim().is_empty() {
-        relevant_html = cleaned_body;
+        relevant_html = body_inner_html(&cleaned_document);
+    }
+
+    relevant_html
+}
+
+/// Parses an HTML fragment and removes scripts, styles and every element matched by
+/// the configured removal selectors.
+///
+/// The HTML is parsed once so that cleaning operates on whole subtrees.
+fn strip_unwanted_elements(
+    content_html: &str,
+    config: &ExtractionConfig,
+    cleaning: &mut Option<CleaningReport>,
+) -> Html {
+    let mut cleaned_document =
+        Html::parse_document(&format!("<html><body>{}</body></html>", content_html));
+
+    // Remove script and style blocks
+    remove_elements(&mut cleaned_document, &["script", "style"], cleaning);
+
+    // Remove media, navigation, header, footer, sidebar, advertising and hidden elements
+    remove_elements(&mut cleaned_document, config.removal_selectors(), cleaning);
+
+    cleaned_document
+}
+
+/// Detaches every element matching any of the given CSS selectors from the document.
+///
+/// Matching elements are removed together with their whole subtree, so nested
+/// markup never leaves dangling closing tags behind. Selectors that fail to
+/// parse are skipped. Detached nodes stay in the tree's arena, so later queries
+/// must start from [`Html::root_element`] rather than [`Html::select`].
+pub(crate) fn remove_elements<S: AsRef<str>>(
+    document: &mut Html,
+    selectors: &[S],
+    cleaning: &mut Option<CleaningReport>,
+) {
+    for selector_str in selectors.iter() {
+        let Ok(selector) = Selector::parse(selector_str.as_ref()) else {
+            continue;
+        };
+
+        // Walk from the root element so already detached subtrees are not visited again
+        let matched: Vec<_> = document
+            .root_element()
+            .select(&selector)
+            .map(|element| {
+                record_removal(
+                    cleaning,
+                    || CleaningRule::RemovalSelector(selector_str.as_ref().to_string()),
+                    || element_summary(element),
+                );
+                element.id()
+            })
+            .collect();
+        for node_id in matched {
+            if let Some(mut node) = document.tree.get_mut(node_id) {
+                node.detach();
+            }
+        }
     }
+}
 
-    // Additional cleanup before markdown conversion - remove remaining unwanted elements
-    for pattern in additional_cleanup().iter() {
-        let regex = Regex::new(pattern).unwrap();
-        relevant_html = regex.replace_all(&relevant_html, "").to_string();
+/// Describes a removed element by the start of its text, or by its tag name when
+/// it has no text.
+fn element_summary(element: ElementRef<'_>) -> String {
+    let text = element.text().collect::<Vec<_>>().join(" ");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return format!("<{}>", element.value().name());
     }
+    match text.char_indices().nth(REMOVAL_SUMMARY_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
 
-    return relevant_html;
+/// Returns the inner HTML of the document body, or an empty string if there is none.
+fn body_inner_html(document: &Html) -> String {
+    let body_selector = Selector::parse("body").unwrap();
+
+    document
+        .root_element()
+        .select(&body_selector)
+        .next()
+        .map(|body| body.inner_html())
+        .unwrap_or_default()
 }
 
-fn final_clean_from_markdown(markdown_content: String) -> String {
+/// Cleans the Markdown produced by html2md. The text of `tables`, which stand
+/// behind placeholders in `markdown_content`, gets the same link handling but
+/// none of the line filters.
+fn final_clean_from_markdown(
+    markdown_content: String,
+    tables: &mut [String],
+    config: &ExtractionConfig,
+    base_url: Option<&Url>,
+    boilerplate: Option<&BoilerplatePack>,
+    cleaning: &mut Option<CleaningReport>,
+) -> String {
     let mut result = markdown_content;
 
     // Remove any remaining HTML tags that might have been missed
-    let html_tag_regex = Regex::new(r"<[^>]+>").unwrap();
-    result = html_tag_regex.replace_all(&result, "").to_string();
+    result = remove_stray_tags(&result);
 
-    // Remove Markdown links [text](url) and keep only the text part
-    let link_regex = Regex::new(r"\[([^\]]+)\]\([^)]+\)").unwrap();
-    result = link_regex.replace_all(&result, "$1").to_string();
-
-    // Remove standalone URLs that might remain
+    // Strip, resolve or collect Markdown links [text](url) according to the link
+    // mode, numbering references in document order across text and tables
+    let mut references = Vec::new();
     let url_regex = Regex::new(r"https?://[^\s]+").unwrap();
-    result = url_regex.replace_all(&result, "").to_string();
-
-    // Keep Markdown formatting but clean up problematic patterns
-    // Remove code blocks (usually not relevant content)
-    let code_block_regex = Regex::new(r"```[\s\S]*?```").unwrap();
-    result = code_block_regex.replace_all(&result, "").to_string();
+    result = rewrite_around_tables(&result, tables, |markdown| {
+        let linked = rewrite_links(markdown, config.link_mode(), base_url, &mut references);
+        // Remove standalone URLs that might remain when links are stripped
+        if config.link_mode() == LinkMode::Strip {
+            url_regex.replace_all(&linked, "").to_string()
+        } else {
+            linked
+        }
+    });
+    let link_references = reference_definitions(&references);
+
+    // Remove code blocks unless they are preserved; preserved blocks are only
+    // placeholders at this point
+    if !config.preserve_code_blocks() {
+        let code_block_regex = Regex::new(r"```[\s\S]*?```").unwrap();
+        result = code_block_regex.replace_all(&result, "").to_string();
+    }
 
     // Remove excessive whitespace and normalize line breaks
     let space_regex = Regex::new(r"[ \t]+").unwrap();
@@ -264,18 +745,40 @@ fn final_clean_from_markdown(markdown_content: String) -> String {
     let newline_regex = Regex::new(r"\n{3,}").unwrap();
     result = newline_regex.replace_all(&result, "\n\n").to_string();
 
-    // Remove common advertising/navigation text patterns but preserve line structure
-    for pattern in unwanted_text_patterns().iter() {
-        let regex = Regex::new(pattern).unwrap();
-        result = regex.replace_all(&result, "").to_string();
+    // Drop banner, call-to-action and navigation lines in the page language
+    if let Some(pack) = boilerplate {
+        result = filter_lines(&result, cleaning, |line| {
+            pack.matching_phrase(line)
+                .map(|phrase| CleaningRule::BoilerplatePhrase {
+                    language: pack.language().to_string(),
+                    phrase: phrase.to_string(),
+                })
+        });
     }
 
+    // Drop lines made up mostly of advertising/navigation phrases; sentences that
+    // merely contain such a word are kept intact
+    let patterns: Vec<(&str, Regex)> = config
+        .text_patterns()
+        .iter()
+        .chain(boilerplate.map(BoilerplatePack::text_patterns).unwrap_or_default())
+        .filter_map(|pattern| Some((pattern.as_str(), Regex::new(pattern).ok()?)))
+        .collect();
+    result = filter_lines(&result, cleaning, |line| {
+        boilerplate_pattern(line, &patterns)
+            .map(|pattern| CleaningRule::TextPattern(pattern.to_string()))
+    });
+
     // Clean up empty lines and extra spacing
     let cleanup_regex = Regex::new(r"\n\s*\n\s*\n").unwrap();
     result = cleanup_regex.replace_all(&result, "\n\n").to_string();
 
     // Remove lines that are likely metadata or navigation while preserving markdown structure
-    result = remove_lines_metadata_or_navigation(result.lines().collect()).join("\n");
+    let mut structured = structured_lines(&result).into_iter();
+    result = filter_lines(&result, cleaning, |line| {
+        let structured = structured.next().unwrap_or_default();
+        metadata_or_navigation_rule(line, structured, config)
+    });
 
     // Clean up excessive empty lines but preserve paragraph structure
     let excessive_newlines_regex = Regex::new(r"\n{4,}").unwrap();
@@ -283,102 +786,796 @@ fn final_clean_from_markdown(markdown_content: String) -> String {
         .replace_all(&result, "\n\n\n")
         .to_string();
 
-    result.trim().to_string()
+    let mut result = result.trim().to_string();
+
+    // Append reference-style link definitions collected above
+    if !link_references.is_empty() {
+        result.push_str("\n\n");
+        result.push_str(&link_references.join("\n"));
+    }
+
+    result
 }
 
-fn remove_lines_metadata_or_navigation(lines: Vec<&str>) -> Vec<&str> {
-    lines
-        .into_iter()
-        .filter(|line| {
-            let trimmed = line.trim();
+/// Removes the HTML tags html2md passes through, such as `<sup>` or
+/// `<iframe>`. Inline code spans and escaped text like `\<b\>`, which stand
+/// for literal angle brackets, are left alone.
+fn remove_stray_tags(markdown: &str) -> String {
+    let code_span_regex = Regex::new(r"`[^`\n]+`").unwrap();
+    let html_tag_regex = Regex::new(r"\\?<[^<>]+>").unwrap();
+    let remove_tags = |text: &str| {
+        html_tag_regex
+            .replace_all(text, |caps: &Captures| {
+                if caps[0].starts_with('\\') {
+                    caps[0].to_string()
+                } else {
+                    String::new()
+                }
+            })
+            .into_owned()
+    };
 
-            // Always keep lines that start with markdown headers
-            if trimmed.starts_with('#') || trimmed.starts_with("##") {
-                return true;
-            }
+    let mut result = String::new();
+    let mut last = 0;
+    for span in code_span_regex.find_iter(markdown) {
+        result.push_str(&remove_tags(&markdown[last..span.start()]));
+        result.push_str(span.as_str());
+        last = span.end();
+    }
+    result.push_str(&remove_tags(&markdown[last..]));
+    result
+}
 
-            // Filter out very short lines that aren't meaningful
-            if trimmed.is_empty() || trimmed.len() < 5 {
-                return trimmed.is_empty(); // Keep empty lines for spacing
+/// Removes the lines for which `rule` returns the rule that removes them,
+/// recording each removal.
+fn filter_lines(
+    text: &str,
+    cleaning: &mut Option<CleaningReport>,
+    mut rule: impl FnMut(&str) -> Option<CleaningRule>,
+) -> String {
+    text.lines()
+        .filter(|line| match rule(line) {
+            Some(rule) => {
+                record_removal(cleaning, || rule, || line.trim().to_string());
+                false
             }
+            None => true,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
 
-            // Keep lines with meaningful content (relaxed filtering for single words)
-            let lower = trimmed.to_lowercase();
-
-            // Only filter out single words if they are likely navigation/metadata terms
-            if !trimmed.contains(' ') {
-                let navigation_terms = [
-                    "home",
-                    "about",
-                    "contact",
-                    "menu",
-                    "search",
-                    "login",
-                    "register",
-                    "subscribe",
-                    "share",
-                    "follow",
-                    "back",
-                    "next",
-                    "prev",
-                    "more",
-                    "advertisement",
-                    "ads",
-                    "sponsored",
-                    "cookie",
-                    "privacy",
-                    "terms",
-                ];
-                if navigation_terms.iter().any(|&term| lower == term) {
-                    return false;
-                }
-            }
+/// Returns the first pattern matching a short line whose letters are mostly
+/// covered by pattern matches.
+fn boilerplate_pattern<'a>(line: &str, patterns: &[(&'a str, Regex)]) -> Option<&'a str> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.split_whitespace().count() > PATTERN_MAX_WORDS {
+        return None;
+    }
 
-            // Skip obvious metadata/navigation patterns
-            if lower.starts_with("http")
-                || lower.contains("@")
-                || lower == "menu"
-                || lower == "navigation"
-                || lower == "nav"
-                || lower == "footer"
-                || lower == "header"
-                || lower == "sidebar"
-            {
-                return false;
-            }
+    let mut first_match = None;
+    let mut covered = vec![false; trimmed.len()];
+    for (pattern, regex) in patterns {
+        for found in regex.find_iter(trimmed) {
+            first_match.get_or_insert(*pattern);
+            covered[found.range()].fill(true);
+        }
+    }
 
-            // Filter out extremely short lines (less than 2 characters) that aren't meaningful
-            if trimmed.len() < 2 {
-                return false;
+    let mut letters = 0;
+    let mut covered_letters = 0;
+    for (index, c) in trimmed.char_indices() {
+        if c.is_alphabetic() {
+            letters += 1;
+            if covered[index] {
+                covered_letters += 1;
             }
+        }
+    }
 
-            // Keep everything else, including single words that could be content
-            true
-        })
-        .collect()
+    first_match.filter(|_| {
+        letters > 0 && covered_letters as f64 / letters as f64 >= PATTERN_MIN_COVERAGE
+    })
+}
+
+/// Flags the lines of `markdown` that belong to a list item, a block quote or
+/// a setext heading, which the short-line rule leaves alone.
+fn structured_lines(markdown: &str) -> Vec<bool> {
+    let item_regex = Regex::new(r"^\s*(?:[*+-]|\d+[.)])\s|^\s*>").unwrap();
+    let underline_regex = Regex::new(r"^\s*(?:=+|-+)\s*$").unwrap();
+
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut structured: Vec<bool> = lines.iter().map(|line| item_regex.is_match(line)).collect();
+    for (index, pair) in lines.windows(2).enumerate() {
+        if !pair[0].trim().is_empty() && underline_regex.is_match(pair[1]) {
+            structured[index] = true;
+            structured[index + 1] = true;
+        }
+    }
+    structured
+}
+
+/// Returns the rule removing a line that is likely metadata or navigation.
+///
+/// Very short lines are only removed when they stand alone, outside lists,
+/// quotes and headings (`structured`), and do not end a sentence. Bare URLs,
+/// email addresses and handles are only removed when links are stripped.
+fn metadata_or_navigation_rule(
+    line: &str,
+    structured: bool,
+    config: &ExtractionConfig,
+) -> Option<CleaningRule> {
+    let trimmed = line.trim();
+
+    // Always keep empty lines for spacing, and lines that start with markdown headers
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+
+    // Filter out very short lines that aren't meaningful
+    let is_sentence =
+        trimmed.ends_with(['.', '!', '?']) && trimmed.chars().any(char::is_alphabetic);
+    if trimmed.len() < 5 && !structured && !is_sentence {
+        return Some(CleaningRule::ShortLine);
+    }
+
+    let lower = trimmed.to_lowercase();
+
+    // Only filter out single words if they are likely navigation/metadata terms
+    if !trimmed.contains(' ') && config.navigation_terms().contains(&lower) {
+        return Some(CleaningRule::NavigationTerm(lower));
+    }
+
+    // Skip lines that are nothing but a URL, an email address or a handle when
+    // links are stripped, and layout labels
+    let is_bare_reference = config.link_mode() == LinkMode::Strip
+        && !trimmed.contains(' ')
+        && (lower.starts_with("http://")
+            || lower.starts_with("https://")
+            || lower.starts_with('@')
+            || (lower.contains('@') && lower.contains('.')));
+    if is_bare_reference
+        || matches!(
+            lower.as_str(),
+            "menu" | "navigation" | "nav" | "footer" | "header" | "sidebar"
+        )
+    {
+        return Some(CleaningRule::Metadata);
+    }
+
+    // Keep everything else, including single words that could be content
+    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::image_handler::ImageMode;
+    use crate::language::LanguageSource;
+    use crate::output_format::OutputFormat;
 
     #[test]
     fn test_new() {
         let processor = ContentProcessor::new();
-        assert_eq!(std::mem::size_of_val(&processor), 0);
+        assert_eq!(processor.config(), &ExtractionConfig::default());
+    }
+
+    #[test]
+    fn test_html_to_markdown_with_url_uses_site_extractor() {
+        let html = r#"
+            <html><body>
+                <div id="mw-navigation"><p>Main page links</p></div>
+                <h1 id="firstHeading">Ferris</h1>
+                <div id="mw-content-text"><div class="mw-parser-output">
+                    <p>Ferris is the unofficial mascot of Rust.<sup class="reference">[1]</sup></p>
+                    <h2>Origin<span class="mw-editsection">[edit]</span></h2>
+                    <p>The crab was drawn in 2015.</p>
+                </div></div>
+            </body></html>
+        "#;
+        let processor = ContentProcessor::new();
+
+        let result =
+            processor.html_to_markdown_with_url(html, "https://en.wikipedia.org/wiki/Ferris");
+
+        assert!(result.contains("Ferris"));
+        assert!(result.contains("unofficial mascot of Rust."));
+        assert!(result.contains("The crab was drawn in 2015."));
+        assert!(!result.contains("[1]"));
+        assert!(!result.contains("[edit]"));
+        assert!(!result.contains("Main page links"));
+        // Document order is preserved rather than grouped by element type
+        assert!(result.find("Origin").unwrap() < result.find("The crab").unwrap());
+    }
+
+    #[test]
+    fn test_html_to_markdown_with_url_falls_back_to_generic_pipeline() {
+        let html = "<html><body><article><h1>Title</h1><p>Generic article body.</p></article></body></html>";
+        let processor = ContentProcessor::new();
+
+        let with_url = processor.html_to_markdown_with_url(html, "https://example.com/post");
+        let invalid_url = processor.html_to_markdown_with_url(html, "not a url");
+
+        assert_eq!(with_url, processor.html_to_markdown(html));
+        assert_eq!(invalid_url, processor.html_to_markdown(html));
+    }
+
+    #[test]
+    fn test_set_site_extractors_disables_builtin() {
+        let mut processor = ContentProcessor::new();
+        assert!(!processor.site_extractors().is_empty());
+
+        processor.set_site_extractors(SiteExtractorRegistry::new());
+        assert!(processor.site_extractors().is_empty());
+    }
+
+    #[test]
+    fn test_html_to_markdown_link_modes() {
+        let html = r#"<html><head><base href="https://example.com/docs/"></head><body><article>
+            <p>Read the <a href="intro.html">introduction</a> and the <a href="https://crates.io/crates/regex">regex crate</a> docs.</p>
+        </article></body></html>"#;
+
+        let process = |link_mode| {
+            let config = ExtractionConfig::builder().link_mode(link_mode).build();
+            ContentProcessor::with_config(config)
+                .html_to_markdown_with_url(html, "https://example.com/start")
+        };
+
+        let stripped = process(LinkMode::Strip);
+        assert!(stripped.contains("Read the introduction and the regex crate docs."));
+        assert!(!stripped.contains("https://"));
+
+        let inline = process(LinkMode::Inline);
+        assert!(inline.contains("[introduction](https://example.com/docs/intro.html)"));
+        assert!(inline.contains("[regex crate](https://crates.io/crates/regex)"));
+
+        let reference = process(LinkMode::Reference);
+        assert!(reference.contains("[introduction][1]"));
+        assert!(reference.contains("[regex crate][2]"));
+        assert!(reference.ends_with(
+            "[1]: https://example.com/docs/intro.html\n[2]: https://crates.io/crates/regex"
+        ));
+
+        let external = process(LinkMode::ExternalOnly);
+        assert!(external.contains("Read the introduction and"));
+        assert!(external.contains("[regex crate](https://crates.io/crates/regex)"));
+    }
+
+    #[test]
+    fn test_html_to_markdown_keeps_bare_references_unless_stripped() {
+        let html = r#"<html><body><article>
+            <p>The installer for every platform is available from the download page.</p>
+            <p>https://example.com/download</p>
+            <p>support@example.com</p>
+        </article></body></html>"#;
+
+        let process = |link_mode| {
+            let config = ExtractionConfig::builder().link_mode(link_mode).build();
+            ContentProcessor::with_config(config).html_to_markdown(html)
+        };
+
+        let stripped = process(LinkMode::Strip);
+        assert!(!stripped.contains("example.com"));
+
+        for link_mode in [
+            LinkMode::Inline,
+            LinkMode::Reference,
+            LinkMode::ExternalOnly,
+        ] {
+            let kept = process(link_mode);
+            assert!(kept.contains("https://example.com/download"));
+            assert!(kept.contains("support@example.com"));
+        }
+    }
+
+    #[test]
+    fn test_html_to_markdown_preserves_code_blocks() {
+        let html = r#"<html><body><article>
+            <h1>Using iterators</h1>
+            <p>The example below sums a vector of numbers.</p>
+            <div class="highlight highlight-source-rust"><pre><span class="k">let</span> total_sum: i32 = v.iter().sum();
+
+<span class="c">// print  it</span>
+println!("{}", total_sum);</pre></div>
+            <p>That is all there is to it.</p>
+        </article></body></html>"#;
+
+        let result = ContentProcessor::new().html_to_markdown(html);
+        assert!(result.contains(
+            "```rust\nlet total_sum: i32 = v.iter().sum();\n\n// print  it\nprintln!(\"{}\", total_sum);\n```"
+        ));
+        assert!(result.contains("That is all there is to it."));
+
+        let config = ExtractionConfig::builder()
+            .preserve_code_blocks(false)
+            .build();
+        let without_code = ContentProcessor::with_config(config).html_to_markdown(html);
+        assert!(!without_code.contains("total_sum"));
+        assert!(!without_code.contains("```"));
+        assert!(without_code.contains("That is all there is to it."));
+    }
+
+    #[test]
+    fn test_html_to_markdown_keeps_angle_brackets_in_code_and_text() {
+        let html = r#"<html><body><article>
+            <p>Collect the names into a <code>Vec&lt;String&gt;</code> before sorting them.</p>
+            <p>Write a &lt;b&gt; c to show the tag, and x<sup>2</sup> for squares.</p>
+        </article></body></html>"#;
+
+        let result = ContentProcessor::new().html_to_markdown(html);
+        assert!(result.contains("into a `Vec<String>` before sorting"));
+        assert!(result.contains(r"Write a \<b\> c to show the tag"));
+        assert!(result.contains("x2 for squares."));
+    }
+
+    #[test]
+    fn test_html_to_markdown_converts_tables() {
+        let html = r#"<html><body><article>
+            <h1>Pricing overview</h1>
+            <p>Every plan includes unlimited projects.</p>
+            <table>
+                <thead><tr><th>Plan</th><th>Monthly price</th></tr></thead>
+                <tbody>
+                    <tr><td>Starter</td><td>$9 per seat</td></tr>
+                    <tr><td>Business</td><td><a href="/contact">Contact sales</a></td></tr>
+                </tbody>
+            </table>
+            <p>Prices exclude taxes.</p>
+        </article></body></html>"#;
+
+        let result = ContentProcessor::new().html_to_markdown(html);
+        assert!(result.contains(
+            "| Plan | Monthly price |\n| --- | --- |\n| Starter | $9 per seat |\n| Business | Contact sales |"
+        ));
+        assert!(result.contains("Every plan includes unlimited projects.\n\n| Plan"));
+        assert!(result.contains("Prices exclude taxes."));
+
+        let config = ExtractionConfig::builder()
+            .link_mode(LinkMode::Inline)
+            .build();
+        let with_links = ContentProcessor::with_config(config)
+            .html_to_markdown_with_url(html, "https://example.com/pricing");
+        assert!(with_links.contains("| Business | [Contact sales](https://example.com/contact) |"));
+    }
+
+    #[test]
+    fn test_cleanup_keeps_short_structured_lines() {
+        let html = r#"<html><body><article>
+            <p>The services of the platform are written in three languages.</p>
+            <ul><li>Go</li><li>Rust</li><li>C</li></ul>
+            <p>Is every service covered by the on-call rotation?</p>
+            <p>Yes.</p>
+            <h2>API</h2>
+            <p>The API reference lists every endpoint with its parameters.</p>
+            <blockquote>Ok</blockquote>
+            <p>»</p>
+        </article></body></html>"#;
+
+        let markdown = ContentProcessor::new().html_to_markdown(html);
+        assert!(markdown.contains("* Go\n* Rust\n* C"));
+        assert!(markdown.contains("rotation?\n\nYes.\n\n"));
+        assert!(markdown.contains("API\n----------\n\nThe API reference"));
+        assert!(markdown.contains("> Ok"));
+        assert!(!markdown.contains('»'));
+    }
+
+    #[test]
+    fn test_cleanup_keeps_short_table_rows() {
+        let html = r#"<html lang="en"><body><article>
+            <p>The results of the vote were published this morning by the committee.</p>
+            <p>Subscribe to our newsletter</p>
+            <table>
+                <tr><th>Option</th><th>Votes</th></tr>
+                <tr><td>a</td><td>1</td></tr>
+                <tr><td>Menu</td><td>Share</td></tr>
+                <tr><td>x &lt;y&gt; z</td><td>2</td></tr>
+            </table>
+        </article></body></html>"#;
+
+        let extracted = ContentProcessor::new().extract(html, None);
+        assert_eq!(
+            extracted.markdown,
+            "The results of the vote were published this morning by the committee.\n\n\
+             | Option | Votes |\n| --- | --- |\n| a | 1 |\n| Menu | Share |\n| x \\<y\\> z | 2 |"
+        );
+        assert_eq!(extracted.report.boilerplate_pack.as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn test_reference_links_in_tables_follow_document_order() {
+        let html = r#"<html><body><article>
+            <p>Read the <a href="/guide">installation guide</a> before upgrading.</p>
+            <table>
+                <tr><th>Version</th><th>Notes</th></tr>
+                <tr><td>2.0</td><td><a href="/notes/2.0">Release notes</a></td></tr>
+            </table>
+            <p>Report problems on the <a href="/issues">issue tracker</a>.</p>
+        </article></body></html>"#;
+
+        let config = ExtractionConfig::builder()
+            .link_mode(LinkMode::Reference)
+            .build();
+        let markdown = ContentProcessor::with_config(config)
+            .html_to_markdown_with_url(html, "https://example.com/docs/");
+        assert!(markdown.contains("| 2.0 | [Release notes][2] |"));
+        assert!(markdown.contains("[issue tracker][3]"));
+        assert!(markdown.ends_with(
+            "[1]: https://example.com/guide\n[2]: https://example.com/notes/2.0\n[3]: https://example.com/issues"
+        ));
+    }
+
+    #[test]
+    fn test_extract_returns_metadata() {
+        let html = r#"<html><head>
+            <title>Quarterly results | Example Corp</title>
+            <meta property="og:site_name" content="Example Corp">
+            <meta property="article:published_time" content="2026-04-15T10:00:00Z">
+            <meta name="author" content="Finance Team">
+        </head><body><article><p>Revenue grew by twelve percent this quarter.</p></article></body></html>"#;
+
+        let extracted = ContentProcessor::new().extract(html, Some("https://example.com/q1"));
+        assert_eq!(
+            extracted.markdown,
+            "Revenue grew by twelve percent this quarter."
+        );
+        assert_eq!(extracted.metadata.title.as_deref(), Some("Quarterly results"));
+        assert_eq!(extracted.metadata.author.as_deref(), Some("Finance Team"));
+        assert_eq!(
+            extracted.metadata.published_time.as_deref(),
+            Some("2026-04-15T10:00:00Z")
+        );
+
+        assert_eq!(extracted.report.strategy, ExtractionStrategy::ContentSelector);
+        assert_eq!(extracted.report.content_selector.as_deref(), Some("article"));
+
+        let without_body = ContentProcessor::new().extract("<title>Only a title</title>", None);
+        assert!(without_body.markdown.is_empty());
+        assert_eq!(without_body.metadata.title.as_deref(), Some("Only a title"));
+        assert_eq!(without_body.report.strategy, ExtractionStrategy::Empty);
+    }
+
+    #[test]
+    fn test_extract_reports_site_extractor_and_body_fallback() {
+        let wikipedia = r#"<html><body><div id="mw-content-text"><div class="mw-parser-output"><p>Rust is a programming language.</p><pre>fn main() {}</pre></div></div></body></html>"#;
+        let extracted = ContentProcessor::new()
+            .extract(wikipedia, Some("https://en.wikipedia.org/wiki/Rust"));
+        assert_eq!(extracted.report.strategy, ExtractionStrategy::SiteExtractor);
+        assert_eq!(extracted.report.site_extractor.as_deref(), Some("wikipedia"));
+        assert_eq!(extracted.report.code_blocks, 1);
+
+        let plain = "<html><body><p>Plain page without containers.</p></body></html>";
+        let extracted = ContentProcessor::new().extract(plain, None);
+        assert_eq!(extracted.report.strategy, ExtractionStrategy::Body);
+        assert!(extracted.report.content_selector.is_none());
+    }
+
+    #[test]
+    fn test_extract_collects_content_links_whatever_the_link_mode() {
+        let html = r#"<html><body>
+            <nav><a href="/login">Log in to your account</a></nav>
+            <article><p>The <a href="part-2.html">second part</a> covers deployment, and the
+            <a href="https://docs.example.org/deploy#cli">reference</a> lists every flag.</p></article>
+            </body></html>"#;
+        let extracted =
+            ContentProcessor::new().extract(html, Some("https://example.com/series/part-1.html"));
+
+        assert!(!extracted.markdown.contains("]("));
+        assert_eq!(
+            extracted.links,
+            [
+                "https://example.com/series/part-2.html",
+                "https://docs.example.org/deploy"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_applies_text_patterns_for_detected_language() {
+        let portuguese = r#"<html lang="pt-BR"><body><article>
+            <p>A prefeitura anunciou hoje a ampliação da linha de metrô até o aeroporto internacional.</p>
+            <p>Inscreva-se</p>
+        </article></body></html>"#;
+        let extracted = ContentProcessor::new().extract(portuguese, None);
+        assert_eq!(extracted.language.as_ref().unwrap().tag, "pt-BR");
+        assert!(extracted.markdown.contains("ampliação da linha"));
+        assert!(!extracted.markdown.contains("Inscreva-se"));
+
+        // "créditos" is a Portuguese boilerplate word but ordinary Spanish
+        let spanish = r#"<html><body><article>
+            <p>El banco central anunció nuevas condiciones para los créditos hipotecarios de este año.</p>
+        </article></body></html>"#;
+        let extracted = ContentProcessor::new().extract(spanish, None);
+        let language = extracted.language.unwrap();
+        assert_eq!(language.tag, "es");
+        assert_eq!(language.source, LanguageSource::Statistical);
+        assert!(extracted.markdown.contains("los créditos hipotecarios"));
+    }
+
+    #[test]
+    fn test_extract_removes_localized_boilerplate() {
+        let spanish = r#"<html lang="es"><body><article>
+            <p>Utilizamos cookies propias y de terceros para mejorar tu experiencia.</p>
+            <p>El ayuntamiento aprobó ayer el nuevo plan de movilidad para el centro histórico.</p>
+            <p>Suscríbete a nuestro boletín</p>
+            <p>Compartir en Facebook</p>
+        </article></body></html>"#;
+        let extracted = ContentProcessor::new().extract(spanish, None);
+        assert_eq!(
+            extracted.markdown,
+            "El ayuntamiento aprobó ayer el nuevo plan de movilidad para el centro histórico."
+        );
+        assert_eq!(extracted.report.boilerplate_pack.as_deref(), Some("es"));
+
+        // Short German text without a declared language: choose the pack explicitly
+        let german = "<html><body><article><p>Die Sitzung dauerte lange.</p>\
+            <p>Newsletter abonnieren</p></article></body></html>";
+        let fixed = ContentProcessor::with_config(
+            ExtractionConfig::builder()
+                .boilerplate_language(BoilerplateLanguage::Fixed("de".to_string()))
+                .build(),
+        );
+        assert_eq!(fixed.html_to_markdown(german), "Die Sitzung dauerte lange.");
+
+        let disabled = ContentProcessor::with_config(
+            ExtractionConfig::builder()
+                .boilerplate_language(BoilerplateLanguage::Disabled)
+                .build(),
+        );
+        let extracted = disabled.extract(spanish, None);
+        assert!(extracted.markdown.contains("Compartir en Facebook"));
+        assert!(extracted.report.boilerplate_pack.is_none());
+    }
+
+    #[test]
+    fn test_line_filters_keep_sentences_intact() {
+        let html = r#"<html><body><article>
+            <p>The maintainers recommended that users subscribe to the security mailing list.</p>
+            <p>Write to press@example.com or mention @rustlang for questions about the release.</p>
+            <p>Related articles</p>
+            <p>Click here to subscribe</p>
+            <p>press@example.com</p>
+        </article></body></html>"#;
+
+        let markdown = ContentProcessor::new().html_to_markdown(html);
+        assert_eq!(
+            markdown,
+            "The maintainers recommended that users subscribe to the security mailing list.\n\n\
+             Write to press@example.com or mention @rustlang for questions about the release."
+        );
+    }
+
+    #[test]
+    fn test_extract_collects_cleaning_report() {
+        let html = r#"<html><body><article>
+            <nav>Home Blog About</nav>
+            <p>Deployment times dropped by half after the migration.</p>
+            <p>Read more</p>
+            <p>Register</p>
+        </article></body></html>"#;
+
+        let without = ContentProcessor::new().extract(html, None);
+        assert!(without.report.cleaning.is_none());
+
+        let processor = ContentProcessor::with_config(
+            ExtractionConfig::builder().cleaning_report(true).build(),
+        );
+        let extracted = processor.extract(html, None);
+        assert_eq!(
+            extracted.markdown,
+            "Deployment times dropped by half after the migration."
+        );
+
+        let removals = extracted.report.cleaning.unwrap().removals;
+        assert_eq!(removals.len(), 3);
+        assert_eq!(
+            removals[0].rule,
+            CleaningRule::RemovalSelector("nav".to_string())
+        );
+        assert_eq!(removals[0].content, "Home Blog About");
+        assert_eq!(
+            removals[1].rule,
+            CleaningRule::TextPattern(r"(?i)\bread more\b".to_string())
+        );
+        assert_eq!(removals[1].content, "Read more");
+        assert_eq!(
+            removals[2].rule,
+            CleaningRule::NavigationTerm("register".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_recovers_noscript_and_template_content() {
+        let html = r#"<html><body><article>
+            <noscript>Please enable JavaScript to continue.</noscript>
+            <noscript><p>The full story is rendered client-side, but this fallback carries it.</p></noscript>
+            <template><p>Comments are loaded later from a template in the page.</p></template>
+        </article></body></html>"#;
+
+        assert_eq!(
+            ContentProcessor::new().html_to_markdown(html),
+            "The full story is rendered client-side, but this fallback carries it.\n\n\
+             Comments are loaded later from a template in the page."
+        );
+
+        let config = ExtractionConfig::builder()
+            .recover_hidden_content(false)
+            .build();
+        let markdown = ContentProcessor::with_config(config).html_to_markdown(html);
+        assert!(!markdown.contains("Comments are loaded later"));
+    }
+
+    #[test]
+    fn test_extract_falls_back_to_embedded_data() {
+        let body = "<h2>Migration guide</h2>\
+            <p>Version two replaces the global configuration file with per-project settings, \
+            so every repository can pin the options it depends on without affecting others.</p>\
+            <p>Run the migration command once in each project; it reads the old file, writes \
+            the new settings next to the manifest and leaves a backup of the original behind.</p>\
+            <p>Projects that skip the migration keep working until the next major release, \
+            which drops support for the global file entirely.</p>";
+        let state = serde_json::json!({"props": {"pageProps": {"post": {"html": body}}}});
+        let html = format!(
+            r#"<html><body><div id="__next"><p>Loading the article, please wait a moment.</p></div>
+            <script id="__NEXT_DATA__" type="application/json">{}</script></body></html>"#,
+            state
+        );
+
+        let extracted = ContentProcessor::new().extract(&html, None);
+        assert_eq!(extracted.report.strategy, ExtractionStrategy::EmbeddedData);
+        let source = extracted.report.embedded_source.as_deref();
+        assert_eq!(source, Some("__NEXT_DATA__"));
+        assert!(extracted.markdown.starts_with("Migration guide\n---"));
+        assert!(extracted.markdown.contains("per-project settings"));
+        assert!(!extracted.markdown.contains("Loading the article"));
+
+        let config = ExtractionConfig::builder().embedded_content(false).build();
+        let extracted = ContentProcessor::with_config(config).extract(&html, None);
+        assert_eq!(extracted.report.strategy, ExtractionStrategy::Body);
+        assert_eq!(extracted.report.embedded_source, None);
+    }
+
+    #[test]
+    fn test_extract_image_modes() {
+        let html = r#"<html><body><article>
+            <nav><img src="/logo.png" alt="Site logo"></nav>
+            <p>The new dashboard shows request volume for every service in one view.</p>
+            <figure><a href="/full.png"><img data-src="/dashboard.png" alt="Dashboard"></a>
+                <figcaption>Request volume per service</figcaption></figure>
+            <img src="https://stats.example.com/pixel.gif" width="1" height="1">
+        </article></body></html>"#;
+        let extract = |mode: ImageMode| {
+            let config = ExtractionConfig::builder().image_mode(mode).build();
+            ContentProcessor::with_config(config).extract(html, Some("https://example.com/news"))
+        };
+
+        let removed = extract(ImageMode::Remove);
+        assert!(!removed.markdown.contains("[Image"));
+        assert!(!removed.markdown.contains("Dashboard"));
+        assert_eq!(removed.report.images, 0);
+
+        let placeholder = extract(ImageMode::Placeholder);
+        let figure = "one view.\n\n[Image: Dashboard — Request volume per service]";
+        assert!(placeholder.markdown.ends_with(figure));
+        assert_eq!(placeholder.report.images, 1);
+
+        let markdown = extract(ImageMode::Markdown).markdown;
+        assert!(markdown.ends_with(
+            "![Dashboard](https://example.com/dashboard.png)\n\n*Request volume per service*"
+        ));
+        assert!(!markdown.contains("logo") && !markdown.contains("pixel"));
+    }
+
+    #[test]
+    fn test_extract_renders_output_formats() {
+        let html = r#"<html><body><article>
+            <h2>Pricing overview</h2>
+            <ul><li>Monthly billing is available for every plan.</li></ul>
+            <table><tr><th>Plan</th><th>Price</th></tr><tr><td>Pro</td><td>$10</td></tr></table>
+        </article></body></html>"#;
+        let extract = |format: OutputFormat| {
+            let config = ExtractionConfig::builder().output_format(format).build();
+            ContentProcessor::with_config(config).extract(html, None)
+        };
+
+        let gfm = extract(OutputFormat::Gfm);
+        assert_eq!(gfm.content, gfm.markdown);
+        assert!(gfm.markdown.contains("| Pro | $10 |"));
+
+        let commonmark = extract(OutputFormat::CommonMark);
+        assert_eq!(commonmark.content, commonmark.markdown);
+        let row = "- **Plan**: Pro; **Price**: $10";
+        assert!(commonmark.markdown.ends_with(row));
+
+        let text = extract(OutputFormat::PlainText);
+        assert!(text.content.starts_with("Pricing overview\n\n• Monthly"));
+        assert!(text.content.ends_with("• Plan: Pro; Price: $10"));
+
+        let rendered = extract(OutputFormat::Html).content;
+        assert!(rendered.starts_with("<h2>Pricing overview</h2>"));
+        assert!(rendered.contains("<li>Monthly billing is available for every plan.</li>"));
+        assert!(rendered.contains("<td>Pro</td>"));
+    }
+
+    #[test]
+    fn test_extract_injects_title_heading() {
+        let config = ExtractionConfig::builder().inject_title(true).build();
+        let processor = ContentProcessor::with_config(config);
+
+        let html = r#"<html><head><title>Release notes</title></head>
+            <body><article><p>Version 2.0 ships a new parser.</p></article></body></html>"#;
+        assert_eq!(
+            processor.html_to_markdown(html),
+            "# Release notes\n\nVersion 2.0 ships a new parser."
+        );
+
+        let with_heading = r#"<html><head><title>Release notes</title></head>
+            <body><article><h1>Release notes</h1><p>Version 2.0 ships a new parser.</p></article></body></html>"#;
+        let result = processor.html_to_markdown(with_heading);
+        assert_eq!(result.matches("Release notes").count(), 1);
+
+        let default_result = ContentProcessor::new().html_to_markdown(html);
+        assert!(!default_result.contains("Release notes"));
+    }
+
+    #[test]
+    fn test_with_config() {
+        let config = ExtractionConfig::builder()
+            .add_removal_selector(".promo")
+            .build();
+        let processor = ContentProcessor::with_config(config.clone());
+        assert_eq!(processor.config(), &config);
+    }
+
+    #[test]
+    fn test_html_to_markdown_with_custom_removal_selector() {
+        let html = r#"<html><body><article><h1>Title</h1><div class="promo"><p>Promo block text</p></div><p>Body text here.</p></article></body></html>"#;
+
+        let default_result = ContentProcessor::new().html_to_markdown(html);
+        assert!(default_result.contains("Promo block text"));
+
+        let config = ExtractionConfig::builder()
+            .add_removal_selector(".promo")
+            .build();
+        let result = ContentProcessor::with_config(config).html_to_markdown(html);
+        assert!(!result.contains("Promo block text"));
+        assert!(result.contains("Body text here."));
+    }
+
+    #[test]
+    fn test_html_to_markdown_with_custom_content_selector() {
+        let html = r#"<html><body><div id="story"><p>Story paragraph text.</p></div><div class="other"><p>Unrelated block text.</p></div></body></html>"#;
+
+        let config = ExtractionConfig::builder()
+            .content_selectors(["#story"])
+            .build();
+        let result = ContentProcessor::with_config(config).html_to_markdown(html);
+        assert!(result.contains("Story paragraph text."));
+        assert!(!result.contains("Unrelated block text."));
+    }
+
+    #[test]
+    fn test_html_to_markdown_with_custom_navigation_terms() {
+        let html = "<html><body><p>Weiter</p><p>Article body text.</p></body></html>";
+
+        let config = ExtractionConfig::builder()
+            .add_navigation_term("weiter")
+            .build();
+        let result = ContentProcessor::with_config(config).html_to_markdown(html);
+        assert!(!result.contains("Weiter"));
+        assert!(result.contains("Article body text."));
     }
 
     #[test]
     fn test_extract_and_clean_body_with_empty_html() {
         let empty_html = "";
-        let result = extract_and_clean_body(empty_html);
+        let result = ContentProcessor::new().html_to_markdown(empty_html);
         assert_eq!(result, "");
     }
 
     #[test]
     fn test_extract_and_clean_body_with_no_body() {
         let html_without_body = "<html><head><title>Test</title></head></html>";
-        let result = extract_and_clean_body(html_without_body);
+        let result = ContentProcessor::new().html_to_markdown(html_without_body);
         assert_eq!(result, "");
     }
 
@@ -386,7 +1583,7 @@ mod tests {
     fn test_extract_and_clean_body_with_simple_content() {
         let simple_html =
             "<html><body><h1>Test Title</h1><p>Test paragraph content.</p></body></html>";
-        let result = extract_and_clean_body(simple_html);
+        let result = ContentProcessor::new().html_to_markdown(simple_html);
 
         // Should contain the content without HTML tags
         assert!(result.contains("Test Title"));
@@ -407,6 +1604,56 @@ mod tests {
         assert!(!result.contains("<body>"));
     }
 
+    #[test]
+    fn test_clear_content_removes_nested_unwanted_subtree() {
+        let html = r#"
+            <div class="sidebar">
+                <div><p>Sidebar widget</p></div>
+                <p>Sidebar trailing text</p>
+            </div>
+            <p>Real paragraph content.</p>
+        "#;
+
+        let result = clear_content(html.to_string(), &ExtractionConfig::default(), &mut None);
+
+        assert!(!result.contains("Sidebar widget"));
+        assert!(!result.contains("Sidebar trailing text"));
+        assert!(!result.contains("</div>"));
+        assert!(result.contains("Real paragraph content."));
+    }
+
+    #[test]
+    fn test_clear_content_matches_exact_class_tokens() {
+        let html = r#"
+            <div class="header-gradient"><p>Gradient header text</p></div>
+            <div class="loading"><p>Loading section text</p></div>
+            <div class="promo ad"><p>Buy our product</p></div>
+            <div id="comments"><p>First comment</p></div>
+        "#;
+
+        let result = clear_content(html.to_string(), &ExtractionConfig::default(), &mut None);
+
+        assert!(result.contains("Gradient header text"));
+        assert!(result.contains("Loading section text"));
+        assert!(!result.contains("Buy our product"));
+        assert!(!result.contains("First comment"));
+    }
+
+    #[test]
+    fn test_clear_content_removes_aria_roles_and_hidden_elements() {
+        let html = r#"
+            <div role="navigation"><p>Role navigation links</p></div>
+            <div style="display: none"><p>Hidden block</p></div>
+            <p>Visible paragraph.</p>
+        "#;
+
+        let result = clear_content(html.to_string(), &ExtractionConfig::default(), &mut None);
+
+        assert!(!result.contains("Role navigation links"));
+        assert!(!result.contains("Hidden block"));
+        assert!(result.contains("Visible paragraph."));
+    }
+
     // ============================================================================
     // NEW TESTS FOR SEMANTIC HTML5 TAG EXTRACTION (Issue #40)
     // ============================================================================
@@ -429,7 +1676,7 @@ mod tests {
             </html>
         "#;
 
-        let result = extract_and_clean_body(html);
+        let result = ContentProcessor::new().html_to_markdown(html);
 
         // Should extract article content
         assert!(
@@ -467,7 +1714,7 @@ mod tests {
             </html>
         "#;
 
-        let result = extract_and_clean_body(html);
+        let result = ContentProcessor::new().html_to_markdown(html);
 
         // Should extract main content
         assert!(
@@ -503,7 +1750,7 @@ mod tests {
             </html>
         "#;
 
-        let result = extract_and_clean_body(html);
+        let result = ContentProcessor::new().html_to_markdown(html);
 
         // Should fallback to body extraction and still work
         assert!(
@@ -547,7 +1794,7 @@ mod tests {
             </html>
         "#;
 
-        let result = extract_and_clean_body(html);
+        let result = ContentProcessor::new().html_to_markdown(html);
 
         // Should extract article content
         assert!(
@@ -595,7 +1842,7 @@ mod tests {
             </html>
         "#;
 
-        let result = extract_and_clean_body(html);
+        let result = ContentProcessor::new().html_to_markdown(html);
 
         // Should extract only the first article (or both, depending on implementation)
         assert!(
@@ -621,7 +1868,7 @@ mod tests {
             </html>
         "#;
 
-        let result = extract_and_clean_body(html);
+        let result = ContentProcessor::new().html_to_markdown(html);
 
         // Should extract content with role="main"
         assert!(
@@ -638,10 +1885,7 @@ mod tests {
             !result.contains("Navigation"),
             "Should not contain navigation"
         );
-        assert!(
-            !result.contains("Sidebar"),
-            "Should not contain sidebar"
-        );
+        assert!(!result.contains("Sidebar"), "Should not contain sidebar");
     }
 
     #[test]
@@ -689,10 +1933,7 @@ mod tests {
         let result = processor.html_to_markdown(html);
 
         // Validate extraction
-        assert!(
-            !result.is_empty(),
-            "Extracted content should not be empty"
-        );
+        assert!(!result.is_empty(), "Extracted content should not be empty");
 
         assert!(
             result.len() > 1000,
@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+
+use scraper::{ElementRef, Html, Node, Selector};
+
+use crate::image_handler::{LAZY_SRC_ATTRIBUTES, SRCSET_ATTRIBUTES};
+use crate::table_converter::rename_element;
+
+/// Elements whose source may be deferred by a lazy loader.
+const LAZY_ELEMENTS_SELECTOR: &str = "img, source, iframe, video, audio";
+
+/// Lazy-load attributes and the attribute each one stands in for, in order of
+/// preference: the ones the image handler reads, but the standard `srcset`.
+fn lazy_attributes() -> impl Iterator<Item = (&'static str, &'static str)> {
+    let sources = LAZY_SRC_ATTRIBUTES.iter().map(|lazy| (*lazy, "src"));
+    let srcsets = SRCSET_ATTRIBUTES[1..].iter().map(|lazy| (*lazy, "srcset"));
+    sources.chain(srcsets)
+}
+
+/// Reveals content that browsers only show through JavaScript or user
+/// interaction, so the extraction sees what a reader would.
+///
+/// - `<noscript>` fallbacks in the body are unwrapped, except notices asking to
+///   enable JavaScript and image fallbacks duplicating a lazy-loaded image
+///   right before them.
+/// - Lazy-load attributes (`data-src`, `data-original`, `data-srcset`, ...)
+///   replace the placeholder `src`/`srcset` of images, sources and frames.
+/// - `<template>` contents are moved into the page.
+/// - `<details>` elements are expanded into a `<div>` with the `<summary>` as a
+///   paragraph.
+pub(crate) fn recover_hidden_content(document: &mut Html) {
+    unwrap_noscript(document);
+    promote_lazy_attributes(document);
+    expand_templates(document);
+    expand_details(document);
+}
+
+fn unwrap_noscript(document: &mut Html) {
+    let noscript_selector = Selector::parse("body noscript").unwrap();
+    let image_selector = Selector::parse("img, picture").unwrap();
+
+    let found: Vec<_> = document
+        .root_element()
+        .select(&noscript_selector)
+        .map(|noscript| {
+            // With scripting enabled, parsers keep the fallback as raw text
+            let markup = if noscript.children().any(|child| child.value().is_element()) {
+                noscript.inner_html()
+            } else {
+                noscript.text().collect()
+            };
+            let fallback = Html::parse_fragment(&markup);
+            let text = fallback.root_element().text().collect::<String>();
+            let only_images = text.trim().is_empty()
+                && fallback
+                    .root_element()
+                    .select(&image_selector)
+                    .next()
+                    .is_some();
+            let duplicate = only_images
+                && noscript
+                    .prev_siblings()
+                    .find_map(ElementRef::wrap)
+                    .is_some_and(|sibling| {
+                        sibling.value().name() == "img"
+                            || sibling.select(&image_selector).next().is_some()
+                    });
+            let keep = !duplicate && !text.to_lowercase().contains("javascript");
+            (noscript.id(), keep.then_some(fallback))
+        })
+        .collect();
+
+    for (noscript_id, fallback) in found {
+        let Some(fallback) = fallback else {
+            if let Some(mut noscript) = document.tree.get_mut(noscript_id) {
+                noscript.detach();
+            }
+            continue;
+        };
+
+        // Copy the fallback's nodes in front of the <noscript>, parents first
+        let root = fallback.root_element();
+        let mut copies = HashMap::new();
+        for node in root.descendants().skip(1) {
+            let Some(parent) = node.parent() else {
+                continue;
+            };
+            let target_id = if parent.id() == root.id() {
+                Some(noscript_id)
+            } else {
+                copies.get(&parent.id()).copied()
+            };
+            let Some(mut target) = target_id.and_then(|id| document.tree.get_mut(id)) else {
+                continue;
+            };
+            let copy = if parent.id() == root.id() {
+                target.insert_before(node.value().clone())
+            } else {
+                target.append(node.value().clone())
+            };
+            copies.insert(node.id(), copy.id());
+        }
+
+        if let Some(mut noscript) = document.tree.get_mut(noscript_id) {
+            noscript.detach();
+        }
+    }
+}
+
+fn promote_lazy_attributes(document: &mut Html) {
+    let lazy_selector = Selector::parse(LAZY_ELEMENTS_SELECTOR).unwrap();
+    let lazy_ids: Vec<_> = document
+        .root_element()
+        .select(&lazy_selector)
+        .filter(|element| lazy_attributes().any(|(lazy, _)| element.value().attr(lazy).is_some()))
+        .map(|element| element.id())
+        .collect();
+
+    for element_id in lazy_ids {
+        let Some(mut node) = document.tree.get_mut(element_id) else {
+            continue;
+        };
+        let Node::Element(element) = node.value() else {
+            continue;
+        };
+
+        let mut promoted: Vec<&str> = Vec::new();
+        for (lazy, target) in lazy_attributes() {
+            if promoted.contains(&target) {
+                continue;
+            }
+            let Some((name, value)) = element
+                .attrs
+                .iter()
+                .find(|(name, value)| &*name.local == lazy && !value.trim().is_empty())
+                .map(|(name, value)| (name.clone(), value.clone()))
+            else {
+                continue;
+            };
+
+            element
+                .attrs
+                .retain(|(existing, _)| &*existing.local != target);
+            let mut name = name;
+            name.local = target.into();
+            element.attrs.push((name, value));
+            promoted.push(target);
+        }
+    }
+}
+
+/// Moves the contents of every `<template>` out in front of it.
+fn expand_templates(document: &mut Html) {
+    let template_selector = Selector::parse("body template").unwrap();
+    let templates: Vec<_> = document
+        .root_element()
+        .select(&template_selector)
+        .map(|template| {
+            // The contents hang below a document fragment node
+            let contents: Vec<_> = template
+                .children()
+                .flat_map(|child| match child.value() {
+                    Node::Fragment => child.children().map(|node| node.id()).collect(),
+                    _ => vec![child.id()],
+                })
+                .collect();
+            (template.id(), contents)
+        })
+        .collect();
+
+    for (template_id, contents) in templates {
+        let Some(mut template) = document.tree.get_mut(template_id) else {
+            continue;
+        };
+        for content_id in contents {
+            template.insert_id_before(content_id);
+        }
+        template.detach();
+    }
+}
+
+fn expand_details(document: &mut Html) {
+    let details_selector = Selector::parse("details, summary").unwrap();
+    let ids: Vec<_> = document
+        .root_element()
+        .select(&details_selector)
+        .map(|element| (element.id(), element.value().name() == "summary"))
+        .collect();
+
+    for (element_id, is_summary) in ids {
+        if let Some(mut node) = document.tree.get_mut(element_id) {
+            rename_element(node.value(), if is_summary { "p" } else { "div" });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recover(html: &str) -> String {
+        let mut document = Html::parse_document(html);
+        recover_hidden_content(&mut document);
+        document
+            .root_element()
+            .select(&Selector::parse("body").unwrap())
+            .next()
+            .unwrap()
+            .inner_html()
+    }
+
+    #[test]
+    fn test_recover_unwraps_noscript() {
+        let html = recover(
+            r#"<p>Intro</p><noscript><p>Fallback <b>content</b></p></noscript>
+            <img data-src="/a.jpg" src="placeholder.gif"><noscript><img src="/a.jpg"></noscript>
+            <noscript>Please enable JavaScript to view this page.</noscript>"#,
+        );
+
+        assert!(html.contains("<p>Fallback <b>content</b></p>"));
+        assert_eq!(html.matches("<img").count(), 1);
+        assert!(html.contains(r#"src="/a.jpg""#));
+        assert!(!html.contains("noscript") && !html.contains("JavaScript"));
+    }
+
+    #[test]
+    fn test_recover_promotes_lazy_attributes() {
+        let html = recover(
+            r#"<img src="data:image/gif;base64,R0lGOD" data-src="/photo.jpg" data-srcset="/photo-2x.jpg 2x">
+            <iframe data-src="https://player.example.com/embed/1"></iframe>"#,
+        );
+
+        assert!(html.contains(r#"src="/photo.jpg""#));
+        assert!(html.contains(r#"srcset="/photo-2x.jpg 2x""#));
+        assert!(!html.contains("data:image/gif"));
+        assert!(html.contains(r#"<iframe data-src="https://player.example.com/embed/1" src="https://player.example.com/embed/1">"#));
+    }
+
+    #[test]
+    fn test_recover_expands_templates_and_details() {
+        let html = recover(
+            r#"<p>Intro</p><template><p>Deferred paragraph</p></template>
+            <details><summary>Specifications</summary><p>Weight: 1.2 kg</p></details>"#,
+        );
+
+        assert!(html.starts_with("<p>Intro</p><p>Deferred paragraph</p>"));
+        assert!(!html.contains("template"));
+        assert!(html.contains("<div><p>Specifications</p><p>Weight: 1.2 kg</p></div>"));
+    }
+
+    #[test]
+    fn test_recover_empty_and_missing_content() {
+        assert_eq!(recover(""), "");
+        assert_eq!(recover("<p>Plain page</p>"), "<p>Plain page</p>");
+
+        // Empty fallbacks, templates and lazy attributes leave nothing behind
+        let html = recover(
+            r#"<p>Intro</p><noscript></noscript><template></template>
+            <img src="/kept.jpg" data-src=" "><details><p>No summary</p></details>"#,
+        );
+
+        assert!(!html.contains("noscript") && !html.contains("template"));
+        assert!(html.contains(r#"src="/kept.jpg""#));
+        assert!(html.contains("<div><p>No summary</p></div>"));
+    }
+}
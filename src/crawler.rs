@@ -0,0 +1,549 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use reqwest::Url;
+use reqwest::blocking;
+use reqwest::header::HeaderMap;
+
+use crate::harvest_config::HarvestConfig;
+use crate::harvest_error::HarvestError;
+use crate::harvested_document::HarvestedDocument;
+use crate::http_client::{browser_headers, build_client, fetch_page};
+use crate::markdown_harvester::harvest_pages;
+use crate::site_extractor::never_matching_regex;
+use crate::user_agent::UserAgent;
+
+const DEFAULT_MAX_DEPTH: usize = 2;
+const DEFAULT_MAX_PAGES: usize = 100;
+const DEFAULT_HOST_DELAY_MS: u64 = 1000;
+
+/// Query parameters that only record where a visitor came from; they are
+/// dropped when normalizing URLs so that tracked links are not crawled twice.
+const TRACKING_PARAMETERS: [&str; 5] = ["fbclid", "gclid", "mc_cid", "mc_eid", "ref_src"];
+
+/// Which links a [`Crawler`] follows, relative to its seed URLs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CrawlScope {
+    /// Links to the host of a seed URL.
+    #[default]
+    SameHost,
+    /// Links under the directory of a seed URL: `https://example.com/docs/`
+    /// for the seed `https://example.com/docs/intro`.
+    SamePrefix,
+    /// Any `http(s)` link.
+    Any,
+}
+
+/// Limits of a crawl started with
+/// [`MarkdownHarvester::crawl`](crate::MarkdownHarvester::crawl).
+///
+/// Path patterns are regular expressions matched against the URL path of the
+/// links found; seed URLs are always fetched. An invalid pattern never
+/// matches, as in [`SitemapConfig`](crate::SitemapConfig).
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::{CrawlConfig, CrawlScope};
+///
+/// let config = CrawlConfig::builder()
+///     .max_depth(3)
+///     .max_pages(50)
+///     .scope(CrawlScope::SamePrefix)
+///     .exclude_path(r"/tag/")
+///     .host_delay(500)
+///     .build();
+///
+/// assert_eq!(config.max_depth(), 3);
+/// assert_eq!(config.scope(), CrawlScope::SamePrefix);
+/// assert_eq!(config.exclude_paths(), ["/tag/"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CrawlConfig {
+    max_depth: usize,
+    max_pages: usize,
+    scope: CrawlScope,
+    include_paths: Vec<Regex>,
+    exclude_paths: Vec<Regex>,
+    host_delay: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct CrawlConfigBuilder {
+    max_depth: usize,
+    max_pages: usize,
+    scope: CrawlScope,
+    include_paths: Vec<Regex>,
+    exclude_paths: Vec<Regex>,
+    host_delay: u64,
+}
+
+impl Default for CrawlConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CrawlConfigBuilder {
+    pub fn new() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_pages: DEFAULT_MAX_PAGES,
+            scope: CrawlScope::default(),
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            host_delay: DEFAULT_HOST_DELAY_MS,
+        }
+    }
+
+    /// How many links away from a seed URL the crawl goes; 0 fetches the
+    /// seeds only. Defaults to 2.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// How many pages are fetched at most, failed requests included.
+    /// Defaults to 100.
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
+    pub fn scope(mut self, scope: CrawlScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Only follows links whose path matches one of the include patterns.
+    pub fn include_path(mut self, pattern: &str) -> Self {
+        self.include_paths.push(compile(pattern));
+        self
+    }
+
+    /// Never follows links whose path matches this pattern.
+    pub fn exclude_path(mut self, pattern: &str) -> Self {
+        self.exclude_paths.push(compile(pattern));
+        self
+    }
+
+    /// Minimum time between two requests to the same host, in milliseconds.
+    /// Pages of other hosts are fetched in the meantime. Defaults to 1000.
+    pub fn host_delay(mut self, ms: u64) -> Self {
+        self.host_delay = ms;
+        self
+    }
+
+    pub fn build(self) -> CrawlConfig {
+        CrawlConfig {
+            max_depth: self.max_depth,
+            max_pages: self.max_pages,
+            scope: self.scope,
+            include_paths: self.include_paths,
+            exclude_paths: self.exclude_paths,
+            host_delay: self.host_delay,
+        }
+    }
+}
+
+impl Default for CrawlConfig {
+    fn default() -> Self {
+        CrawlConfigBuilder::new().build()
+    }
+}
+
+impl CrawlConfig {
+    pub fn builder() -> CrawlConfigBuilder {
+        CrawlConfigBuilder::new()
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn max_pages(&self) -> usize {
+        self.max_pages
+    }
+
+    pub fn scope(&self) -> CrawlScope {
+        self.scope
+    }
+
+    pub fn include_paths(&self) -> Vec<&str> {
+        self.include_paths.iter().map(Regex::as_str).collect()
+    }
+
+    pub fn exclude_paths(&self) -> Vec<&str> {
+        self.exclude_paths.iter().map(Regex::as_str).collect()
+    }
+
+    pub fn host_delay(&self) -> u64 {
+        self.host_delay
+    }
+
+    /// Returns `true` when a link found on a page descending from `seeds`
+    /// passes the scope and path filters.
+    fn follows(&self, url: &Url, seeds: &[Url]) -> bool {
+        let in_scope = match self.scope {
+            CrawlScope::SameHost => seeds.iter().any(|seed| seed.host() == url.host()),
+            CrawlScope::SamePrefix => seeds.iter().any(|seed| {
+                seed.join("./")
+                    .is_ok_and(|prefix| url.as_str().starts_with(prefix.as_str()))
+            }),
+            CrawlScope::Any => true,
+        };
+        let path = url.path();
+        in_scope
+            && (self.include_paths.is_empty()
+                || self
+                    .include_paths
+                    .iter()
+                    .any(|pattern| pattern.is_match(path)))
+            && !self
+                .exclude_paths
+                .iter()
+                .any(|pattern| pattern.is_match(path))
+    }
+}
+
+fn compile(pattern: &str) -> Regex {
+    Regex::new(pattern).unwrap_or_else(|_| never_matching_regex())
+}
+
+/// Normalizes a URL for the visited set: only `http(s)` URLs are kept, the
+/// fragment and tracking parameters are dropped. The host is lowercased and
+/// default ports removed by parsing.
+fn normalize_url(url: &str) -> Option<Url> {
+    let mut url = Url::parse(url.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https") || url.host().is_none() {
+        return None;
+    }
+    url.set_fragment(None);
+
+    let query: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| {
+            !name.starts_with("utm_") && !TRACKING_PARAMETERS.contains(&name.as_ref())
+        })
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    if query.is_empty() {
+        url.set_query(None);
+    } else if url.query_pairs().count() != query.len() {
+        url.query_pairs_mut().clear().extend_pairs(query);
+    }
+
+    Some(url)
+}
+
+/// A URL waiting to be fetched, `depth` links away from a seed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct QueuedUrl {
+    url: Url,
+    depth: usize,
+}
+
+/// What the frontier has to offer at a given time.
+#[derive(Debug, PartialEq, Eq)]
+enum Next {
+    Ready(QueuedUrl),
+    /// Every queued URL is on a host that was requested too recently.
+    Wait(Instant),
+    Done,
+}
+
+/// The URLs left to crawl, in breadth-first order, with the visited set and
+/// the time each host may be requested again.
+#[derive(Debug, Default)]
+struct Frontier {
+    queue: VecDeque<QueuedUrl>,
+    seen: HashSet<String>,
+    next_request: HashMap<String, Instant>,
+    host_delay: Duration,
+}
+
+impl Frontier {
+    fn new(host_delay: Duration) -> Self {
+        Self {
+            host_delay,
+            ..Self::default()
+        }
+    }
+
+    /// Marks a URL as visited, returning `false` when it already was.
+    fn mark_seen(&mut self, url: &Url) -> bool {
+        self.seen.insert(url.to_string())
+    }
+
+    /// Queues a URL unless it was seen before.
+    fn push(&mut self, url: Url, depth: usize) {
+        if self.mark_seen(&url) {
+            self.queue.push_back(QueuedUrl { url, depth });
+        }
+    }
+
+    /// Takes the first queued URL whose host may be requested at `now`.
+    fn next(&mut self, now: Instant) -> Next {
+        let ready_at = |queued: &QueuedUrl| {
+            queued
+                .url
+                .host_str()
+                .and_then(|host| self.next_request.get(host).copied())
+        };
+        let position = self
+            .queue
+            .iter()
+            .position(|queued| ready_at(queued).is_none_or(|ready_at| ready_at <= now));
+
+        match position {
+            Some(position) => Next::Ready(self.queue.remove(position).unwrap()),
+            None => self
+                .queue
+                .iter()
+                .filter_map(ready_at)
+                .min()
+                .map_or(Next::Done, Next::Wait),
+        }
+    }
+
+    /// Records that `url`'s host was just requested.
+    fn requested(&mut self, url: &Url, now: Instant) {
+        if let Some(host) = url.host_str() {
+            self.next_request
+                .insert(host.to_string(), now + self.host_delay);
+        }
+    }
+}
+
+/// A bounded crawl from seed URLs, following the links in the extracted
+/// content of each page.
+///
+/// Created by [`MarkdownHarvester::crawl`](crate::MarkdownHarvester::crawl).
+/// The crawl runs as the iterator is advanced: each call fetches pages until
+/// one yields a document, so results stream as they are produced and dropping
+/// the iterator stops the crawl. Pages are visited breadth-first, but a host
+/// requested less than [`CrawlConfig::host_delay`] ago is skipped in favour of
+/// the next queued host, and waited for only when no other host is queued.
+///
+/// Each page goes through the same pipeline as
+/// [`get_hyperlinks_documents`](crate::MarkdownHarvester::get_hyperlinks_documents),
+/// so feeds, PDFs, rendering and front matter apply as configured.
+pub struct Crawler {
+    config: HarvestConfig,
+    crawl_config: CrawlConfig,
+    seeds: Vec<Url>,
+    frontier: Frontier,
+    pending: VecDeque<Result<HarvestedDocument, HarvestError>>,
+    fetched: usize,
+    client: blocking::Client,
+    headers: HeaderMap,
+}
+
+impl Crawler {
+    pub(crate) fn new(seeds: &[String], config: HarvestConfig, crawl_config: CrawlConfig) -> Self {
+        let mut frontier = Frontier::new(Duration::from_millis(crawl_config.host_delay()));
+        let mut pending = VecDeque::new();
+        let mut seed_urls = Vec::new();
+
+        for seed in seeds {
+            match normalize_url(seed) {
+                Some(url) => {
+                    seed_urls.push(url.clone());
+                    frontier.push(url, 0);
+                }
+                None => pending.push_back(Err(HarvestError::Request {
+                    url: seed.clone(),
+                    message: "not an http(s) URL".to_string(),
+                })),
+            }
+        }
+
+        Self {
+            client: build_client(config.http_config()),
+            headers: browser_headers(UserAgent::random()),
+            config,
+            crawl_config,
+            seeds: seed_urls,
+            frontier,
+            pending,
+            fetched: 0,
+        }
+    }
+
+    /// Number of pages requested so far.
+    pub fn pages_fetched(&self) -> usize {
+        self.fetched
+    }
+
+    /// Fetches and harvests one page, queuing the links of its documents.
+    fn visit(&mut self, queued: QueuedUrl) {
+        self.fetched += 1;
+        let page = fetch_page(&self.client, queued.url.as_str(), self.headers.clone());
+        self.frontier.requested(&queued.url, Instant::now());
+
+        // A redirect to a page that was crawled already yields nothing new
+        if let Ok(page) = &page
+            && let Some(final_url) = normalize_url(&page.final_url)
+            && final_url != queued.url
+            && !self.frontier.mark_seen(&final_url)
+        {
+            return;
+        }
+
+        for document in harvest_pages(vec![page], &self.client, &self.headers, &self.config) {
+            if let Ok(document) = &document
+                && queued.depth < self.crawl_config.max_depth()
+            {
+                for link in document.links.iter().filter_map(|link| normalize_url(link)) {
+                    if self.crawl_config.follows(&link, &self.seeds) {
+                        self.frontier.push(link, queued.depth + 1);
+                    }
+                }
+            }
+            self.pending.push_back(document);
+        }
+    }
+}
+
+impl Iterator for Crawler {
+    type Item = Result<HarvestedDocument, HarvestError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(document) = self.pending.pop_front() {
+                return Some(document);
+            }
+            if self.fetched >= self.crawl_config.max_pages() {
+                return None;
+            }
+            match self.frontier.next(Instant::now()) {
+                Next::Ready(queued) => self.visit(queued),
+                Next::Wait(until) => thread::sleep(until.saturating_duration_since(Instant::now())),
+                Next::Done => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn test_crawl_config_defaults_and_builder() {
+        let config = CrawlConfig::default();
+        assert_eq!(config.max_depth(), 2);
+        assert_eq!(config.max_pages(), 100);
+        assert_eq!(config.scope(), CrawlScope::SameHost);
+        assert!(config.include_paths().is_empty());
+        assert_eq!(config.host_delay(), 1000);
+
+        let config = CrawlConfig::builder()
+            .max_depth(0)
+            .max_pages(5)
+            .scope(CrawlScope::Any)
+            .include_path(r"^/blog/")
+            .host_delay(0)
+            .build();
+        assert_eq!(config.max_depth(), 0);
+        assert_eq!(config.max_pages(), 5);
+        assert_eq!(config.scope(), CrawlScope::Any);
+        assert_eq!(config.include_paths(), ["^/blog/"]);
+        assert_eq!(config.host_delay(), 0);
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        let normalized =
+            normalize_url("https://Example.COM:443/guide?utm_source=feed&page=2#setup");
+        assert_eq!(
+            normalized.unwrap().as_str(),
+            "https://example.com/guide?page=2"
+        );
+        assert_eq!(
+            normalize_url("http://example.com/a?fbclid=abc")
+                .unwrap()
+                .as_str(),
+            "http://example.com/a"
+        );
+        assert!(normalize_url("mailto:editor@example.com").is_none());
+        assert!(normalize_url("/relative").is_none());
+    }
+
+    #[test]
+    fn test_crawl_config_follows_scope_and_paths() {
+        let seeds = [url("https://example.com/docs/intro")];
+
+        let same_host = CrawlConfig::default();
+        assert!(same_host.follows(&url("https://example.com/blog/post"), &seeds));
+        assert!(!same_host.follows(&url("https://other.org/docs/"), &seeds));
+
+        let same_prefix = CrawlConfig::builder().scope(CrawlScope::SamePrefix).build();
+        assert!(same_prefix.follows(&url("https://example.com/docs/setup"), &seeds));
+        assert!(!same_prefix.follows(&url("https://example.com/blog/post"), &seeds));
+
+        let filtered = CrawlConfig::builder()
+            .scope(CrawlScope::Any)
+            .include_path(r"^/docs/")
+            .exclude_path(r"/v1/")
+            .build();
+        assert!(filtered.follows(&url("https://other.org/docs/guide"), &seeds));
+        assert!(!filtered.follows(&url("https://other.org/docs/v1/guide"), &seeds));
+        assert!(!filtered.follows(&url("https://other.org/about"), &seeds));
+    }
+
+    #[test]
+    fn test_frontier_dedupes_and_schedules_hosts() {
+        let now = Instant::now();
+        let mut frontier = Frontier::new(Duration::from_millis(1000));
+        frontier.push(url("https://a.example/1"), 0);
+        frontier.push(url("https://a.example/2"), 1);
+        frontier.push(url("https://a.example/1"), 1);
+        frontier.push(url("https://b.example/1"), 1);
+
+        let Next::Ready(first) = frontier.next(now) else {
+            panic!("expected a ready URL");
+        };
+        assert_eq!(first.url.as_str(), "https://a.example/1");
+        frontier.requested(&first.url, now);
+
+        // a.example was just requested, so b.example goes first
+        let Next::Ready(second) = frontier.next(now) else {
+            panic!("expected a ready URL");
+        };
+        assert_eq!(second.url.as_str(), "https://b.example/1");
+        frontier.requested(&second.url, now);
+
+        assert_eq!(
+            frontier.next(now),
+            Next::Wait(now + Duration::from_millis(1000))
+        );
+        let Next::Ready(third) = frontier.next(now + Duration::from_millis(1000)) else {
+            panic!("expected a ready URL");
+        };
+        assert_eq!(third.url.as_str(), "https://a.example/2");
+        assert_eq!(third.depth, 1);
+        assert_eq!(frontier.next(now), Next::Done);
+    }
+
+    #[test]
+    fn test_crawler_reports_invalid_seeds() {
+        let crawl_config = CrawlConfig::builder().max_pages(0).build();
+        let mut crawler = Crawler::new(
+            &["ftp://example.com/file".to_string()],
+            HarvestConfig::default(),
+            crawl_config,
+        );
+
+        let error = crawler.next().unwrap().unwrap_err();
+        assert_eq!(error.url(), "ftp://example.com/file");
+        assert!(crawler.next().is_none());
+        assert_eq!(crawler.pages_fetched(), 0);
+    }
+}
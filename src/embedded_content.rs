@@ -0,0 +1,253 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::{Html, Selector};
+use serde_json::Value;
+
+use crate::page_metadata::find_article_json_ld;
+
+/// Strings with less text than this are not considered article content.
+const MIN_EMBEDDED_CHARS: usize = 200;
+
+/// Extracted markup with less text than this is considered too thin, and the
+/// embedded state is searched for the article instead.
+pub(crate) const THIN_CONTENT_CHARS: usize = 500;
+
+/// Keys whose string values hold plain-text article bodies. Other keys are only
+/// considered when their value is HTML.
+const TEXT_KEYS: [&str; 6] = [
+    "articlebody",
+    "body",
+    "content",
+    "text",
+    "bodytext",
+    "markdown",
+];
+
+/// `window.<name> = {...}` assignments of known embedded-state globals.
+static STATE_ASSIGNMENT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?s)window\.(__NUXT__|__INITIAL_STATE__|__PRELOADED_STATE__|__APOLLO_STATE__)\s*=\s*(\{.*\})\s*;?\s*$",
+    )
+    .unwrap()
+});
+
+static HTML_TAG_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"</?(?:p|h[1-6]|ul|ol|li|blockquote|div|br|strong|em|a)\b[^>]*>").unwrap()
+});
+
+static ANY_TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]+>").unwrap());
+
+/// Article content found in a script rather than in the rendered markup.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EmbeddedContent {
+    /// Where the content was found, e.g. `__NEXT_DATA__` or `JSON-LD articleBody`.
+    pub(crate) source: String,
+    /// The content as HTML, ready for the Markdown conversion.
+    pub(crate) html: String,
+    /// Number of text characters in the content.
+    pub(crate) text_chars: usize,
+}
+
+/// Looks for the article in the embedded state of client-side rendered pages.
+///
+/// JSON-LD `articleBody`, Next.js `__NEXT_DATA__`, Nuxt `__NUXT_DATA__` and
+/// `window.__NUXT__` (and similar `window.__*_STATE__` globals written as JSON)
+/// are searched for the string with the most text that is HTML, or plain text
+/// under a body-like key. Plain text is split into paragraphs.
+pub(crate) fn extract_embedded_content(document: &Html) -> Option<EmbeddedContent> {
+    let mut candidates: Vec<EmbeddedContent> = Vec::new();
+
+    if let Some(body) = find_article_json_ld(document)
+        .as_ref()
+        .and_then(|article| article.get("articleBody"))
+        .and_then(Value::as_str)
+    {
+        candidates.extend(candidate("JSON-LD articleBody", body));
+    }
+
+    let script_selector = Selector::parse("script").unwrap();
+    for script in document.root_element().select(&script_selector) {
+        let id = script.value().attr("id").unwrap_or_default();
+        let source = script.text().collect::<String>();
+
+        let state = match id {
+            "__NEXT_DATA__" | "__NUXT_DATA__" => serde_json::from_str::<Value>(&source)
+                .ok()
+                .map(|value| (id.to_string(), value)),
+            _ => STATE_ASSIGNMENT_REGEX
+                .captures(source.trim())
+                .and_then(|caps| {
+                    serde_json::from_str::<Value>(&caps[2])
+                        .ok()
+                        .map(|value| (format!("window.{}", &caps[1]), value))
+                }),
+        };
+        if let Some((name, value)) = state {
+            candidates.extend(richest_string(&value, None).and_then(|text| candidate(&name, text)));
+        }
+    }
+
+    candidates.into_iter().reduce(|best, candidate| {
+        if candidate.text_chars > best.text_chars {
+            candidate
+        } else {
+            best
+        }
+    })
+}
+
+/// Walks a JSON value and returns the eligible string with the most text.
+fn richest_string<'a>(value: &'a Value, key: Option<&str>) -> Option<&'a str> {
+    match value {
+        Value::String(text) => {
+            let is_text_key =
+                key.is_some_and(|key| TEXT_KEYS.contains(&key.to_ascii_lowercase().as_str()));
+            (is_text_key || looks_like_html(text)).then_some(text.as_str())
+        }
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| richest_string(item, key))
+            .max_by_key(|text| text_chars(text)),
+        Value::Object(object) => object
+            .iter()
+            .filter_map(|(key, item)| richest_string(item, Some(key)))
+            .max_by_key(|text| text_chars(text)),
+        _ => None,
+    }
+}
+
+fn candidate(source: &str, text: &str) -> Option<EmbeddedContent> {
+    let text_chars = text_chars(text);
+    if text_chars < MIN_EMBEDDED_CHARS {
+        return None;
+    }
+    let html = if looks_like_html(text) {
+        text.to_string()
+    } else {
+        paragraphs_to_html(text)
+    };
+    Some(EmbeddedContent {
+        source: source.to_string(),
+        html,
+        text_chars,
+    })
+}
+
+fn looks_like_html(text: &str) -> bool {
+    HTML_TAG_REGEX.is_match(text)
+}
+
+/// Counts the characters of `text` outside HTML tags.
+pub(crate) fn text_chars(text: &str) -> usize {
+    ANY_TAG_REGEX.replace_all(text, "").trim().chars().count()
+}
+
+/// Wraps the lines of a plain-text body in escaped `<p>` elements.
+fn paragraphs_to_html(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let escaped = line
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            format!("<p>{}</p>", escaped)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paragraph(topic: &str) -> String {
+        format!(
+            "The {} section explains how the release was planned, built and rolled out \
+             across every region, including the lessons the team learned along the way.",
+            topic
+        )
+    }
+
+    #[test]
+    fn test_extract_embedded_content_next_data() {
+        let state = serde_json::json!({
+            "props": {"pageProps": {
+                "title": "Release notes",
+                "teaser": paragraph("teaser"),
+                "post": {"html": format!("<h2>Rollout</h2><p>{}</p><p>{}</p>", paragraph("first"), paragraph("second"))}
+            }}
+        });
+        let html = format!(
+            r#"<html><body><div id="__next"></div>
+            <script id="__NEXT_DATA__" type="application/json">{}</script></body></html>"#,
+            state
+        );
+
+        let content = extract_embedded_content(&Html::parse_document(&html)).unwrap();
+        assert_eq!(content.source, "__NEXT_DATA__");
+        assert!(
+            content
+                .html
+                .starts_with("<h2>Rollout</h2><p>The first section")
+        );
+        assert!(!content.html.contains("teaser"));
+    }
+
+    #[test]
+    fn test_extract_embedded_content_nuxt_and_json_ld() {
+        let nuxt = format!(
+            r#"<html><body><script>window.__NUXT__ = {{"data": [{{"body": "{}\n{}"}}]}};</script></body></html>"#,
+            paragraph("first"),
+            paragraph("second")
+        );
+        let content = extract_embedded_content(&Html::parse_document(&nuxt)).unwrap();
+        assert_eq!(content.source, "window.__NUXT__");
+        assert_eq!(
+            content.html,
+            format!(
+                "<p>{}</p><p>{}</p>",
+                paragraph("first"),
+                paragraph("second")
+            )
+        );
+
+        let json_ld = format!(
+            r#"<html><head><script type="application/ld+json">
+                {{"@type": "NewsArticle", "articleBody": "{} {} <b>"}}
+            </script></head><body></body></html>"#,
+            paragraph("first"),
+            paragraph("second")
+        );
+        let content = extract_embedded_content(&Html::parse_document(&json_ld)).unwrap();
+        assert_eq!(content.source, "JSON-LD articleBody");
+        assert!(content.html.ends_with("along the way. &lt;b&gt;</p>"));
+    }
+
+    #[test]
+    fn test_extract_embedded_content_ignores_short_and_unknown_state() {
+        let html = format!(
+            r#"<html><body>
+            <script id="__NEXT_DATA__" type="application/json">{{"props": {{"body": "Too short"}}}}</script>
+            <script>var config = {{"body": "{}"}};</script></body></html>"#,
+            paragraph("unknown")
+        );
+        assert_eq!(extract_embedded_content(&Html::parse_document(&html)), None);
+    }
+
+    #[test]
+    fn test_extract_embedded_content_empty_and_invalid_state() {
+        assert_eq!(extract_embedded_content(&Html::parse_document("")), None);
+
+        // Malformed JSON, empty state and bodies of the wrong type are skipped
+        let html = format!(
+            r#"<html><head><script type="application/ld+json">{{"@type": "Article"}}</script></head><body>
+            <script id="__NEXT_DATA__" type="application/json">{{"props": {{"body": "{}"</script>
+            <script id="__NUXT_DATA__" type="application/json"></script>
+            <script>window.__INITIAL_STATE__ = {{"body": 42, "content": null, "text": ["Too short"]}};</script>
+            </body></html>"#,
+            paragraph("truncated")
+        );
+        assert_eq!(extract_embedded_content(&Html::parse_document(&html)), None);
+    }
+}
@@ -0,0 +1,671 @@
+use crate::boilerplate::{BoilerplateLanguage, BoilerplatePack, BoilerplatePacks};
+use crate::image_handler::ImageMode;
+use crate::link_handler::LinkMode;
+use crate::output_format::OutputFormat;
+use crate::patterns::{
+    additional_cleanup, content_selectors, media_elements, navigation_terms, text_selectors,
+    unwanted_elements, unwanted_text_patterns,
+};
+
+/// Rules that drive content extraction and cleaning in [`ContentProcessor`](crate::ContentProcessor).
+///
+/// `ExtractionConfig` replaces the fixed pattern arrays from the `patterns` module with
+/// owned, editable lists. Its default value reproduces the built-in behaviour exactly,
+/// so only the rules that need to change have to be touched.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::{ContentProcessor, ExtractionConfig};
+///
+/// let config = ExtractionConfig::builder()
+///     .add_content_selector("#main-content")
+///     .remove_removal_selector("header")
+///     .add_navigation_term("newsletter")
+///     .build();
+///
+/// let processor = ContentProcessor::with_config(config);
+/// let markdown = processor.html_to_markdown("<html><body><p>Hello world</p></body></html>");
+/// assert!(markdown.contains("Hello world"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractionConfig {
+    content_selectors: Vec<String>,
+    text_selectors: Vec<String>,
+    removal_selectors: Vec<String>,
+    text_patterns: Vec<String>,
+    navigation_terms: Vec<String>,
+    boilerplate_language: BoilerplateLanguage,
+    boilerplate_packs: BoilerplatePacks,
+    link_mode: LinkMode,
+    image_mode: ImageMode,
+    preserve_code_blocks: bool,
+    recover_hidden_content: bool,
+    embedded_content: bool,
+    convert_tables: bool,
+    inject_title: bool,
+    cleaning_report: bool,
+    output_format: OutputFormat,
+}
+
+/// Builder for [`ExtractionConfig`].
+///
+/// A new builder starts from the default rules. Each list can be extended with
+/// `add_*`, trimmed with `remove_*`, or replaced wholesale with the plural setter.
+#[derive(Debug, Clone)]
+pub struct ExtractionConfigBuilder {
+    content_selectors: Vec<String>,
+    text_selectors: Vec<String>,
+    removal_selectors: Vec<String>,
+    text_patterns: Vec<String>,
+    navigation_terms: Vec<String>,
+    boilerplate_language: BoilerplateLanguage,
+    boilerplate_packs: BoilerplatePacks,
+    link_mode: LinkMode,
+    image_mode: ImageMode,
+    preserve_code_blocks: bool,
+    recover_hidden_content: bool,
+    embedded_content: bool,
+    convert_tables: bool,
+    inject_title: bool,
+    cleaning_report: bool,
+    output_format: OutputFormat,
+}
+
+impl Default for ExtractionConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExtractionConfigBuilder {
+    pub fn new() -> Self {
+        Self {
+            content_selectors: to_strings(content_selectors()),
+            text_selectors: to_strings(text_selectors()),
+            removal_selectors: to_strings(
+                media_elements()
+                    .into_iter()
+                    .chain(unwanted_elements())
+                    .chain(additional_cleanup()),
+            ),
+            text_patterns: to_strings(unwanted_text_patterns()),
+            navigation_terms: to_strings(navigation_terms()),
+            boilerplate_language: BoilerplateLanguage::default(),
+            boilerplate_packs: BoilerplatePacks::builtin(),
+            link_mode: LinkMode::default(),
+            image_mode: ImageMode::default(),
+            preserve_code_blocks: true,
+            recover_hidden_content: true,
+            embedded_content: true,
+            convert_tables: true,
+            inject_title: false,
+            cleaning_report: false,
+            output_format: OutputFormat::default(),
+        }
+    }
+
+    /// Replaces the selectors used to locate the main content container.
+    ///
+    /// Selectors are tried in order and the first one that matches wins.
+    pub fn content_selectors<I, S>(mut self, selectors: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.content_selectors = to_strings(selectors);
+        self
+    }
+
+    pub fn add_content_selector(mut self, selector: impl Into<String>) -> Self {
+        push_unique(&mut self.content_selectors, selector.into());
+        self
+    }
+
+    pub fn remove_content_selector(mut self, selector: &str) -> Self {
+        self.content_selectors.retain(|s| s != selector);
+        self
+    }
+
+    /// Replaces the selectors used to collect individual text elements when no
+    /// content container is found.
+    pub fn text_selectors<I, S>(mut self, selectors: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.text_selectors = to_strings(selectors);
+        self
+    }
+
+    pub fn add_text_selector(mut self, selector: impl Into<String>) -> Self {
+        push_unique(&mut self.text_selectors, selector.into());
+        self
+    }
+
+    pub fn remove_text_selector(mut self, selector: &str) -> Self {
+        self.text_selectors.retain(|s| s != selector);
+        self
+    }
+
+    /// Replaces the selectors whose matching elements are removed, subtree and all,
+    /// before Markdown conversion.
+    pub fn removal_selectors<I, S>(mut self, selectors: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.removal_selectors = to_strings(selectors);
+        self
+    }
+
+    pub fn add_removal_selector(mut self, selector: impl Into<String>) -> Self {
+        push_unique(&mut self.removal_selectors, selector.into());
+        self
+    }
+
+    pub fn remove_removal_selector(mut self, selector: &str) -> Self {
+        self.removal_selectors.retain(|s| s != selector);
+        self
+    }
+
+    /// Replaces the regex patterns applied to the Markdown text to strip boilerplate phrases.
+    ///
+    /// Patterns that fail to compile are ignored at processing time.
+    pub fn text_patterns<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.text_patterns = to_strings(patterns);
+        self
+    }
+
+    pub fn add_text_pattern(mut self, pattern: impl Into<String>) -> Self {
+        push_unique(&mut self.text_patterns, pattern.into());
+        self
+    }
+
+    pub fn remove_text_pattern(mut self, pattern: &str) -> Self {
+        self.text_patterns.retain(|p| p != pattern);
+        self
+    }
+
+    /// Replaces the single-word navigation terms whose lines are dropped from the output.
+    ///
+    /// Terms are compared case-insensitively.
+    pub fn navigation_terms<I, S>(mut self, terms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.navigation_terms = terms
+            .into_iter()
+            .map(|term| term.into().to_lowercase())
+            .collect();
+        self
+    }
+
+    pub fn add_navigation_term(mut self, term: impl Into<String>) -> Self {
+        push_unique(&mut self.navigation_terms, term.into().to_lowercase());
+        self
+    }
+
+    pub fn remove_navigation_term(mut self, term: &str) -> Self {
+        let term = term.to_lowercase();
+        self.navigation_terms.retain(|t| *t != term);
+        self
+    }
+
+    /// Sets which [`BoilerplatePack`] is applied to a page.
+    ///
+    /// Defaults to [`BoilerplateLanguage::Auto`], which uses the pack for the
+    /// detected page language.
+    pub fn boilerplate_language(mut self, boilerplate_language: BoilerplateLanguage) -> Self {
+        self.boilerplate_language = boilerplate_language;
+        self
+    }
+
+    /// Replaces the boilerplate packs, which default to the built-in ones.
+    pub fn boilerplate_packs(mut self, boilerplate_packs: BoilerplatePacks) -> Self {
+        self.boilerplate_packs = boilerplate_packs;
+        self
+    }
+
+    /// Adds a boilerplate pack, merging it into the pack already registered for
+    /// its language.
+    pub fn add_boilerplate_pack(mut self, pack: BoilerplatePack) -> Self {
+        self.boilerplate_packs.register(pack);
+        self
+    }
+
+    /// Sets how hyperlinks are rendered in the Markdown output.
+    ///
+    /// Defaults to [`LinkMode::Strip`].
+    pub fn link_mode(mut self, link_mode: LinkMode) -> Self {
+        self.link_mode = link_mode;
+        self
+    }
+
+    /// Sets how images and figures are rendered in the Markdown output.
+    ///
+    /// Defaults to [`ImageMode::Remove`]. With the other modes, images are set
+    /// aside before the removal selectors run, so the `img`, `picture` and `svg`
+    /// selectors no longer apply to them; images inside removed elements such
+    /// as navigation are still dropped.
+    pub fn image_mode(mut self, image_mode: ImageMode) -> Self {
+        self.image_mode = image_mode;
+        self
+    }
+
+    /// Sets whether `<pre>` blocks are kept as fenced Markdown code blocks.
+    ///
+    /// Enabled by default. When disabled, code blocks are dropped from the output
+    /// as in earlier versions.
+    pub fn preserve_code_blocks(mut self, preserve_code_blocks: bool) -> Self {
+        self.preserve_code_blocks = preserve_code_blocks;
+        self
+    }
+
+    /// Sets whether content hidden from non-JavaScript readers is recovered
+    /// before the main content is located.
+    ///
+    /// Enabled by default: `<noscript>` fallbacks are unwrapped, lazy-load
+    /// attributes such as `data-src` and `data-original` replace placeholder
+    /// sources, and `<template>` and `<details>` contents are expanded.
+    pub fn recover_hidden_content(mut self, recover_hidden_content: bool) -> Self {
+        self.recover_hidden_content = recover_hidden_content;
+        self
+    }
+
+    /// Sets whether the article is looked for in the page's embedded JSON
+    /// state when the markup yields too little content.
+    ///
+    /// Enabled by default. Client-side rendered pages often ship the article in
+    /// JSON-LD `articleBody`, Next.js `__NEXT_DATA__` or Nuxt `window.__NUXT__`
+    /// rather than in their markup; the richest HTML or text field found there
+    /// is converted instead of the thin rendered page.
+    pub fn embedded_content(mut self, embedded_content: bool) -> Self {
+        self.embedded_content = embedded_content;
+        self
+    }
+
+    /// Sets whether tables are converted by the built-in table converter.
+    ///
+    /// Enabled by default: simple grids become GitHub-flavored Markdown tables,
+    /// tables using spans or block content become key-value lists and layout
+    /// tables are flattened. When disabled, tables are left to html2md.
+    pub fn convert_tables(mut self, convert_tables: bool) -> Self {
+        self.convert_tables = convert_tables;
+        self
+    }
+
+    /// Sets whether the page title is added as a top-level `#` heading at the
+    /// start of the Markdown, unless the content already begins with it.
+    ///
+    /// Disabled by default.
+    pub fn inject_title(mut self, inject_title: bool) -> Self {
+        self.inject_title = inject_title;
+        self
+    }
+
+    /// Sets whether a [`CleaningReport`](crate::CleaningReport) listing every
+    /// removed element and line, with the rule that removed it, is collected into
+    /// [`ExtractionReport::cleaning`](crate::ExtractionReport::cleaning).
+    ///
+    /// Disabled by default.
+    pub fn cleaning_report(mut self, cleaning_report: bool) -> Self {
+        self.cleaning_report = cleaning_report;
+        self
+    }
+
+    /// Sets the format of the content returned by
+    /// [`ContentProcessor::html_to_output`](crate::ContentProcessor::html_to_output)
+    /// and [`ExtractedContent::content`](crate::ExtractedContent::content).
+    ///
+    /// Defaults to [`OutputFormat::Gfm`]. With [`OutputFormat::CommonMark`], tables
+    /// are rendered as key-value lists.
+    pub fn output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
+    pub fn build(self) -> ExtractionConfig {
+        ExtractionConfig {
+            content_selectors: self.content_selectors,
+            text_selectors: self.text_selectors,
+            removal_selectors: self.removal_selectors,
+            text_patterns: self.text_patterns,
+            navigation_terms: self.navigation_terms,
+            boilerplate_language: self.boilerplate_language,
+            boilerplate_packs: self.boilerplate_packs,
+            link_mode: self.link_mode,
+            image_mode: self.image_mode,
+            preserve_code_blocks: self.preserve_code_blocks,
+            recover_hidden_content: self.recover_hidden_content,
+            embedded_content: self.embedded_content,
+            convert_tables: self.convert_tables,
+            inject_title: self.inject_title,
+            cleaning_report: self.cleaning_report,
+            output_format: self.output_format,
+        }
+    }
+}
+
+impl Default for ExtractionConfig {
+    fn default() -> Self {
+        ExtractionConfigBuilder::new().build()
+    }
+}
+
+impl ExtractionConfig {
+    pub fn builder() -> ExtractionConfigBuilder {
+        ExtractionConfigBuilder::new()
+    }
+
+    pub fn content_selectors(&self) -> &[String] {
+        &self.content_selectors
+    }
+
+    pub fn text_selectors(&self) -> &[String] {
+        &self.text_selectors
+    }
+
+    pub fn removal_selectors(&self) -> &[String] {
+        &self.removal_selectors
+    }
+
+    pub fn text_patterns(&self) -> &[String] {
+        &self.text_patterns
+    }
+
+    pub fn navigation_terms(&self) -> &[String] {
+        &self.navigation_terms
+    }
+
+    pub fn boilerplate_language(&self) -> &BoilerplateLanguage {
+        &self.boilerplate_language
+    }
+
+    pub fn boilerplate_packs(&self) -> &BoilerplatePacks {
+        &self.boilerplate_packs
+    }
+
+    pub fn link_mode(&self) -> LinkMode {
+        self.link_mode
+    }
+
+    pub fn image_mode(&self) -> ImageMode {
+        self.image_mode
+    }
+
+    pub fn preserve_code_blocks(&self) -> bool {
+        self.preserve_code_blocks
+    }
+
+    pub fn recover_hidden_content(&self) -> bool {
+        self.recover_hidden_content
+    }
+
+    pub fn embedded_content(&self) -> bool {
+        self.embedded_content
+    }
+
+    pub fn convert_tables(&self) -> bool {
+        self.convert_tables
+    }
+
+    pub fn inject_title(&self) -> bool {
+        self.inject_title
+    }
+
+    pub fn cleaning_report(&self) -> bool {
+        self.cleaning_report
+    }
+
+    pub fn output_format(&self) -> OutputFormat {
+        self.output_format
+    }
+}
+
+fn to_strings<I, S>(values: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    values.into_iter().map(Into::into).collect()
+}
+
+fn push_unique(values: &mut Vec<String>, value: String) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extraction_config_default_matches_patterns() {
+        let config = ExtractionConfig::default();
+
+        assert_eq!(config.content_selectors(), to_strings(content_selectors()));
+        assert_eq!(config.text_selectors(), to_strings(text_selectors()));
+        assert_eq!(config.text_patterns(), to_strings(unwanted_text_patterns()));
+        assert_eq!(config.navigation_terms(), to_strings(navigation_terms()));
+        assert_eq!(config.link_mode(), LinkMode::Strip);
+        assert_eq!(config.image_mode(), ImageMode::Remove);
+        assert_eq!(config.boilerplate_language(), &BoilerplateLanguage::Auto);
+        assert_eq!(config.boilerplate_packs(), &BoilerplatePacks::builtin());
+        assert!(config.preserve_code_blocks());
+        assert!(config.recover_hidden_content());
+        assert!(config.embedded_content());
+        assert!(config.convert_tables());
+        assert!(!config.inject_title());
+        assert!(!config.cleaning_report());
+        assert_eq!(config.output_format(), OutputFormat::Gfm);
+        assert_eq!(
+            config.removal_selectors().len(),
+            media_elements().len() + unwanted_elements().len() + additional_cleanup().len()
+        );
+    }
+
+    #[test]
+    fn test_extraction_config_builder_default() {
+        let from_builder = ExtractionConfigBuilder::default().build();
+        assert_eq!(from_builder, ExtractionConfig::default());
+        assert_eq!(
+            ExtractionConfig::builder().build(),
+            ExtractionConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_extraction_config_builder_add_content_selector() {
+        let config = ExtractionConfig::builder()
+            .add_content_selector("#main-content")
+            .build();
+
+        assert_eq!(
+            config.content_selectors().last(),
+            Some(&"#main-content".to_string())
+        );
+        assert_eq!(
+            config.content_selectors().len(),
+            content_selectors().len() + 1
+        );
+    }
+
+    #[test]
+    fn test_extraction_config_builder_add_is_idempotent() {
+        let config = ExtractionConfig::builder()
+            .add_content_selector("article")
+            .add_removal_selector("nav")
+            .build();
+
+        assert_eq!(config, ExtractionConfig::default());
+    }
+
+    #[test]
+    fn test_extraction_config_builder_remove_selectors() {
+        let config = ExtractionConfig::builder()
+            .remove_content_selector(".post")
+            .remove_removal_selector("header")
+            .remove_text_selector("pre")
+            .build();
+
+        assert!(!config.content_selectors().contains(&".post".to_string()));
+        assert!(!config.removal_selectors().contains(&"header".to_string()));
+        assert!(!config.text_selectors().contains(&"pre".to_string()));
+        assert!(config.removal_selectors().contains(&"footer".to_string()));
+    }
+
+    #[test]
+    fn test_extraction_config_builder_replace_lists() {
+        let config = ExtractionConfig::builder()
+            .content_selectors(["#content"])
+            .removal_selectors(Vec::<String>::new())
+            .text_patterns([r"(?i)\bpromo\b"])
+            .build();
+
+        assert_eq!(config.content_selectors(), ["#content".to_string()]);
+        assert!(config.removal_selectors().is_empty());
+        assert_eq!(config.text_patterns(), [r"(?i)\bpromo\b".to_string()]);
+    }
+
+    #[test]
+    fn test_extraction_config_builder_text_patterns() {
+        let config = ExtractionConfig::builder()
+            .add_text_pattern(r"(?i)\bpromo code\b")
+            .remove_text_pattern(r"(?i)\bclick here\b")
+            .build();
+
+        assert!(
+            config
+                .text_patterns()
+                .contains(&r"(?i)\bpromo code\b".to_string())
+        );
+        assert!(
+            !config
+                .text_patterns()
+                .contains(&r"(?i)\bclick here\b".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extraction_config_builder_navigation_terms_are_lowercased() {
+        let config = ExtractionConfig::builder()
+            .add_navigation_term("Newsletter")
+            .remove_navigation_term("HOME")
+            .build();
+
+        assert!(
+            config
+                .navigation_terms()
+                .contains(&"newsletter".to_string())
+        );
+        assert!(!config.navigation_terms().contains(&"home".to_string()));
+
+        let replaced = ExtractionConfig::builder()
+            .navigation_terms(["Menu", "Top"])
+            .build();
+        assert_eq!(
+            replaced.navigation_terms(),
+            ["menu".to_string(), "top".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_extraction_config_builder_link_mode() {
+        let config = ExtractionConfig::builder()
+            .link_mode(LinkMode::Reference)
+            .build();
+        assert_eq!(config.link_mode(), LinkMode::Reference);
+    }
+
+    #[test]
+    fn test_extraction_config_builder_image_mode() {
+        let config = ExtractionConfig::builder()
+            .image_mode(ImageMode::Placeholder)
+            .build();
+        assert_eq!(config.image_mode(), ImageMode::Placeholder);
+    }
+
+    #[test]
+    fn test_extraction_config_builder_boilerplate() {
+        let config = ExtractionConfig::builder()
+            .boilerplate_language(BoilerplateLanguage::Fixed("es".to_string()))
+            .add_boilerplate_pack(BoilerplatePack::new("es").navigation_term("Portada del día"))
+            .build();
+        assert_eq!(
+            config.boilerplate_language(),
+            &BoilerplateLanguage::Fixed("es".to_string())
+        );
+        let spanish = config.boilerplate_packs().find("es").unwrap();
+        assert!(spanish.is_boilerplate_line("Portada del día"));
+        assert!(spanish.is_boilerplate_line("Suscríbete"));
+
+        let config = ExtractionConfig::builder()
+            .boilerplate_packs(BoilerplatePacks::new())
+            .build();
+        assert!(config.boilerplate_packs().is_empty());
+    }
+
+    #[test]
+    fn test_extraction_config_builder_preserve_code_blocks() {
+        let config = ExtractionConfig::builder()
+            .preserve_code_blocks(false)
+            .build();
+        assert!(!config.preserve_code_blocks());
+    }
+
+    #[test]
+    fn test_extraction_config_builder_recover_hidden_content() {
+        let config = ExtractionConfig::builder()
+            .recover_hidden_content(false)
+            .build();
+        assert!(!config.recover_hidden_content());
+    }
+
+    #[test]
+    fn test_extraction_config_builder_embedded_content() {
+        let config = ExtractionConfig::builder().embedded_content(false).build();
+        assert!(!config.embedded_content());
+    }
+
+    #[test]
+    fn test_extraction_config_builder_convert_tables() {
+        let config = ExtractionConfig::builder().convert_tables(false).build();
+        assert!(!config.convert_tables());
+    }
+
+    #[test]
+    fn test_extraction_config_builder_inject_title() {
+        let config = ExtractionConfig::builder().inject_title(true).build();
+        assert!(config.inject_title());
+    }
+
+    #[test]
+    fn test_extraction_config_builder_cleaning_report() {
+        let config = ExtractionConfig::builder().cleaning_report(true).build();
+        assert!(config.cleaning_report());
+    }
+
+    #[test]
+    fn test_extraction_config_builder_output_format() {
+        let config = ExtractionConfig::builder()
+            .output_format(OutputFormat::PlainText)
+            .build();
+        assert_eq!(config.output_format(), OutputFormat::PlainText);
+    }
+
+    #[test]
+    fn test_extraction_config_clone() {
+        let original = ExtractionConfig::builder()
+            .add_content_selector(".story")
+            .build();
+        let cloned = original.clone();
+        assert_eq!(original, cloned);
+    }
+}
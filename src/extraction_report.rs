@@ -0,0 +1,206 @@
+/// How the main content of a page was located.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExtractionStrategy {
+    /// A registered [`SiteExtractor`](crate::SiteExtractor) selected the content.
+    SiteExtractor,
+    /// One of the configured content selectors matched.
+    ContentSelector,
+    /// No content selector matched, so the whole `<body>` was used.
+    Body,
+    /// The markup was too thin, so the article was taken from the page's
+    /// embedded JSON state.
+    EmbeddedData,
+    /// The document was a PDF, converted from the layout of its text.
+    Pdf,
+    /// The document was Markdown, passed through with light normalization.
+    Markdown,
+    /// The document was plain text, wrapped as paragraphs.
+    PlainText,
+    /// The document was a source file, wrapped in a fenced code block.
+    SourceCode,
+    /// Nothing could be extracted, e.g. the document has an empty `<body>`.
+    #[default]
+    Empty,
+}
+
+/// Describes the decisions taken while extracting a page's content.
+///
+/// Useful to audit extraction quality: pages that fell back to
+/// [`ExtractionStrategy::Body`] often carry more boilerplate than the others.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtractionReport {
+    pub strategy: ExtractionStrategy,
+    /// Name of the site extractor used, if any.
+    pub site_extractor: Option<String>,
+    /// Content selector that matched, if any.
+    pub content_selector: Option<String>,
+    /// Embedded state the content was taken from, such as `__NEXT_DATA__`.
+    pub embedded_source: Option<String>,
+    /// Number of `<pre>` code blocks set aside for preservation.
+    pub code_blocks: usize,
+    /// Number of tables converted by the table converter.
+    pub tables: usize,
+    /// Number of images in the output, as placeholders or Markdown images.
+    pub images: usize,
+    /// Language of the boilerplate pack applied, if any.
+    pub boilerplate_pack: Option<String>,
+    /// What was removed and why, when enabled in the configuration.
+    pub cleaning: Option<CleaningReport>,
+}
+
+/// Everything removed while cleaning a page, in the order it was removed.
+///
+/// Only collected when enabled with
+/// [`ExtractionConfigBuilder::cleaning_report`](crate::ExtractionConfigBuilder::cleaning_report),
+/// and returned in [`ExtractionReport::cleaning`]. Useful to find out which rule
+/// removed content that should have been kept.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CleaningReport {
+    pub removals: Vec<Removal>,
+}
+
+/// An element or line removed during cleaning.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Removal {
+    /// The rule that removed it.
+    pub rule: CleaningRule,
+    /// The removed line, or the start of the removed element's text.
+    pub content: String,
+}
+
+/// A cleaning rule, as reported in a [`Removal`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CleaningRule {
+    /// The element matched this removal selector.
+    RemovalSelector(String),
+    /// This text pattern covered most of the line.
+    TextPattern(String),
+    /// The line consisted of this navigation term.
+    NavigationTerm(String),
+    /// The line matched this phrase of the boilerplate pack for `language`.
+    BoilerplatePhrase { language: String, phrase: String },
+    /// The line was a bare URL, email address or handle, or a layout label such
+    /// as "footer".
+    Metadata,
+    /// The line was too short to be content.
+    ShortLine,
+}
+
+/// Records a removal when a report is being collected; `content` is only
+/// evaluated in that case.
+pub(crate) fn record_removal(
+    report: &mut Option<CleaningReport>,
+    rule: impl FnOnce() -> CleaningRule,
+    content: impl FnOnce() -> String,
+) {
+    if let Some(report) = report {
+        report.removals.push(Removal {
+            rule: rule(),
+            content: content(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContentProcessor, ExtractionConfig};
+
+    #[test]
+    fn test_record_removal_only_when_collecting() {
+        let mut disabled = None;
+        record_removal(
+            &mut disabled,
+            || panic!("rule evaluated without a report"),
+            || panic!("content evaluated without a report"),
+        );
+        assert!(disabled.is_none());
+
+        let mut report = Some(CleaningReport::default());
+        record_removal(&mut report, || CleaningRule::ShortLine, || "»".to_string());
+        record_removal(
+            &mut report,
+            || CleaningRule::RemovalSelector("nav".to_string()),
+            || "Home About".to_string(),
+        );
+        assert_eq!(
+            report.unwrap().removals,
+            vec![
+                Removal {
+                    rule: CleaningRule::ShortLine,
+                    content: "»".to_string(),
+                },
+                Removal {
+                    rule: CleaningRule::RemovalSelector("nav".to_string()),
+                    content: "Home About".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report_built_by_extraction() {
+        assert_eq!(
+            ExtractionReport::default().strategy,
+            ExtractionStrategy::Empty
+        );
+
+        let html = r#"<html lang="en"><body>
+            <nav><a href="/">Home</a></nav>
+            <main>
+                <p>The migration guide explains every breaking change in this release.</p>
+                <pre><code class="language-rust">fn main() {}</code></pre>
+                <table><tr><th>Flag</th><th>Default</th></tr><tr><td>--fast</td><td>off</td></tr></table>
+            </main>
+        </body></html>"#;
+        let config = ExtractionConfig::builder().cleaning_report(true).build();
+        let report = ContentProcessor::with_config(config)
+            .extract(html, None)
+            .report;
+
+        assert_eq!(report.strategy, ExtractionStrategy::ContentSelector);
+        assert_eq!(report.content_selector.as_deref(), Some("main"));
+        assert_eq!(report.site_extractor, None);
+        assert_eq!(report.code_blocks, 1);
+        assert_eq!(report.tables, 1);
+        assert_eq!(report.images, 0);
+        assert_eq!(report.boilerplate_pack.as_deref(), Some("en"));
+        assert!(report.cleaning.is_some());
+
+        let empty = ContentProcessor::new()
+            .extract("<html><body></body></html>", None)
+            .report;
+        assert_eq!(empty.strategy, ExtractionStrategy::Empty);
+        assert_eq!(empty.cleaning, None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_report_serde_round_trip() {
+        let report = ExtractionReport {
+            strategy: ExtractionStrategy::SiteExtractor,
+            site_extractor: Some("wikipedia".to_string()),
+            cleaning: Some(CleaningReport {
+                removals: vec![Removal {
+                    rule: CleaningRule::BoilerplatePhrase {
+                        language: "de".to_string(),
+                        phrase: "newsletter abonnieren".to_string(),
+                    },
+                    content: "Newsletter abonnieren".to_string(),
+                }],
+            }),
+            ..ExtractionReport::default()
+        };
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ExtractionReport>(&json).unwrap(),
+            report
+        );
+    }
+}
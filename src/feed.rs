@@ -0,0 +1,698 @@
+use std::fmt;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::Url;
+use roxmltree::{Document, Node, ParsingOptions};
+use tokio_util::sync::CancellationToken;
+
+use crate::content_processor::ContentProcessor;
+use crate::harvest_config::HarvestConfig;
+use crate::harvest_error::HarvestError;
+use crate::harvested_document::HarvestedDocument;
+use crate::http_client::FetchedPage;
+use crate::renderer::harvest_page;
+
+const ATOM_NAMESPACE: &str = "http://www.w3.org/2005/Atom";
+const CONTENT_NAMESPACE: &str = "http://purl.org/rss/1.0/modules/content/";
+const DUBLIN_CORE_NAMESPACE: &str = "http://purl.org/dc/elements/1.1/";
+
+/// The root element of an RSS or Atom document, after any prolog.
+static FEED_ROOT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?s)\A\s*(?:<\?[^>]*\?>\s*|<!--.*?-->\s*|<!DOCTYPE[^>]*>\s*)*<(?:rss|feed|rdf:RDF)\b",
+    )
+    .unwrap()
+});
+
+/// RFC 822 dates as used by RSS, e.g. `Tue, 10 Jun 2003 04:00:00 GMT`.
+static RFC_822_DATE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(?:[A-Za-z]{3},\s*)?(\d{1,2})\s+([A-Za-z]{3})\s+(\d{2,4})\s+(\d{2}):(\d{2})(?::(\d{2}))?\s*([A-Za-z]+|[+-]\d{4})?$",
+    )
+    .unwrap()
+});
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// A parsed RSS 2.0, RSS 1.0 (RDF) or Atom feed.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::Feed;
+///
+/// let xml = r#"<rss version="2.0"><channel><title>Engineering Blog</title>
+///     <item><title>Hello</title><link>https://blog.example.com/hello</link>
+///     <pubDate>Tue, 10 Jun 2025 04:00:00 GMT</pubDate>
+///     <description>&lt;p&gt;Our first post.&lt;/p&gt;</description></item>
+/// </channel></rss>"#;
+///
+/// let feed = Feed::parse(xml).unwrap();
+/// assert_eq!(feed.title.as_deref(), Some("Engineering Blog"));
+/// assert_eq!(feed.entries[0].link.as_deref(), Some("https://blog.example.com/hello"));
+/// assert_eq!(feed.entries[0].published.as_deref(), Some("2025-06-10T04:00:00Z"));
+/// assert_eq!(feed.entries[0].summary.as_deref(), Some("<p>Our first post.</p>"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Feed {
+    pub title: Option<String>,
+    /// Link to the site the feed belongs to.
+    pub link: Option<String>,
+    /// Language declared by the feed, e.g. `en-us`.
+    pub language: Option<String>,
+    pub entries: Vec<FeedEntry>,
+}
+
+/// An item of an RSS feed or an entry of an Atom feed.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeedEntry {
+    pub title: Option<String>,
+    /// Link to the full article.
+    pub link: Option<String>,
+    pub author: Option<String>,
+    /// Publication date, as RFC 3339 when the feed's date could be read.
+    pub published: Option<String>,
+    /// Last update date, as RFC 3339 when the feed's date could be read.
+    pub updated: Option<String>,
+    /// Full content as HTML, from `content:encoded` or Atom `content`.
+    pub content: Option<String>,
+    /// Summary as HTML, from RSS `description` or Atom `summary`.
+    pub summary: Option<String>,
+}
+
+/// Error raised when a document cannot be read as a feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedError {
+    /// The document is not well-formed XML.
+    Xml(String),
+    /// The document is XML, but its root is not `rss`, `RDF` or `feed`.
+    NotAFeed(String),
+}
+
+impl fmt::Display for FeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeedError::Xml(message) => write!(f, "Invalid feed XML: {}", message),
+            FeedError::NotAFeed(root) => write!(f, "Not an RSS or Atom feed: <{}>", root),
+        }
+    }
+}
+
+impl std::error::Error for FeedError {}
+
+impl Feed {
+    /// Parses an RSS 2.0, RSS 1.0 (RDF) or Atom document.
+    pub fn parse(xml: &str) -> Result<Feed, FeedError> {
+        let options = ParsingOptions {
+            allow_dtd: true,
+            ..ParsingOptions::default()
+        };
+        let document = Document::parse_with_options(xml, options)
+            .map_err(|error| FeedError::Xml(error.to_string()))?;
+        let root = document.root_element();
+
+        match root.tag_name().name() {
+            "rss" | "RDF" => {
+                // RSS 1.0 lists its items next to the channel rather than in it
+                let channel = child(root, "channel").unwrap_or(root);
+                Ok(Feed {
+                    title: child_text(channel, "title"),
+                    link: child_text(channel, "link"),
+                    language: child_text(channel, "language"),
+                    entries: channel
+                        .children()
+                        .chain(root.children())
+                        .filter(|node| node.has_tag_name("item"))
+                        .map(rss_item)
+                        .collect(),
+                })
+            }
+            "feed" => Ok(Feed {
+                title: child_text(root, "title"),
+                link: atom_link(root),
+                language: root
+                    .attribute((roxmltree::NS_XML_URI, "lang"))
+                    .map(str::to_string),
+                entries: root
+                    .children()
+                    .filter(|node| node.has_tag_name((ATOM_NAMESPACE, "entry")))
+                    .map(|entry| atom_entry(xml, entry))
+                    .collect(),
+            }),
+            name => Err(FeedError::NotAFeed(name.to_string())),
+        }
+    }
+}
+
+fn rss_item(item: Node) -> FeedEntry {
+    let date = child_text(item, "pubDate").or_else(|| dublin_core(item, "date"));
+    FeedEntry {
+        title: child_text(item, "title"),
+        link: child_text(item, "link").or_else(|| {
+            child(item, "guid")
+                .filter(|guid| guid.attribute("isPermaLink") != Some("false"))
+                .and_then(text_of)
+        }),
+        author: dublin_core(item, "creator").or_else(|| child_text(item, "author")),
+        published: date.as_deref().map(normalize_date),
+        updated: None,
+        content: item
+            .children()
+            .find(|node| node.has_tag_name((CONTENT_NAMESPACE, "encoded")))
+            .and_then(text_of),
+        summary: child_text(item, "description"),
+    }
+}
+
+fn atom_entry(xml: &str, entry: Node) -> FeedEntry {
+    let atom_child = |name| {
+        entry
+            .children()
+            .find(|node| node.has_tag_name((ATOM_NAMESPACE, name)))
+    };
+    FeedEntry {
+        title: atom_child("title").and_then(atom_text),
+        link: atom_link(entry),
+        author: atom_child("author").and_then(|author| child_text(author, "name")),
+        published: atom_child("published")
+            .and_then(text_of)
+            .map(|date| normalize_date(&date)),
+        updated: atom_child("updated")
+            .and_then(text_of)
+            .map(|date| normalize_date(&date)),
+        content: atom_child("content")
+            .filter(|content| content.attribute("src").is_none())
+            .and_then(|content| atom_html(xml, content)),
+        summary: atom_child("summary").and_then(|summary| atom_html(xml, summary)),
+    }
+}
+
+/// The `alternate` link of an Atom feed or entry, or its first link without a `rel`.
+fn atom_link(node: Node) -> Option<String> {
+    node.children()
+        .filter(|link| link.has_tag_name((ATOM_NAMESPACE, "link")))
+        .find(|link| matches!(link.attribute("rel"), None | Some("alternate")))
+        .and_then(|link| link.attribute("href"))
+        .map(str::to_string)
+}
+
+/// The content of an Atom text construct as HTML.
+fn atom_html(xml: &str, node: Node) -> Option<String> {
+    match node.attribute("type") {
+        Some("html") => text_of(node),
+        Some("xhtml") => atom_xhtml(xml, node),
+        _ => text_of(node).map(|text| text_to_html(&text)),
+    }
+}
+
+/// The content of an Atom text construct as plain text.
+fn atom_text(node: Node) -> Option<String> {
+    match node.attribute("type") {
+        Some("xhtml") => Some(all_text(node)).filter(|text| !text.is_empty()),
+        Some("html") => text_of(node).map(|html| {
+            scraper::Html::parse_fragment(&html)
+                .root_element()
+                .text()
+                .collect::<String>()
+        }),
+        _ => text_of(node),
+    }
+}
+
+/// The markup inside the wrapping `<div>` of an `xhtml` text construct, as written.
+fn atom_xhtml(xml: &str, node: Node) -> Option<String> {
+    let div = node.children().find(Node::is_element)?;
+    let inner = div
+        .children()
+        .map(|child| &xml[child.range()])
+        .collect::<String>();
+    Some(inner.trim().to_string()).filter(|html| !html.is_empty())
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|child| child.is_element() && child.tag_name().name() == name)
+}
+
+fn child_text(node: Node, name: &str) -> Option<String> {
+    child(node, name).and_then(text_of)
+}
+
+fn dublin_core(node: Node, name: &str) -> Option<String> {
+    node.children()
+        .find(|child| child.has_tag_name((DUBLIN_CORE_NAMESPACE, name)))
+        .and_then(text_of)
+}
+
+/// The trimmed text of an element, including CDATA sections; `None` when blank.
+fn text_of(node: Node) -> Option<String> {
+    let text = all_text(node);
+    (!text.is_empty()).then_some(text)
+}
+
+fn all_text(node: Node) -> String {
+    node.descendants()
+        .filter(Node::is_text)
+        .filter_map(|text| text.text())
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Escapes plain text and wraps its paragraphs in `<p>` elements.
+fn text_to_html(text: &str) -> String {
+    text.split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| {
+            let escaped = paragraph
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            format!("<p>{}</p>", escaped)
+        })
+        .collect()
+}
+
+/// Converts an RFC 822 date to RFC 3339, leaving other dates, and RFC 822
+/// dates with an unknown month or zone or an out-of-range field, as they are.
+pub(crate) fn normalize_date(date: &str) -> String {
+    let date = date.trim();
+    let Some(caps) = RFC_822_DATE_REGEX.captures(date) else {
+        return date.to_string();
+    };
+    let Some(month) = MONTHS
+        .iter()
+        .position(|month| caps[2].eq_ignore_ascii_case(month))
+    else {
+        return date.to_string();
+    };
+    // Two-digit years below 50 are this century; other short years are 1900+
+    let year = match (caps[3].len(), caps[3].parse::<u32>()) {
+        (2, Ok(year)) if year < 50 => 2000 + year,
+        (2 | 3, Ok(year)) => 1900 + year,
+        (_, Ok(year)) => year,
+        (_, Err(_)) => return date.to_string(),
+    };
+    let field = |index: usize| {
+        caps.get(index)
+            .map_or(Ok(0), |field| field.as_str().parse())
+    };
+    let (Ok(day @ 1..=31), Ok(0..=23), Ok(0..=59), Ok(0..=60)) =
+        (field(1), field(4), field(5), field(6))
+    else {
+        return date.to_string();
+    };
+    let offset = match caps.get(7).map(|zone| zone.as_str().to_ascii_uppercase()) {
+        None => "Z".to_string(),
+        Some(zone) => match zone.as_str() {
+            "GMT" | "UT" | "UTC" | "Z" => "Z".to_string(),
+            "EDT" => "-04:00".to_string(),
+            "EST" | "CDT" => "-05:00".to_string(),
+            "CST" | "MDT" => "-06:00".to_string(),
+            "MST" | "PDT" => "-07:00".to_string(),
+            "PST" => "-08:00".to_string(),
+            zone if zone.starts_with(['+', '-']) => {
+                format!("{}:{}", &zone[..3], &zone[3..])
+            }
+            _ => return date.to_string(),
+        },
+    };
+    format!(
+        "{:04}-{:02}-{:02}T{}:{}:{}{}",
+        year,
+        month + 1,
+        day,
+        &caps[4],
+        &caps[5],
+        caps.get(6).map_or("00", |seconds| seconds.as_str()),
+        offset
+    )
+}
+
+/// Returns `true` when a response is an RSS or Atom feed: served as
+/// `application/rss+xml` or `application/atom+xml`, or as generic XML whose
+/// root element is `rss`, `rdf:RDF` or `feed`.
+pub(crate) fn is_feed(content_type: Option<&str>, body: &str) -> bool {
+    let media_type = content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .map(|media_type| media_type.trim().to_ascii_lowercase());
+    match media_type.as_deref() {
+        Some("application/rss+xml" | "application/atom+xml") => true,
+        None
+        | Some(
+            "application/xml" | "text/xml" | "application/rdf+xml" | "application/octet-stream",
+        ) => FEED_ROOT_REGEX.is_match(body),
+        _ => false,
+    }
+}
+
+/// Reads a fetched feed, reporting documents that are not feeds as body errors.
+pub(crate) fn parse_feed_page(page: &FetchedPage) -> Result<Feed, HarvestError> {
+    Feed::parse(&page.body).map_err(|error| HarvestError::Body {
+        url: page.requested_url.clone(),
+        message: error.to_string(),
+    })
+}
+
+/// The absolute link of each entry, resolved against the feed URL.
+pub(crate) fn entry_links(page: &FetchedPage, feed: &Feed) -> Vec<Option<String>> {
+    let base = Url::parse(&page.final_url).ok();
+    feed.entries
+        .iter()
+        .map(|entry| {
+            let link = entry.link.as_deref()?;
+            match &base {
+                Some(base) => base.join(link).ok().map(String::from),
+                None => Some(link.to_string()),
+            }
+        })
+        .collect()
+}
+
+/// Pairs the pages fetched for the linked entries back with every entry.
+pub(crate) fn align_articles(
+    links: &[Option<String>],
+    fetched: Vec<Result<FetchedPage, HarvestError>>,
+) -> Vec<Option<Result<FetchedPage, HarvestError>>> {
+    let mut fetched = fetched.into_iter();
+    links
+        .iter()
+        .map(|link| link.as_ref().and_then(|_| fetched.next()))
+        .collect()
+}
+
+/// Harvests one document per feed entry.
+///
+/// An entry with a fetched `article` is harvested from it like any other page;
+/// otherwise, or when the article could not be fetched or extracted, its
+/// content, or its summary, is extracted from the feed. Either
+/// way, the entry's title, author and dates take precedence over those found
+/// in the markup, and the front matter is added last so it reflects them.
+pub(crate) fn entry_documents(
+    page: &FetchedPage,
+    feed: &Feed,
+    articles: Vec<Option<Result<FetchedPage, HarvestError>>>,
+    processor: &ContentProcessor,
+    config: &HarvestConfig,
+    cancel: &CancellationToken,
+) -> Vec<Result<HarvestedDocument, HarvestError>> {
+    let links = entry_links(page, feed);
+    feed.entries
+        .iter()
+        .zip(links)
+        .zip(articles.into_iter().chain(std::iter::repeat_with(|| None)))
+        .map(|((entry, link), article)| {
+            let article = article.map(|article| {
+                article.and_then(|article| {
+                    harvest_page(
+                        article,
+                        processor,
+                        None,
+                        config.renderer(),
+                        config.render_policy(),
+                        cancel,
+                    )
+                })
+            });
+            let mut document = match article {
+                Some(Ok(document)) => document,
+                _ => entry_summary_document(page, feed, entry, link, processor)?,
+            };
+            document.requested_url = page.requested_url.clone();
+            document.feed_url = Some(page.final_url.clone());
+            let metadata = &mut document.metadata;
+            metadata.title = entry.title.clone().or(metadata.title.take());
+            metadata.author = entry.author.clone().or(metadata.author.take());
+            metadata.published_time = entry
+                .published
+                .clone()
+                .or(entry.updated.clone())
+                .or(metadata.published_time.take());
+            metadata.modified_time = entry.updated.clone().or(metadata.modified_time.take());
+            metadata.site_name = metadata.site_name.take().or(feed.title.clone());
+            document.lead_with_front_matter(config.front_matter(), processor);
+            Ok(document)
+        })
+        .collect()
+}
+
+/// Extracts an entry's content, or its summary, as the document served at its link.
+fn entry_summary_document(
+    page: &FetchedPage,
+    feed: &Feed,
+    entry: &FeedEntry,
+    link: Option<String>,
+    processor: &ContentProcessor,
+) -> Result<HarvestedDocument, HarvestError> {
+    let body = entry
+        .content
+        .as_ref()
+        .or(entry.summary.as_ref())
+        .map(|html| format!("<html><body><article>{}</article></body></html>", html))
+        .unwrap_or_default();
+    let entry_page = FetchedPage {
+        requested_url: page.requested_url.clone(),
+        final_url: link.unwrap_or_else(|| page.final_url.clone()),
+        content_type: Some("text/html".to_string()),
+        content_language: page.content_language.clone().or(feed.language.clone()),
+        body,
+        pdf: None,
+        ..page.clone()
+    };
+    let mut document = HarvestedDocument::from_page(entry_page, processor, None)?;
+    document.content_type = page.content_type.clone();
+    Ok(document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extraction_report::ExtractionStrategy;
+    use crate::front_matter::{FrontMatter, FrontMatterField, FrontMatterFormat};
+    use crate::http_client::test_page;
+
+    const RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/"
+     xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <channel>
+    <title>Engineering Blog</title>
+    <link>https://blog.example.com/</link>
+    <language>en-us</language>
+    <item>
+      <title>Shipping the new indexer</title>
+      <link>/posts/new-indexer</link>
+      <dc:creator>Ana Lima</dc:creator>
+      <pubDate>Mon, 02 Jun 2025 09:30:00 +0200</pubDate>
+      <description>A short teaser.</description>
+      <content:encoded><![CDATA[<h2>Why we rebuilt it</h2><p>The old indexer could not keep up with the <strong>volume</strong>.</p>]]></content:encoded>
+    </item>
+    <item>
+      <title>Release notes</title>
+      <guid isPermaLink="true">https://blog.example.com/posts/release-notes</guid>
+      <description>&lt;p&gt;Version 2 is out.&lt;/p&gt;</description>
+    </item>
+  </channel>
+</rss>"#;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="pt-BR">
+  <title>Notas</title>
+  <link rel="self" href="https://example.com/feed.atom"/>
+  <link href="https://example.com/"/>
+  <entry>
+    <title type="html">Primeira &lt;em&gt;nota&lt;/em&gt;</title>
+    <link rel="alternate" href="https://example.com/notas/1"/>
+    <author><name>Rui</name></author>
+    <published>2025-05-01T12:00:00Z</published>
+    <updated>2025-05-02T08:00:00Z</updated>
+    <summary>Resumo</summary>
+    <content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml"><p>Texto <b>completo</b>.</p></div></content>
+  </entry>
+</feed>"#;
+
+    fn feed_page(body: &str) -> FetchedPage {
+        FetchedPage {
+            requested_url: "https://blog.example.com/feed".to_string(),
+            ..test_page(
+                "https://blog.example.com/feed.xml",
+                "application/rss+xml",
+                body,
+            )
+        }
+    }
+
+    #[test]
+    fn test_feed_parse_rss_and_atom() {
+        let feed = Feed::parse(RSS).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Engineering Blog"));
+        assert_eq!(feed.language.as_deref(), Some("en-us"));
+        assert_eq!(feed.entries.len(), 2);
+        let entry = &feed.entries[0];
+        assert_eq!(entry.author.as_deref(), Some("Ana Lima"));
+        assert_eq!(
+            entry.published.as_deref(),
+            Some("2025-06-02T09:30:00+02:00")
+        );
+        assert!(
+            entry
+                .content
+                .as_deref()
+                .unwrap()
+                .starts_with("<h2>Why we rebuilt it</h2>")
+        );
+        assert_eq!(
+            feed.entries[1].link.as_deref(),
+            Some("https://blog.example.com/posts/release-notes")
+        );
+
+        let feed = Feed::parse(ATOM).unwrap();
+        assert_eq!(feed.link.as_deref(), Some("https://example.com/"));
+        assert_eq!(feed.language.as_deref(), Some("pt-BR"));
+        let entry = &feed.entries[0];
+        assert_eq!(entry.title.as_deref(), Some("Primeira nota"));
+        assert_eq!(entry.link.as_deref(), Some("https://example.com/notas/1"));
+        assert_eq!(entry.author.as_deref(), Some("Rui"));
+        assert_eq!(entry.updated.as_deref(), Some("2025-05-02T08:00:00Z"));
+        assert_eq!(
+            entry.content.as_deref(),
+            Some("<p>Texto <b>completo</b>.</p>")
+        );
+        assert_eq!(entry.summary.as_deref(), Some("<p>Resumo</p>"));
+
+        assert!(matches!(
+            Feed::parse("<urlset></urlset>"),
+            Err(FeedError::NotAFeed(_))
+        ));
+        assert!(matches!(Feed::parse("<rss>"), Err(FeedError::Xml(_))));
+        assert!(Feed::parse("").is_err());
+    }
+
+    #[test]
+    fn test_is_feed() {
+        assert!(is_feed(Some("application/atom+xml; charset=utf-8"), ""));
+        assert!(is_feed(Some("text/xml"), RSS));
+        assert!(is_feed(None, ATOM));
+        assert!(!is_feed(Some("application/xml"), "<urlset></urlset>"));
+        assert!(!is_feed(Some("text/html"), RSS));
+        assert!(!is_feed(None, ""));
+        assert!(!is_feed(Some("application/xml"), "  "));
+    }
+
+    #[test]
+    fn test_normalize_date() {
+        for (date, normalized) in [
+            ("Tue, 10 Jun 2003 04:00:00 GMT", "2003-06-10T04:00:00Z"),
+            ("Mon, 2 Jun 2025 09:30 +0200", "2025-06-02T09:30:00+02:00"),
+            ("2 Jun 2025 09:30 -0330", "2025-06-02T09:30:00-03:30"),
+            ("Sun, 01 Dec 2024 23:59:60 EST", "2024-12-01T23:59:60-05:00"),
+            ("sat, 07 sep 24 18:00:00 pdt", "2024-09-07T18:00:00-07:00"),
+            ("07 Sep 99 18:00:00 UT", "1999-09-07T18:00:00Z"),
+            ("07 Sep 103 18:00:00", "2003-09-07T18:00:00Z"),
+        ] {
+            assert_eq!(normalize_date(date), normalized, "{date}");
+        }
+
+        // Not RFC 822, or with an unknown month or zone, or out of range
+        for date in [
+            "2025-06-02T09:30:00+02:00",
+            "June 2, 2025",
+            "",
+            "Tue, 10 Foo 2003 04:00:00 GMT",
+            "Tue, 10 Jun 2003 04:00:00 CET",
+            "Tue, 32 Jun 2003 04:00:00 GMT",
+            "Tue, 10 Jun 2003 24:00:00 GMT",
+            "Tue, 10 Jun 2003 04:61:00 GMT",
+        ] {
+            assert_eq!(normalize_date(date), date);
+        }
+    }
+
+    #[test]
+    fn test_entry_documents_from_feed_content() {
+        let page = feed_page(RSS);
+        let feed = parse_feed_page(&page).unwrap();
+        let config = HarvestConfig::builder()
+            .front_matter(FrontMatter::new(FrontMatterFormat::Yaml).field(FrontMatterField::Title))
+            .build();
+        let documents = entry_documents(
+            &page,
+            &feed,
+            Vec::new(),
+            &config.content_processor(),
+            &config,
+            &CancellationToken::new(),
+        );
+        assert_eq!(documents.len(), 2);
+
+        let first = documents[0].as_ref().unwrap();
+        assert_eq!(first.requested_url, "https://blog.example.com/feed");
+        assert_eq!(
+            first.final_url,
+            "https://blog.example.com/posts/new-indexer"
+        );
+        assert_eq!(
+            first.feed_url.as_deref(),
+            Some("https://blog.example.com/feed.xml")
+        );
+        assert_eq!(
+            first.markdown,
+            "Why we rebuilt it\n----------\n\nThe old indexer could not keep up with the **volume**."
+        );
+        assert_eq!(
+            first.metadata.title.as_deref(),
+            Some("Shipping the new indexer")
+        );
+        assert_eq!(
+            first.metadata.site_name.as_deref(),
+            Some("Engineering Blog")
+        );
+        assert_eq!(first.language.as_ref().unwrap().tag, "en-us");
+        assert_eq!(first.content_type.as_deref(), Some("application/rss+xml"));
+        assert!(
+            first
+                .content
+                .starts_with("---\ntitle: \"Shipping the new indexer\"\n---\n\n")
+        );
+
+        let second = documents[1].as_ref().unwrap();
+        assert_eq!(second.markdown, "Version 2 is out.");
+        assert_eq!(second.report.strategy, ExtractionStrategy::ContentSelector);
+
+        // Fetched articles replace the feed content; failed ones fall back to it
+        let article = FetchedPage {
+            final_url: "https://blog.example.com/posts/new-indexer".to_string(),
+            content_type: Some("text/html".to_string()),
+            body: "<html><body><article><p>The full article, with every detail.</p>\
+                   </article></body></html>"
+                .to_string(),
+            ..feed_page("")
+        };
+        let error = HarvestError::Request {
+            url: "https://blog.example.com/posts/release-notes".to_string(),
+            message: "timed out".to_string(),
+        };
+        let documents = entry_documents(
+            &page,
+            &feed,
+            vec![Some(Ok(article)), Some(Err(error.clone()))],
+            &config.content_processor(),
+            &config,
+            &CancellationToken::new(),
+        );
+        let first = documents[0].as_ref().unwrap();
+        assert_eq!(first.markdown, "The full article, with every detail.");
+        assert_eq!(first.metadata.author.as_deref(), Some("Ana Lima"));
+        let second = documents[1].as_ref().unwrap();
+        assert_eq!(second.markdown, "Version 2 is out.");
+        assert_eq!(
+            second.final_url,
+            "https://blog.example.com/posts/release-notes"
+        );
+    }
+}
@@ -0,0 +1,311 @@
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Syntax of a front-matter block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FrontMatterFormat {
+    /// YAML between `---` lines, as read by Jekyll, Hugo, Obsidian and most
+    /// static site generators.
+    #[default]
+    Yaml,
+    /// TOML between `+++` lines, as read by Hugo and Zola.
+    Toml,
+}
+
+/// A field of a front-matter block. Fields without a value for a document,
+/// such as the author of a page that names none, are left out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontMatterField {
+    /// The URL as found in the input text.
+    SourceUrl,
+    /// The URL the content was served from, after redirects.
+    FinalUrl,
+    Title,
+    Author,
+    /// The publication date declared by the page, as written there.
+    PublishedTime,
+    /// When the page was fetched, in RFC 3339 format (UTC).
+    FetchedAt,
+    /// The detected language tag.
+    Language,
+    /// SHA-256 of the Markdown content, as `sha256:<hex>`.
+    ContentHash,
+}
+
+impl FrontMatterField {
+    /// Every field, in the order they are written.
+    pub const ALL: [FrontMatterField; 8] = [
+        FrontMatterField::SourceUrl,
+        FrontMatterField::FinalUrl,
+        FrontMatterField::Title,
+        FrontMatterField::Author,
+        FrontMatterField::PublishedTime,
+        FrontMatterField::FetchedAt,
+        FrontMatterField::Language,
+        FrontMatterField::ContentHash,
+    ];
+
+    /// The key the field is written under.
+    pub fn key(self) -> &'static str {
+        match self {
+            FrontMatterField::SourceUrl => "source_url",
+            FrontMatterField::FinalUrl => "final_url",
+            FrontMatterField::Title => "title",
+            FrontMatterField::Author => "author",
+            FrontMatterField::PublishedTime => "published",
+            FrontMatterField::FetchedAt => "fetched_at",
+            FrontMatterField::Language => "language",
+            FrontMatterField::ContentHash => "content_hash",
+        }
+    }
+}
+
+/// Describes the front-matter block written ahead of harvested Markdown.
+///
+/// [`FrontMatter::yaml`] and [`FrontMatter::toml`] include every
+/// [`FrontMatterField`]; [`FrontMatter::new`] starts without fields. Set it with
+/// [`HarvestConfigBuilder::front_matter`](crate::HarvestConfigBuilder::front_matter)
+/// to have it lead [`HarvestedDocument::content`](crate::HarvestedDocument::content),
+/// or render it for any document with
+/// [`HarvestedDocument::markdown_with_front_matter`](crate::HarvestedDocument::markdown_with_front_matter).
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::{FrontMatter, FrontMatterField, FrontMatterFormat};
+///
+/// let front_matter = FrontMatter::yaml().without_field(FrontMatterField::FetchedAt);
+/// assert_eq!(front_matter.format(), FrontMatterFormat::Yaml);
+/// assert!(!front_matter.fields().contains(&FrontMatterField::FetchedAt));
+///
+/// let front_matter = FrontMatter::new(FrontMatterFormat::Toml)
+///     .field(FrontMatterField::FinalUrl)
+///     .field(FrontMatterField::Title);
+/// assert_eq!(front_matter.fields(), [FrontMatterField::FinalUrl, FrontMatterField::Title]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontMatter {
+    format: FrontMatterFormat,
+    fields: Vec<FrontMatterField>,
+}
+
+impl FrontMatter {
+    /// Creates a front matter in `format` without any field.
+    pub fn new(format: FrontMatterFormat) -> Self {
+        Self {
+            format,
+            fields: Vec::new(),
+        }
+    }
+
+    /// A YAML front matter with every field.
+    pub fn yaml() -> Self {
+        Self::new(FrontMatterFormat::Yaml).with_all_fields()
+    }
+
+    /// A TOML front matter with every field.
+    pub fn toml() -> Self {
+        Self::new(FrontMatterFormat::Toml).with_all_fields()
+    }
+
+    /// Adds a field, after those already present.
+    pub fn field(mut self, field: FrontMatterField) -> Self {
+        if !self.fields.contains(&field) {
+            self.fields.push(field);
+        }
+        self
+    }
+
+    pub fn without_field(mut self, field: FrontMatterField) -> Self {
+        self.fields.retain(|f| *f != field);
+        self
+    }
+
+    pub fn format(&self) -> FrontMatterFormat {
+        self.format
+    }
+
+    pub fn fields(&self) -> &[FrontMatterField] {
+        &self.fields
+    }
+
+    fn with_all_fields(self) -> Self {
+        FrontMatterField::ALL
+            .into_iter()
+            .fold(self, |front_matter, field| front_matter.field(field))
+    }
+
+    /// Writes the block for the configured fields, taking their values from
+    /// `value`. Ends with a blank line, ready to be followed by the content.
+    pub(crate) fn render(&self, value: impl Fn(FrontMatterField) -> Option<String>) -> String {
+        let (fence, separator) = match self.format {
+            FrontMatterFormat::Yaml => ("---", ": "),
+            FrontMatterFormat::Toml => ("+++", " = "),
+        };
+
+        let mut block = format!("{}\n", fence);
+        for field in &self.fields {
+            if let Some(value) = value(*field) {
+                block.push_str(field.key());
+                block.push_str(separator);
+                block.push_str(&quote(&value));
+                block.push('\n');
+            }
+        }
+        block.push_str(fence);
+        block.push_str("\n\n");
+        block
+    }
+}
+
+/// Quotes a value as a double-quoted string. JSON string escapes are valid in
+/// both YAML double-quoted scalars and TOML basic strings; DEL and the C1
+/// control characters, which JSON leaves as they are but neither format
+/// accepts raw, are escaped too.
+fn quote(value: &str) -> String {
+    let quoted = serde_json::to_string(value).unwrap_or_default();
+    let mut escaped = String::with_capacity(quoted.len());
+    for c in quoted.chars() {
+        if matches!(c, '\u{7f}'..='\u{9f}') {
+            escaped.push_str(&format!("\\u{:04X}", c as u32));
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// Returns the SHA-256 digest of `content` as `sha256:<hex>`.
+pub(crate) fn content_hash(content: &str) -> String {
+    let digest = Sha256::digest(content.as_bytes());
+    let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("sha256:{}", hex)
+}
+
+/// Formats `time` as an RFC 3339 UTC timestamp with second precision, such as
+/// `2026-01-04T09:30:00Z`.
+pub(crate) fn format_timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let (days, seconds_of_day) = (seconds / 86_400, seconds % 86_400);
+
+    // Civil date from days since the epoch (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_front_matter_render_yaml_and_toml() {
+        let value = |field: FrontMatterField| match field {
+            FrontMatterField::Title => Some("Say \"hi\"\nagain".to_string()),
+            FrontMatterField::Language => Some("en".to_string()),
+            _ => None,
+        };
+
+        assert_eq!(
+            FrontMatter::yaml().render(value),
+            "---\ntitle: \"Say \\\"hi\\\"\\nagain\"\nlanguage: \"en\"\n---\n\n"
+        );
+        assert_eq!(
+            FrontMatter::toml().render(value),
+            "+++\ntitle = \"Say \\\"hi\\\"\\nagain\"\nlanguage = \"en\"\n+++\n\n"
+        );
+        assert_eq!(
+            FrontMatter::new(FrontMatterFormat::Yaml)
+                .field(FrontMatterField::Language)
+                .render(value),
+            "---\nlanguage: \"en\"\n---\n\n"
+        );
+    }
+
+    #[test]
+    fn test_front_matter_escapes_values() {
+        let title = "Rust: a #1 guide\t- \"C:\\tools\"\r\n{draft} 'v2' \u{7f}\u{85}é";
+        let value = |field: FrontMatterField| match field {
+            FrontMatterField::Title => Some(title.to_string()),
+            _ => None,
+        };
+        let escaped = r#""Rust: a #1 guide\t- \"C:\\tools\"\r\n{draft} 'v2' \u007F\u0085é""#;
+
+        assert_eq!(
+            FrontMatter::yaml().render(value),
+            format!("---\ntitle: {}\n---\n\n", escaped)
+        );
+        assert_eq!(
+            FrontMatter::toml().render(value),
+            format!("+++\ntitle = {}\n+++\n\n", escaped)
+        );
+        assert_eq!(quote(""), "\"\"");
+        assert_eq!(quote("\u{1}"), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn test_front_matter_fields_keep_their_order() {
+        let value = |field: FrontMatterField| Some(field.key().to_uppercase());
+
+        let rendered = FrontMatter::toml()
+            .without_field(FrontMatterField::Author)
+            .without_field(FrontMatterField::ContentHash)
+            .render(value);
+        assert_eq!(
+            rendered,
+            "+++\nsource_url = \"SOURCE_URL\"\nfinal_url = \"FINAL_URL\"\ntitle = \"TITLE\"\n\
+             published = \"PUBLISHED\"\nfetched_at = \"FETCHED_AT\"\nlanguage = \"LANGUAGE\"\n+++\n\n"
+        );
+
+        let custom = FrontMatter::new(FrontMatterFormat::Yaml)
+            .field(FrontMatterField::Language)
+            .field(FrontMatterField::Title)
+            .field(FrontMatterField::Language);
+        assert_eq!(
+            custom.fields(),
+            [FrontMatterField::Language, FrontMatterField::Title]
+        );
+        assert_eq!(
+            FrontMatter::new(FrontMatterFormat::Toml).render(value),
+            "+++\n+++\n\n"
+        );
+    }
+
+    #[test]
+    fn test_content_hash_and_timestamp() {
+        assert_eq!(
+            content_hash("abc"),
+            "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(format_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            format_timestamp(UNIX_EPOCH + Duration::from_secs(1_709_210_096)),
+            "2024-02-29T12:34:56Z"
+        );
+    }
+}
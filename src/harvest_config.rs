@@ -0,0 +1,278 @@
+use crate::content_processor::ContentProcessor;
+use crate::extraction_config::ExtractionConfig;
+use crate::front_matter::FrontMatter;
+use crate::http_config::HttpConfig;
+use crate::renderer::{NoopRenderer, RenderPolicy, Renderer};
+use crate::site_extractor::SiteExtractorRegistry;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+/// Settings for the document-returning harvest entry points, such as
+/// [`MarkdownHarvester::get_hyperlinks_documents`](crate::MarkdownHarvester::get_hyperlinks_documents).
+///
+/// Bundles the HTTP settings with the extraction rules and site extractors that
+/// would otherwise be configured on a [`ContentProcessor`].
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::{ExtractionConfig, HarvestConfig, HttpConfig, LinkMode};
+///
+/// let config = HarvestConfig::builder()
+///     .http_config(HttpConfig::builder().timeout(10000).build())
+///     .extraction_config(ExtractionConfig::builder().link_mode(LinkMode::Inline).build())
+///     .build();
+///
+/// assert_eq!(config.http_config().timeout(), Some(10000));
+///
+/// // An HttpConfig converts into a HarvestConfig with the default extraction rules
+/// let config: HarvestConfig = HttpConfig::default().into();
+/// assert_eq!(config.extraction_config(), &ExtractionConfig::default());
+/// ```
+#[derive(Default, Clone)]
+pub struct HarvestConfig {
+    http_config: HttpConfig,
+    extraction_config: ExtractionConfig,
+    site_extractors: SiteExtractorRegistry,
+    front_matter: Option<FrontMatter>,
+    renderer: Option<Arc<dyn Renderer>>,
+    render_policy: RenderPolicy,
+    fetch_feed_articles: bool,
+    pagination_pages: usize,
+    deadline: Option<u64>,
+    cancellation_token: Option<CancellationToken>,
+}
+
+#[derive(Default, Clone)]
+pub struct HarvestConfigBuilder {
+    http_config: HttpConfig,
+    extraction_config: ExtractionConfig,
+    site_extractors: SiteExtractorRegistry,
+    front_matter: Option<FrontMatter>,
+    renderer: Option<Arc<dyn Renderer>>,
+    render_policy: RenderPolicy,
+    fetch_feed_articles: bool,
+    pagination_pages: usize,
+    deadline: Option<u64>,
+    cancellation_token: Option<CancellationToken>,
+}
+
+impl HarvestConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn http_config(mut self, http_config: HttpConfig) -> Self {
+        self.http_config = http_config;
+        self
+    }
+
+    pub fn extraction_config(mut self, extraction_config: ExtractionConfig) -> Self {
+        self.extraction_config = extraction_config;
+        self
+    }
+
+    /// Replaces the site extractors, which default to the built-in ones.
+    pub fn site_extractors(mut self, site_extractors: SiteExtractorRegistry) -> Self {
+        self.site_extractors = site_extractors;
+        self
+    }
+
+    /// Leads the [`content`](crate::HarvestedDocument::content) of every
+    /// document with a front-matter block, when the output format is Markdown.
+    pub fn front_matter(mut self, front_matter: FrontMatter) -> Self {
+        self.front_matter = Some(front_matter);
+        self
+    }
+
+    /// Sets the renderer used for pages selected by the render policy. The
+    /// default, [`NoopRenderer`], leaves every page as fetched.
+    pub fn renderer(mut self, renderer: impl Renderer + 'static) -> Self {
+        self.renderer = Some(Arc::new(renderer));
+        self
+    }
+
+    /// Sets which pages are rendered; defaults to pages whose static extraction
+    /// yields less than [`DEFAULT_THIN_CONTENT_CHARS`](crate::DEFAULT_THIN_CONTENT_CHARS)
+    /// characters of text.
+    pub fn render_policy(mut self, render_policy: RenderPolicy) -> Self {
+        self.render_policy = render_policy;
+        self
+    }
+
+    /// Harvests each entry of an RSS or Atom feed from its full article
+    /// rather than from the content or summary in the feed, which is still
+    /// used for entries whose article cannot be fetched or extracted.
+    /// Disabled by default.
+    pub fn fetch_feed_articles(mut self, fetch_feed_articles: bool) -> Self {
+        self.fetch_feed_articles = fetch_feed_articles;
+        self
+    }
+
+    /// Follows the pagination links of multi-page articles, fetching up to
+    /// this many following pages and merging them into one document.
+    /// Disabled (0) by default.
+    pub fn pagination_pages(mut self, pagination_pages: usize) -> Self {
+        self.pagination_pages = pagination_pages;
+        self
+    }
+
+    /// Bounds a whole asynchronous batch, in milliseconds from the call. When
+    /// it passes, the pages still in flight are cancelled and the results
+    /// gathered so far are returned: requests are dropped, the
+    /// [`Renderer`] is told to stop and no further page of a paginated article
+    /// is fetched. Unlike [`HttpConfig::timeout`], which applies to each
+    /// request, this bounds the batch as a whole.
+    pub fn deadline(mut self, ms: u64) -> Self {
+        self.deadline = Some(ms);
+        self
+    }
+
+    /// Stops asynchronous batches when `cancellation_token` is cancelled, as
+    /// when the [`deadline`](Self::deadline) passes.
+    pub fn cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = Some(cancellation_token);
+        self
+    }
+
+    pub fn build(self) -> HarvestConfig {
+        HarvestConfig {
+            http_config: self.http_config,
+            extraction_config: self.extraction_config,
+            site_extractors: self.site_extractors,
+            front_matter: self.front_matter,
+            renderer: self.renderer,
+            render_policy: self.render_policy,
+            fetch_feed_articles: self.fetch_feed_articles,
+            pagination_pages: self.pagination_pages,
+            deadline: self.deadline,
+            cancellation_token: self.cancellation_token,
+        }
+    }
+}
+
+impl HarvestConfig {
+    pub fn builder() -> HarvestConfigBuilder {
+        HarvestConfigBuilder::new()
+    }
+
+    pub fn http_config(&self) -> HttpConfig {
+        self.http_config
+    }
+
+    pub fn extraction_config(&self) -> &ExtractionConfig {
+        &self.extraction_config
+    }
+
+    pub fn site_extractors(&self) -> &SiteExtractorRegistry {
+        &self.site_extractors
+    }
+
+    pub fn front_matter(&self) -> Option<&FrontMatter> {
+        self.front_matter.as_ref()
+    }
+
+    pub fn renderer(&self) -> &dyn Renderer {
+        self.renderer.as_deref().unwrap_or(&NoopRenderer)
+    }
+
+    pub fn render_policy(&self) -> RenderPolicy {
+        self.render_policy
+    }
+
+    pub fn fetch_feed_articles(&self) -> bool {
+        self.fetch_feed_articles
+    }
+
+    pub fn pagination_pages(&self) -> usize {
+        self.pagination_pages
+    }
+
+    pub fn deadline(&self) -> Option<u64> {
+        self.deadline
+    }
+
+    pub fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation_token.as_ref()
+    }
+
+    /// Builds the content processor applying these extraction settings.
+    pub(crate) fn content_processor(&self) -> ContentProcessor {
+        let mut processor = ContentProcessor::with_config(self.extraction_config.clone());
+        processor.set_site_extractors(self.site_extractors.clone());
+        processor
+    }
+}
+
+impl From<HttpConfig> for HarvestConfig {
+    fn from(http_config: HttpConfig) -> Self {
+        HarvestConfig::builder().http_config(http_config).build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LinkMode;
+
+    #[test]
+    fn test_harvest_config_default() {
+        let config = HarvestConfig::default();
+        assert_eq!(config.http_config().timeout(), None);
+        assert_eq!(config.extraction_config(), &ExtractionConfig::default());
+        assert_eq!(
+            config.site_extractors().len(),
+            SiteExtractorRegistry::builtin().len()
+        );
+        assert!(config.front_matter().is_none());
+        assert_eq!(config.renderer().name(), "noop");
+        assert_eq!(
+            config.render_policy(),
+            RenderPolicy::IfThin(crate::DEFAULT_THIN_CONTENT_CHARS)
+        );
+        assert!(!config.fetch_feed_articles());
+        assert_eq!(config.pagination_pages(), 0);
+        assert_eq!(config.deadline(), None);
+        assert!(config.cancellation_token().is_none());
+    }
+
+    #[test]
+    fn test_harvest_config_builder() {
+        let config = HarvestConfig::builder()
+            .http_config(HttpConfig::builder().timeout(5000).build())
+            .extraction_config(
+                ExtractionConfig::builder()
+                    .link_mode(LinkMode::Reference)
+                    .build(),
+            )
+            .site_extractors(SiteExtractorRegistry::new())
+            .front_matter(FrontMatter::toml())
+            .render_policy(RenderPolicy::Never)
+            .fetch_feed_articles(true)
+            .pagination_pages(4)
+            .deadline(4000)
+            .cancellation_token(CancellationToken::new())
+            .build();
+
+        assert_eq!(config.http_config().timeout(), Some(5000));
+        assert_eq!(config.extraction_config().link_mode(), LinkMode::Reference);
+        assert!(config.site_extractors().is_empty());
+        assert_eq!(config.front_matter(), Some(&FrontMatter::toml()));
+        assert_eq!(config.render_policy(), RenderPolicy::Never);
+        assert!(config.fetch_feed_articles());
+        assert_eq!(config.pagination_pages(), 4);
+        assert_eq!(config.deadline(), Some(4000));
+        assert!(!config.cancellation_token().unwrap().is_cancelled());
+
+        let processor = config.content_processor();
+        assert_eq!(processor.config().link_mode(), LinkMode::Reference);
+        assert!(processor.site_extractors().is_empty());
+    }
+
+    #[test]
+    fn test_harvest_config_from_http_config() {
+        let config = HarvestConfig::from(HttpConfig::builder().max_redirect(3).build());
+        assert_eq!(config.http_config().max_redirect(), Some(3));
+        assert_eq!(config.extraction_config(), &ExtractionConfig::default());
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt;
+
+/// Error raised while harvesting a single URL.
+///
+/// Failures are reported per URL, so one unreachable page never prevents the
+/// others from being harvested. Invalid settings, which fail a whole harvest,
+/// are reported once as [`HarvestError::InvalidConfig`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HarvestError {
+    /// The request could not be sent or no response was received.
+    Request { url: String, message: String },
+    /// A response was received but its body could not be read.
+    Body { url: String, message: String },
+    /// The URL was still being harvested when the batch deadline passed or
+    /// the batch was cancelled.
+    Cancelled { url: String, message: String },
+    /// The harvest was not started because its settings are invalid.
+    InvalidConfig { message: String },
+}
+
+impl HarvestError {
+    /// Returns the URL the error relates to, or an empty string for an
+    /// [`InvalidConfig`](HarvestError::InvalidConfig) error.
+    pub fn url(&self) -> &str {
+        match self {
+            HarvestError::Request { url, .. }
+            | HarvestError::Body { url, .. }
+            | HarvestError::Cancelled { url, .. } => url,
+            HarvestError::InvalidConfig { .. } => "",
+        }
+    }
+}
+
+impl fmt::Display for HarvestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HarvestError::Request { url, message } => {
+                write!(f, "Error accessing {}: {}", url, message)
+            }
+            HarvestError::Body { url, message } => {
+                write!(f, "Error reading content from {}: {}", url, message)
+            }
+            HarvestError::Cancelled { url, message } => {
+                write!(f, "Harvest of {} stopped: {}", url, message)
+            }
+            HarvestError::InvalidConfig { message } => {
+                write!(f, "Invalid configuration: {}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HarvestError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_harvest_error_display_and_url() {
+        let error = HarvestError::Request {
+            url: "https://example.com".to_string(),
+            message: "connection refused".to_string(),
+        };
+        assert_eq!(error.url(), "https://example.com");
+        assert_eq!(
+            error.to_string(),
+            "Error accessing https://example.com: connection refused"
+        );
+
+        let error = HarvestError::Body {
+            url: "https://example.com".to_string(),
+            message: "invalid utf-8".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Error reading content from https://example.com: invalid utf-8"
+        );
+
+        let error = HarvestError::Cancelled {
+            url: "https://example.com".to_string(),
+            message: "batch deadline exceeded".to_string(),
+        };
+        assert_eq!(error.url(), "https://example.com");
+        assert_eq!(
+            error.to_string(),
+            "Harvest of https://example.com stopped: batch deadline exceeded"
+        );
+
+        let error = HarvestError::InvalidConfig {
+            message: "chunk_overlap (500) must be smaller than chunk_size (500)".to_string(),
+        };
+        assert_eq!(error.url(), "");
+        assert_eq!(
+            error.to_string(),
+            "Invalid configuration: chunk_overlap (500) must be smaller than chunk_size (500)"
+        );
+    }
+}
@@ -0,0 +1,466 @@
+use std::time::{Instant, SystemTime};
+
+use crate::content_processor::{ContentProcessor, ExtractedContent, PageSpan};
+use crate::extraction_report::ExtractionReport;
+use crate::front_matter::{FrontMatter, FrontMatterField, content_hash, format_timestamp};
+use crate::harvest_error::HarvestError;
+use crate::http_client::FetchedPage;
+use crate::language::DetectedLanguage;
+use crate::page_metadata::PageMetadata;
+use crate::pagination::{without_repeated_lead, without_trailing_controls};
+use crate::plain_text::markdown_to_plain_text;
+
+/// A harvested web page: the extracted content together with everything known
+/// about how it was obtained.
+///
+/// Returned by the `*_documents` entry points of
+/// [`MarkdownHarvester`](crate::MarkdownHarvester). With the `serde` feature
+/// enabled it implements `Serialize` and `Deserialize`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use markdown_harvest::{HarvestConfig, MarkdownHarvester};
+///
+/// let text = "Read https://example.com/article";
+/// for result in MarkdownHarvester::get_hyperlinks_documents(text.to_string(), HarvestConfig::default()) {
+///     match result {
+///         Ok(document) => {
+///             println!("{} -> {} ({})", document.requested_url, document.final_url, document.status);
+///             println!("Published: {:?}", document.metadata.published_time);
+///             println!("{}", document.markdown);
+///         }
+///         Err(error) => eprintln!("{}", error),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HarvestedDocument {
+    /// The URL as found in the input text.
+    pub requested_url: String,
+    /// The URL the content was served from, after redirects.
+    pub final_url: String,
+    /// HTTP status code of the response.
+    pub status: u16,
+    /// Value of the `Content-Type` response header.
+    pub content_type: Option<String>,
+    /// The cleaned Markdown content.
+    pub markdown: String,
+    /// The content as plain text, without Markdown syntax.
+    pub text: String,
+    /// The content in the configured [`OutputFormat`](crate::OutputFormat),
+    /// led by the configured front matter when that format is Markdown.
+    pub content: String,
+    pub metadata: PageMetadata,
+    /// Language of the page, from its markup, the `Content-Language` header and
+    /// the extracted text.
+    pub language: Option<DetectedLanguage>,
+    /// When the page was requested.
+    pub fetched_at: Option<SystemTime>,
+    /// Name of the [`Renderer`](crate::Renderer) whose HTML the content was
+    /// extracted from, when the page was rendered.
+    pub rendered_by: Option<String>,
+    /// Why rendering failed, in which case the content comes from the fetched HTML.
+    pub render_error: Option<String>,
+    /// URL of the RSS or Atom feed that listed this document, for feed entries.
+    pub feed_url: Option<String>,
+    pub timings: DocumentTimings,
+    pub sizes: DocumentSizes,
+    /// How the main content was located.
+    pub report: ExtractionReport,
+    /// Byte ranges of [`markdown`](Self::markdown) taken from each page of a
+    /// PDF document; empty for HTML pages.
+    pub pages: Vec<PageSpan>,
+    /// Absolute URLs of the links in the extracted content, which the
+    /// [`Crawler`](crate::Crawler) follows.
+    pub links: Vec<String>,
+    /// URLs of the following pages of a paginated article merged into this
+    /// document, in order, when [`HarvestConfig::pagination_pages`](crate::HarvestConfig::pagination_pages) is set.
+    pub continuation_urls: Vec<String>,
+}
+
+/// Time spent on each stage of harvesting a document, in milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DocumentTimings {
+    /// From sending the request until the whole body was read.
+    pub fetch_ms: u64,
+    /// Content extraction and Markdown conversion.
+    pub extraction_ms: u64,
+    /// Rendering by the configured [`Renderer`](crate::Renderer), when used.
+    pub render_ms: u64,
+}
+
+/// Sizes of the document at each stage, in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DocumentSizes {
+    /// The decoded response body, or the raw bytes of a PDF document.
+    pub html_bytes: usize,
+    pub markdown_bytes: usize,
+    pub text_bytes: usize,
+}
+
+impl HarvestedDocument {
+    /// Runs the content pipeline on a fetched page.
+    ///
+    /// A `front_matter` leads [`content`](Self::content) when the output format
+    /// is Markdown.
+    pub(crate) fn from_page(
+        page: FetchedPage,
+        processor: &ContentProcessor,
+        front_matter: Option<&FrontMatter>,
+    ) -> Result<Self, HarvestError> {
+        let started = Instant::now();
+        let extracted = extract_page(&page, processor)?;
+        let text = markdown_to_plain_text(&extracted.markdown);
+        let extraction_ms = started.elapsed().as_millis() as u64;
+
+        let mut document = HarvestedDocument {
+            sizes: DocumentSizes {
+                html_bytes: page.pdf.as_ref().map_or(page.body.len(), Vec::len),
+                markdown_bytes: extracted.markdown.len(),
+                text_bytes: text.len(),
+            },
+            timings: DocumentTimings {
+                fetch_ms: page.fetch_duration.as_millis() as u64,
+                extraction_ms,
+                render_ms: 0,
+            },
+            requested_url: page.requested_url,
+            final_url: page.final_url,
+            status: page.status,
+            content_type: page.content_type,
+            markdown: extracted.markdown,
+            text,
+            content: extracted.content,
+            metadata: extracted.metadata,
+            language: extracted.language,
+            fetched_at: Some(page.fetched_at),
+            rendered_by: None,
+            render_error: None,
+            feed_url: None,
+            report: extracted.report,
+            pages: extracted.pages,
+            links: extracted.links,
+            continuation_urls: Vec::new(),
+        };
+        document.lead_with_front_matter(front_matter, processor);
+        Ok(document)
+    }
+
+    /// Leads [`content`](Self::content) with a `front_matter` block when the
+    /// output format is Markdown.
+    pub(crate) fn lead_with_front_matter(
+        &mut self,
+        front_matter: Option<&FrontMatter>,
+        processor: &ContentProcessor,
+    ) {
+        if let Some(front_matter) = front_matter
+            && processor.config().output_format().is_markdown()
+        {
+            self.content = self.front_matter(front_matter) + &self.content;
+        }
+    }
+
+    /// Appends the content of the following page of a paginated article,
+    /// leaving out the pagination controls between the two pages and the
+    /// title, intro and other blocks the page repeats at its start.
+    /// [`refresh_content`](Self::refresh_content) must be called once all
+    /// pages are appended.
+    pub(crate) fn append_continuation(&mut self, continuation: HarvestedDocument) {
+        let markdown = without_repeated_lead(&self.markdown, &continuation.markdown);
+        if !markdown.is_empty() {
+            self.markdown = without_trailing_controls(&self.markdown);
+            self.markdown.push_str("\n\n");
+            self.markdown.push_str(&markdown);
+        }
+        for link in continuation.links {
+            if !self.links.contains(&link) {
+                self.links.push(link);
+            }
+        }
+        self.continuation_urls.push(continuation.final_url);
+        self.timings.fetch_ms += continuation.timings.fetch_ms;
+        self.timings.extraction_ms += continuation.timings.extraction_ms;
+        self.sizes.html_bytes += continuation.sizes.html_bytes;
+    }
+
+    /// Derives the plain text, sizes and rendered content again from
+    /// [`markdown`](Self::markdown).
+    pub(crate) fn refresh_content(
+        &mut self,
+        processor: &ContentProcessor,
+        front_matter: Option<&FrontMatter>,
+    ) {
+        self.text = markdown_to_plain_text(&self.markdown);
+        self.content = processor.config().output_format().render(&self.markdown);
+        self.sizes.markdown_bytes = self.markdown.len();
+        self.sizes.text_bytes = self.text.len();
+        self.lead_with_front_matter(front_matter, processor);
+    }
+
+    /// Returns the PDF page number the byte `offset` of
+    /// [`markdown`](Self::markdown) was taken from, so chunks can cite pages.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_harvest::{HarvestedDocument, PageSpan};
+    ///
+    /// let document = HarvestedDocument {
+    ///     markdown: "First page\n\nSecond page".to_string(),
+    ///     pages: vec![
+    ///         PageSpan { page: 1, start: 0, end: 10 },
+    ///         PageSpan { page: 2, start: 12, end: 23 },
+    ///     ],
+    ///     ..HarvestedDocument::default()
+    /// };
+    ///
+    /// assert_eq!(document.page_at(14), Some(2));
+    /// assert_eq!(document.page_at(11), None);
+    /// ```
+    pub fn page_at(&self, offset: usize) -> Option<u32> {
+        self.pages
+            .iter()
+            .find(|span| (span.start..span.end).contains(&offset))
+            .map(|span| span.page)
+    }
+
+    /// Renders a front-matter block describing this document, from its URLs,
+    /// page metadata, fetch time, language and a hash of its Markdown.
+    pub fn front_matter(&self, front_matter: &FrontMatter) -> String {
+        front_matter.render(|field| match field {
+            FrontMatterField::SourceUrl => Some(self.requested_url.clone()),
+            FrontMatterField::FinalUrl => Some(self.final_url.clone()),
+            FrontMatterField::Title => self.metadata.title.clone(),
+            FrontMatterField::Author => self.metadata.author.clone(),
+            FrontMatterField::PublishedTime => self.metadata.published_time.clone(),
+            FrontMatterField::FetchedAt => self.fetched_at.map(format_timestamp),
+            FrontMatterField::Language => {
+                self.language.as_ref().map(|language| language.tag.clone())
+            }
+            FrontMatterField::ContentHash => Some(content_hash(&self.markdown)),
+        })
+    }
+
+    /// Returns the Markdown led by a front-matter block, ready to be saved as a
+    /// Markdown file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_harvest::{FrontMatter, FrontMatterField, FrontMatterFormat, HarvestedDocument};
+    ///
+    /// let document = HarvestedDocument {
+    ///     requested_url: "https://example.com/post".to_string(),
+    ///     markdown: "Hello".to_string(),
+    ///     ..HarvestedDocument::default()
+    /// };
+    /// let front_matter = FrontMatter::new(FrontMatterFormat::Yaml).field(FrontMatterField::SourceUrl);
+    ///
+    /// assert_eq!(
+    ///     document.markdown_with_front_matter(&front_matter),
+    ///     "---\nsource_url: \"https://example.com/post\"\n---\n\nHello"
+    /// );
+    /// ```
+    pub fn markdown_with_front_matter(&self, front_matter: &FrontMatter) -> String {
+        self.front_matter(front_matter) + &self.markdown
+    }
+}
+
+/// Extracts the body of a fetched page according to its kind, converting it
+/// first when it is a PDF.
+fn extract_page(
+    page: &FetchedPage,
+    processor: &ContentProcessor,
+) -> Result<ExtractedContent, HarvestError> {
+    #[cfg(feature = "pdf")]
+    if let Some(pdf) = &page.pdf {
+        return processor
+            .extract_pdf_with_content_language(pdf, page.content_language.as_deref())
+            .map_err(|error| HarvestError::Body {
+                url: page.requested_url.clone(),
+                message: error.to_string(),
+            });
+    }
+    Ok(processor.extract_as_with_content_language(
+        &page.body,
+        page.body_kind(),
+        Some(&page.final_url),
+        page.content_language.as_deref(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extraction_report::ExtractionStrategy;
+    use crate::front_matter::FrontMatterFormat;
+    use crate::http_client::test_page;
+    use crate::language::LanguageSource;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn page(body: &str, content_language: Option<&str>) -> FetchedPage {
+        FetchedPage {
+            requested_url: "http://example.com/post".to_string(),
+            content_language: content_language.map(str::to_string),
+            fetch_duration: Duration::from_millis(120),
+            fetched_at: UNIX_EPOCH + Duration::from_secs(1_767_519_000),
+            ..test_page("https://example.com/post", "text/html; charset=utf-8", body)
+        }
+    }
+
+    #[test]
+    fn test_harvested_document_from_page() {
+        let html = r#"<html lang="en"><head><title>Launch day</title></head><body><article>
+            <p>We shipped the <strong>new release</strong> today.</p>
+        </article></body></html>"#;
+
+        let document =
+            HarvestedDocument::from_page(page(html, None), &ContentProcessor::new(), None).unwrap();
+
+        assert_eq!(document.requested_url, "http://example.com/post");
+        assert_eq!(document.final_url, "https://example.com/post");
+        assert_eq!(document.status, 200);
+        assert_eq!(
+            document.content_type.as_deref(),
+            Some("text/html; charset=utf-8")
+        );
+        assert_eq!(document.markdown, "We shipped the **new release** today.");
+        assert_eq!(document.text, "We shipped the new release today.");
+        assert_eq!(document.metadata.title.as_deref(), Some("Launch day"));
+        assert_eq!(document.language.unwrap().tag, "en");
+        assert_eq!(document.timings.fetch_ms, 120);
+        assert_eq!(document.sizes.html_bytes, html.len());
+        assert_eq!(document.sizes.markdown_bytes, document.markdown.len());
+        assert_eq!(document.sizes.text_bytes, document.text.len());
+        assert_eq!(
+            document.report.strategy,
+            ExtractionStrategy::ContentSelector
+        );
+    }
+
+    #[test]
+    fn test_harvested_document_language_from_header() {
+        let html = "<html><body><p>Guten Tag und herzlich willkommen.</p></body></html>";
+        let document = HarvestedDocument::from_page(
+            page(html, Some("de-DE, en")),
+            &ContentProcessor::new(),
+            None,
+        )
+        .unwrap();
+        let language = document.language.unwrap();
+        assert_eq!(language.tag, "de-DE");
+        assert_eq!(language.source, LanguageSource::ContentLanguage);
+    }
+
+    #[test]
+    fn test_harvested_document_front_matter() {
+        let html = r#"<html lang="en"><head><title>Launch day</title>
+            <meta name="author" content="Ana Lima"></head><body><article>
+            <p>We shipped the <strong>new release</strong> today.</p>
+        </article></body></html>"#;
+        let front_matter = FrontMatter::yaml().without_field(FrontMatterField::PublishedTime);
+
+        let document = HarvestedDocument::from_page(
+            page(html, None),
+            &ContentProcessor::new(),
+            Some(&front_matter),
+        )
+        .unwrap();
+        assert_eq!(
+            document.content,
+            format!(
+                "---\nsource_url: \"http://example.com/post\"\n\
+                 final_url: \"https://example.com/post\"\ntitle: \"Launch day\"\n\
+                 author: \"Ana Lima\"\nfetched_at: \"2026-01-04T09:30:00Z\"\n\
+                 language: \"en\"\ncontent_hash: \"{}\"\n---\n\n{}",
+                content_hash(&document.markdown),
+                document.markdown
+            )
+        );
+        assert_eq!(
+            document.markdown_with_front_matter(&front_matter),
+            document.content
+        );
+
+        let toml = document.front_matter(
+            &FrontMatter::new(FrontMatterFormat::Toml).field(FrontMatterField::Title),
+        );
+        assert_eq!(toml, "+++\ntitle = \"Launch day\"\n+++\n\n");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_harvested_document_serde_round_trip() {
+        let html = r#"<html><head><meta property="og:type" content="article"></head>
+            <body><article><p>Serializable content for the index.</p></article></body></html>"#;
+        let document =
+            HarvestedDocument::from_page(page(html, None), &ContentProcessor::new(), None).unwrap();
+
+        let json = serde_json::to_string(&document).unwrap();
+        assert!(json.contains("\"final_url\":\"https://example.com/post\""));
+        assert!(json.contains("\"strategy\":\"ContentSelector\""));
+
+        let decoded: HarvestedDocument = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, document);
+    }
+
+    #[test]
+    fn test_harvested_document_passes_raw_markdown_through() {
+        let readme = "# Harvest\n\nRun `cargo test`:\n\n```sh\ncargo test --all-features\n```\n\n\
+                      See [the docs](https://docs.rs/markdown-harvest).\n";
+        let fetched = FetchedPage {
+            final_url: "https://raw.example.com/owner/repo/main/README.md".to_string(),
+            content_type: Some("text/plain; charset=utf-8".to_string()),
+            ..page(readme, None)
+        };
+        let document =
+            HarvestedDocument::from_page(fetched, &ContentProcessor::new(), None).unwrap();
+
+        assert_eq!(document.markdown, readme.trim_end());
+        assert_eq!(document.metadata.title.as_deref(), Some("Harvest"));
+        assert_eq!(document.report.strategy, ExtractionStrategy::Markdown);
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn test_harvested_document_from_pdf() {
+        use crate::pdf_converter::tests::report_pdf;
+
+        let pdf = report_pdf();
+        let fetched = FetchedPage {
+            content_type: Some("application/pdf".to_string()),
+            body: String::new(),
+            pdf: Some(pdf.clone()),
+            ..page("", None)
+        };
+        let document =
+            HarvestedDocument::from_page(fetched, &ContentProcessor::new(), None).unwrap();
+
+        assert!(
+            document
+                .markdown
+                .starts_with("# Annual Report\n\n## Introduction")
+        );
+        assert_eq!(
+            document.metadata.title.as_deref(),
+            Some("Annual Report 2025")
+        );
+        assert_eq!(document.report.strategy, ExtractionStrategy::Pdf);
+        assert_eq!(document.sizes.html_bytes, pdf.len());
+        assert_eq!(document.pages.len(), 2);
+        assert_eq!(document.page_at(0), Some(1));
+        let results = document.markdown.find("## Results").unwrap();
+        assert_eq!(document.page_at(results), Some(2));
+
+        let broken = FetchedPage {
+            pdf: Some(b"%PDF-1.4 truncated".to_vec()),
+            ..page("", None)
+        };
+        let error = HarvestedDocument::from_page(broken, &ContentProcessor::new(), None);
+        assert!(matches!(error, Err(HarvestError::Body { .. })));
+    }
+}
@@ -1,9 +1,86 @@
+use crate::feed::is_feed;
+use crate::harvest_error::HarvestError;
 use crate::http_regex::URL_REGEX;
+use crate::sitemap::{SitemapConfig, SitemapUrl, discover_sitemap_urls};
+use crate::text_passthrough::BodyKind;
 use crate::{http_config::HttpConfig, user_agent::UserAgent};
-use futures::future;
+use futures::{StreamExt, future, stream};
+use reqwest::header::{CONTENT_LANGUAGE, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
 use reqwest::{Client, blocking};
 use std::future::Future;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Browser-like request headers sent with every request, besides the user agent.
+const BROWSER_HEADERS: [(&str, &str); 12] = [
+    (
+        "Accept",
+        "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8,application/signed-exchange;v=b3;q=0.7",
+    ),
+    ("Accept-Language", "en-US,en;q=0.5"),
+    ("DNT", "1"),
+    ("Connection", "keep-alive"),
+    ("Upgrade-Insecure-Requests", "1"),
+    ("Sec-Fetch-Dest", "document"),
+    ("Sec-Fetch-Mode", "navigate"),
+    ("Sec-Fetch-Site", "none"),
+    ("Sec-Fetch-User", "?1"),
+    ("js_timeout", "2000"),
+    ("Cache-Control", "no-cache"),
+    ("js", "true"),
+];
+
+/// A page fetched over HTTP, together with the details of the exchange.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchedPage {
+    /// The URL as found in the input text.
+    pub requested_url: String,
+    /// The URL of the response, after redirects.
+    pub final_url: String,
+    pub status: u16,
+    /// Value of the `Content-Type` response header.
+    pub content_type: Option<String>,
+    /// Value of the `Content-Language` response header.
+    pub content_language: Option<String>,
+    /// The decoded response body.
+    pub body: String,
+    /// The raw body of a PDF response, which is not decoded as text. Only read
+    /// with the `pdf` feature; `body` is then empty.
+    pub pdf: Option<Vec<u8>>,
+    /// Time from sending the request until the whole body was read.
+    pub fetch_duration: Duration,
+    /// When the request was sent.
+    pub fetched_at: SystemTime,
+}
+
+impl FetchedPage {
+    /// How the body is converted to Markdown, from its content type and final URL.
+    pub fn body_kind(&self) -> BodyKind {
+        BodyKind::detect(self.content_type.as_deref(), &self.final_url)
+    }
+
+    /// Returns `true` when the page is an RSS or Atom feed, from its content
+    /// type or, for generic XML, its root element.
+    pub fn is_feed(&self) -> bool {
+        is_feed(self.content_type.as_deref(), &self.body)
+    }
+}
+
+/// A `200 OK` response of `content_type` fetched from `url` without
+/// redirects, for tests to adjust with struct update syntax.
+#[cfg(test)]
+pub(crate) fn test_page(url: &str, content_type: &str, body: &str) -> FetchedPage {
+    FetchedPage {
+        requested_url: url.to_string(),
+        final_url: url.to_string(),
+        status: 200,
+        content_type: Some(content_type.to_string()),
+        content_language: None,
+        body: body.to_string(),
+        pdf: None,
+        fetch_duration: Duration::from_millis(5),
+        fetched_at: SystemTime::now(),
+    }
+}
 
 /// Component responsible for handling HTTP requests and URL processing.
 ///
@@ -62,7 +139,109 @@ impl HttpClient {
         Ok(())
     }
 
-    fn extract_urls(&self, text: &str) -> Vec<String> {
+    /// Extracts URLs from text and fetches each of them, reporting the outcome per URL.
+    ///
+    /// Unlike [`fetch_content_from_text`](Self::fetch_content_from_text), failed
+    /// requests are returned as errors instead of being skipped, and successful
+    /// ones carry the final URL, status, headers of interest and timing.
+    pub fn fetch_pages_from_text(
+        &self,
+        text: &str,
+        http_config: HttpConfig,
+    ) -> Vec<Result<FetchedPage, HarvestError>> {
+        self.fetch_pages(&self.extract_urls(text), http_config)
+    }
+
+    /// Fetches each of the given URLs, reporting the outcome per URL, as
+    /// [`fetch_pages_from_text`](Self::fetch_pages_from_text) does for the
+    /// URLs found in a text.
+    pub fn fetch_pages(
+        &self,
+        urls: &[String],
+        http_config: HttpConfig,
+    ) -> Vec<Result<FetchedPage, HarvestError>> {
+        let client = build_client(http_config);
+        let headers = browser_headers(UserAgent::random());
+
+        urls.iter()
+            .map(|url| fetch_page(&client, url, headers.clone()))
+            .collect()
+    }
+
+    /// Fetches a single URL, as [`fetch_pages`](Self::fetch_pages) does for a list.
+    pub fn fetch_page(
+        &self,
+        url: &str,
+        http_config: HttpConfig,
+    ) -> Result<FetchedPage, HarvestError> {
+        fetch_page(
+            &build_client(http_config),
+            url,
+            browser_headers(UserAgent::random()),
+        )
+    }
+
+    /// Asynchronous version of [`fetch_pages_from_text`](Self::fetch_pages_from_text).
+    ///
+    /// All URLs are fetched concurrently; results keep the order of the URLs in the text.
+    pub async fn fetch_pages_from_text_async(
+        &self,
+        text: &str,
+        http_config: HttpConfig,
+    ) -> Vec<Result<FetchedPage, HarvestError>> {
+        self.fetch_pages_async(&self.extract_urls(text), http_config)
+            .await
+    }
+
+    /// Asynchronous version of [`fetch_pages`](Self::fetch_pages).
+    ///
+    /// URLs are fetched concurrently, at most
+    /// [`HttpConfig::max_concurrency`] at a time; results keep the order of the URLs.
+    pub async fn fetch_pages_async(
+        &self,
+        urls: &[String],
+        http_config: HttpConfig,
+    ) -> Vec<Result<FetchedPage, HarvestError>> {
+        let client = build_client_async(http_config);
+        let headers = browser_headers(UserAgent::random());
+
+        let requests: Vec<_> = urls
+            .iter()
+            .map(|url| fetch_page_async(client.clone(), url.clone(), headers.clone()))
+            .collect();
+
+        match http_config.max_concurrency() {
+            Some(max_concurrency) => {
+                stream::iter(requests)
+                    .buffered(max_concurrency)
+                    .collect()
+                    .await
+            }
+            None => future::join_all(requests).await,
+        }
+    }
+
+    /// Finds the page URLs listed in the sitemaps of a site.
+    ///
+    /// `url` may be a sitemap (plain, gzipped or a sitemap index), a robots.txt
+    /// file or any page of the site, in which case the sitemaps are discovered
+    /// from its robots.txt or `/sitemap.xml`. Sitemap indexes are followed and
+    /// the URLs are filtered by `sitemap_config`.
+    pub fn fetch_sitemap_urls(
+        &self,
+        url: &str,
+        http_config: HttpConfig,
+        sitemap_config: &SitemapConfig,
+    ) -> Result<Vec<SitemapUrl>, HarvestError> {
+        let client = build_client(http_config);
+        let headers = browser_headers(UserAgent::random());
+
+        discover_sitemap_urls(url, sitemap_config, |url| {
+            fetch_bytes(&client, url, headers.clone())
+        })
+    }
+
+    pub(crate) fn extract_urls(&self, text: &str) -> Vec<String> {
         URL_REGEX
             .find_iter(text)
             .map(|m| clean_url(m.as_str()))
@@ -99,27 +278,12 @@ fn handles_http_requests_results(
 ) -> Vec<(String, String)> {
     let client = build_client(http_config);
     let mut results = Vec::new();
-    let user_agent = UserAgent::random();
+    let headers = browser_headers(UserAgent::random());
 
     for url in &urls {
         match client
             .get(url)
-            .header("User-Agent", user_agent.to_string())
-            .header(
-                "Accept",
-                "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8,application/signed-exchange;v=b3;q=0.7",
-            )
-            .header("Accept-Language", "en-US,en;q=0.5")
-            .header("DNT", "1")
-            .header("Connection", "keep-alive")
-            .header("Upgrade-Insecure-Requests", "1")
-            .header("Sec-Fetch-Dest", "document")
-            .header("Sec-Fetch-Mode", "navigate")
-            .header("Sec-Fetch-Site", "none")
-            .header("Sec-Fetch-User", "?1")
-            .header("js_timeout", "2000")
-            .header("Cache-Control", "no-cache")
-            .header("js", "true")
+            .headers(headers.clone())
             .send()
         {
             Ok(response) => match response.text() {
@@ -148,30 +312,17 @@ where
     Fut: Future<Output = ()>,
 {
     let client = build_client_async(http_config);
-    let user_agent = UserAgent::random();
+    let headers = browser_headers(UserAgent::random());
 
     let requests = urls.into_iter().map(|url| {
         let client = client.clone();
         let future = future.clone();
+        let headers = headers.clone();
 
         async move {
             match client
                 .get(&url)
-                .header("User-Agent", user_agent.to_string())
-                .header(
-                    "Accept",
-                    "text/html,application/xhtml+xml,application/xml;q=0.9,image/webp,*/*;q=0.8",
-                )
-                .header("Accept-Language", "en-US,en;q=0.5")
-                .header("DNT", "1")
-                .header("Connection", "keep-alive")
-                .header("Upgrade-Insecure-Requests", "1")
-                .header("Sec-Fetch-Dest", "document")
-                .header("Sec-Fetch-Mode", "navigate")
-                .header("Sec-Fetch-Site", "none")
-                .header("Sec-Fetch-User", "?1")
-                .header("js_timeout", "2000")
-                .header("js", "true")
+                .headers(headers)
                 .send()
                 .await
             {
@@ -189,7 +340,184 @@ where
     Ok(())
 }
 
-fn build_client(http_config: HttpConfig) -> blocking::Client {
+pub(crate) fn fetch_page(
+    client: &blocking::Client,
+    url: &str,
+    headers: HeaderMap,
+) -> Result<FetchedPage, HarvestError> {
+    let fetched_at = SystemTime::now();
+    let started = Instant::now();
+    let response = client
+        .get(url)
+        .headers(headers)
+        .send()
+        .map_err(|e| HarvestError::Request {
+            url: url.to_string(),
+            message: e.to_string(),
+        })?;
+
+    let final_url = response.url().to_string();
+    let status = response.status().as_u16();
+    let content_type = header_value(response.headers(), CONTENT_TYPE);
+    let content_language = header_value(response.headers(), CONTENT_LANGUAGE);
+    let body_error = |e: reqwest::Error| HarvestError::Body {
+        url: url.to_string(),
+        message: e.to_string(),
+    };
+    let (body, pdf) = if is_pdf(content_type.as_deref(), &final_url) {
+        let pdf = response.bytes().map_err(body_error)?;
+        (String::new(), Some(pdf.to_vec()))
+    } else {
+        (response.text().map_err(body_error)?, None)
+    };
+
+    Ok(FetchedPage {
+        requested_url: url.to_string(),
+        final_url,
+        status,
+        content_type,
+        content_language,
+        body,
+        pdf,
+        fetch_duration: started.elapsed(),
+        fetched_at,
+    })
+}
+
+pub(crate) async fn fetch_page_async(
+    client: Client,
+    url: String,
+    headers: HeaderMap,
+) -> Result<FetchedPage, HarvestError> {
+    let fetched_at = SystemTime::now();
+    let started = Instant::now();
+    let response = client
+        .get(&url)
+        .headers(headers)
+        .send()
+        .await
+        .map_err(|e| HarvestError::Request {
+            url: url.clone(),
+            message: e.to_string(),
+        })?;
+
+    let final_url = response.url().to_string();
+    let status = response.status().as_u16();
+    let content_type = header_value(response.headers(), CONTENT_TYPE);
+    let content_language = header_value(response.headers(), CONTENT_LANGUAGE);
+    let body_error = |e: reqwest::Error| HarvestError::Body {
+        url: url.clone(),
+        message: e.to_string(),
+    };
+    let (body, pdf) = if is_pdf(content_type.as_deref(), &final_url) {
+        let pdf = response.bytes().await.map_err(body_error)?;
+        (String::new(), Some(pdf.to_vec()))
+    } else {
+        (response.text().await.map_err(body_error)?, None)
+    };
+
+    Ok(FetchedPage {
+        requested_url: url,
+        final_url,
+        status,
+        content_type,
+        content_language,
+        body,
+        pdf,
+        fetch_duration: started.elapsed(),
+        fetched_at,
+    })
+}
+
+/// Fetches the raw body of a successful response.
+fn fetch_bytes(
+    client: &blocking::Client,
+    url: &str,
+    headers: HeaderMap,
+) -> Result<Vec<u8>, HarvestError> {
+    let response = client
+        .get(url)
+        .headers(headers)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| HarvestError::Request {
+            url: url.to_string(),
+            message: e.to_string(),
+        })?;
+    let body = response.bytes().map_err(|e| HarvestError::Body {
+        url: url.to_string(),
+        message: e.to_string(),
+    })?;
+    Ok(body.to_vec())
+}
+
+/// Asynchronous version of [`fetch_bytes`].
+pub(crate) async fn fetch_bytes_async(
+    client: Client,
+    url: String,
+    headers: HeaderMap,
+) -> Result<Vec<u8>, HarvestError> {
+    let response = client
+        .get(&url)
+        .headers(headers)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| HarvestError::Request {
+            url: url.clone(),
+            message: e.to_string(),
+        })?;
+    let body = response.bytes().await.map_err(|e| HarvestError::Body {
+        url: url.clone(),
+        message: e.to_string(),
+    })?;
+    Ok(body.to_vec())
+}
+
+/// Returns `true` when the response is a PDF document to be read as bytes:
+/// served as `application/pdf`, or without a specific type from a `.pdf` URL.
+/// Always `false` without the `pdf` feature.
+fn is_pdf(content_type: Option<&str>, url: &str) -> bool {
+    if !cfg!(feature = "pdf") {
+        return false;
+    }
+    let media_type = content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .map(|media_type| media_type.trim().to_ascii_lowercase());
+    match media_type.as_deref() {
+        Some("application/pdf") => true,
+        None | Some("application/octet-stream" | "binary/octet-stream") => reqwest::Url::parse(url)
+            .is_ok_and(|url| url.path().to_ascii_lowercase().ends_with(".pdf")),
+        _ => false,
+    }
+}
+
+/// Builds the browser-like headers sent with each request.
+pub(crate) fn browser_headers(user_agent: UserAgent) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Ok(value) = HeaderValue::from_str(&user_agent.to_string()) {
+        headers.insert(HeaderName::from_static("user-agent"), value);
+    }
+    for (name, value) in BROWSER_HEADERS {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            headers.insert(name, value);
+        }
+    }
+    headers
+}
+
+fn header_value(headers: &HeaderMap, name: HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub(crate) fn build_client(http_config: HttpConfig) -> blocking::Client {
     match http_config.timeout() {
         Some(timeout) => blocking::Client::builder()
             .timeout(Duration::from_millis(timeout))
@@ -203,7 +531,7 @@ fn build_client(http_config: HttpConfig) -> blocking::Client {
     }
 }
 
-fn build_client_async(http_config: HttpConfig) -> Client {
+pub(crate) fn build_client_async(http_config: HttpConfig) -> Client {
     match http_config.timeout() {
         Some(timeout) => Client::builder()
             .timeout(Duration::from_millis(timeout))
@@ -242,7 +570,6 @@ fn clean_url(url: &str) -> String {
 mod tests {
     use crate::http_config::HttpConfigBuilder;
     use std::sync::{Arc, Mutex};
-    use tokio;
 
     use super::*;
 
@@ -550,4 +877,23 @@ mod tests {
             std::mem::size_of::<Client>()
         );
     }
+
+    #[test]
+    #[cfg(feature = "pdf")]
+    fn test_is_pdf() {
+        let url = "https://example.com/report";
+        assert!(is_pdf(Some("application/pdf"), url));
+        assert!(is_pdf(Some("Application/PDF; qs=0.001"), url));
+        assert!(!is_pdf(Some("text/html; charset=utf-8"), url));
+        assert!(!is_pdf(None, url));
+        assert!(is_pdf(
+            None,
+            "https://example.com/files/Report.PDF?download=1"
+        ));
+        assert!(is_pdf(
+            Some("application/octet-stream"),
+            "https://example.com/a.pdf"
+        ));
+        assert!(!is_pdf(Some("text/html"), "https://example.com/a.pdf"));
+    }
 }
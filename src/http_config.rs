@@ -3,6 +3,7 @@ pub struct HttpConfig {
     timeout: Option<u64>,
     max_redirect: Option<usize>,
     cookie_store: bool,
+    max_concurrency: Option<usize>,
 }
 
 #[derive(Default)]
@@ -10,6 +11,7 @@ pub struct HttpConfigBuilder {
     timeout: Option<u64>,
     max_redirect: Option<usize>,
     cookie_store: bool,
+    max_concurrency: Option<usize>,
 }
 
 impl HttpConfigBuilder {
@@ -18,6 +20,7 @@ impl HttpConfigBuilder {
             timeout: None,
             max_redirect: None,
             cookie_store: false,
+            max_concurrency: None,
         }
     }
 
@@ -36,11 +39,22 @@ impl HttpConfigBuilder {
         self
     }
 
+    /// Limits how many requests are in flight at once. When unset, the
+    /// asynchronous entry points are unlimited while
+    /// [`get_sitemap_documents`](crate::MarkdownHarvester::get_sitemap_documents)
+    /// fetches on a single thread.
+    /// A value of 0 is treated as 1.
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency.max(1));
+        self
+    }
+
     pub fn build(self) -> HttpConfig {
         HttpConfig {
             timeout: self.timeout,
             max_redirect: self.max_redirect,
             cookie_store: self.cookie_store,
+            max_concurrency: self.max_concurrency,
         }
     }
 }
@@ -52,6 +66,7 @@ impl HttpConfig {
             timeout,
             max_redirect,
             cookie_store,
+            max_concurrency: None,
         }
     }
 
@@ -70,6 +85,10 @@ impl HttpConfig {
     pub fn cookie_store(&self) -> bool {
         self.cookie_store
     }
+
+    pub fn max_concurrency(&self) -> Option<usize> {
+        self.max_concurrency
+    }
 }
 
 #[cfg(test)]
@@ -81,7 +100,7 @@ mod tests {
         let builder = HttpConfigBuilder::new();
         assert_eq!(builder.timeout, None);
         assert_eq!(builder.max_redirect, None);
-        assert_eq!(builder.cookie_store, false);
+        assert!(!builder.cookie_store);
     }
 
     #[test]
@@ -89,7 +108,7 @@ mod tests {
         let builder = HttpConfigBuilder::default();
         assert_eq!(builder.timeout, None);
         assert_eq!(builder.max_redirect, None);
-        assert_eq!(builder.cookie_store, false);
+        assert!(!builder.cookie_store);
     }
 
     #[test]
@@ -97,7 +116,7 @@ mod tests {
         let builder = HttpConfigBuilder::new().timeout(5000);
         assert_eq!(builder.timeout, Some(5000));
         assert_eq!(builder.max_redirect, None);
-        assert_eq!(builder.cookie_store, false);
+        assert!(!builder.cookie_store);
     }
 
     #[test]
@@ -105,7 +124,7 @@ mod tests {
         let builder = HttpConfigBuilder::new().max_redirect(10);
         assert_eq!(builder.timeout, None);
         assert_eq!(builder.max_redirect, Some(10));
-        assert_eq!(builder.cookie_store, false);
+        assert!(!builder.cookie_store);
     }
 
     #[test]
@@ -113,7 +132,7 @@ mod tests {
         let builder = HttpConfigBuilder::new().cookie_store(true);
         assert_eq!(builder.timeout, None);
         assert_eq!(builder.max_redirect, None);
-        assert_eq!(builder.cookie_store, true);
+        assert!(builder.cookie_store);
     }
 
     #[test]
@@ -125,7 +144,7 @@ mod tests {
 
         assert_eq!(builder.timeout, Some(3000));
         assert_eq!(builder.max_redirect, Some(5));
-        assert_eq!(builder.cookie_store, true);
+        assert!(builder.cookie_store);
     }
 
     #[test]
@@ -138,7 +157,7 @@ mod tests {
 
         assert_eq!(config.timeout(), Some(2500));
         assert_eq!(config.max_redirect(), Some(8));
-        assert_eq!(config.cookie_store(), false);
+        assert!(!config.cookie_store());
     }
 
     #[test]
@@ -147,7 +166,7 @@ mod tests {
 
         assert_eq!(config.timeout(), None);
         assert_eq!(config.max_redirect(), None);
-        assert_eq!(config.cookie_store(), false);
+        assert!(!config.cookie_store());
     }
 
     #[test]
@@ -156,7 +175,7 @@ mod tests {
 
         assert_eq!(config.timeout(), None);
         assert_eq!(config.max_redirect(), None);
-        assert_eq!(config.cookie_store(), false);
+        assert!(!config.cookie_store());
     }
 
     #[test]
@@ -169,7 +188,7 @@ mod tests {
 
         assert_eq!(config.timeout(), Some(1000));
         assert_eq!(config.max_redirect(), Some(3));
-        assert_eq!(config.cookie_store(), true);
+        assert!(config.cookie_store());
     }
 
     #[test]
@@ -178,11 +197,23 @@ mod tests {
             timeout: Some(4000),
             max_redirect: Some(7),
             cookie_store: true,
+            max_concurrency: Some(4),
         };
 
         assert_eq!(config.timeout(), Some(4000));
         assert_eq!(config.max_redirect(), Some(7));
-        assert_eq!(config.cookie_store(), true);
+        assert!(config.cookie_store());
+        assert_eq!(config.max_concurrency(), Some(4));
+    }
+
+    #[test]
+    fn test_http_config_builder_max_concurrency() {
+        let config = HttpConfig::builder().max_concurrency(16).build();
+        assert_eq!(config.max_concurrency(), Some(16));
+
+        let config = HttpConfig::builder().max_concurrency(0).build();
+        assert_eq!(config.max_concurrency(), Some(1));
+        assert_eq!(HttpConfig::default().max_concurrency(), None);
     }
 
     #[test]
@@ -193,7 +224,7 @@ mod tests {
             .cookie_store(true)
             .build();
 
-        let cloned = original.clone();
+        let cloned = Clone::clone(&original);
 
         assert_eq!(original.timeout(), cloned.timeout());
         assert_eq!(original.max_redirect(), cloned.max_redirect());
@@ -249,7 +280,7 @@ mod tests {
 
         assert_eq!(config.timeout(), Some(2000));
         assert_eq!(config.max_redirect(), Some(6));
-        assert_eq!(config.cookie_store(), true);
+        assert!(config.cookie_store());
     }
 
     #[test]
@@ -263,7 +294,7 @@ mod tests {
 
         assert_eq!(config.timeout(), Some(0));
         assert_eq!(config.max_redirect(), Some(0));
-        assert_eq!(config.cookie_store(), false);
+        assert!(!config.cookie_store());
 
         let config2 = HttpConfig::builder()
             .timeout(u64::MAX) // Maximum timeout
@@ -273,7 +304,7 @@ mod tests {
 
         assert_eq!(config2.timeout(), Some(u64::MAX));
         assert_eq!(config2.max_redirect(), Some(usize::MAX));
-        assert_eq!(config2.cookie_store(), true);
+        assert!(config2.cookie_store());
     }
 
     #[test]
@@ -282,16 +313,16 @@ mod tests {
         let config1 = HttpConfig::builder().timeout(1000).build();
         assert_eq!(config1.timeout(), Some(1000));
         assert_eq!(config1.max_redirect(), None);
-        assert_eq!(config1.cookie_store(), false);
+        assert!(!config1.cookie_store());
 
         let config2 = HttpConfig::builder().max_redirect(5).build();
         assert_eq!(config2.timeout(), None);
         assert_eq!(config2.max_redirect(), Some(5));
-        assert_eq!(config2.cookie_store(), false);
+        assert!(!config2.cookie_store());
 
         let config3 = HttpConfig::builder().cookie_store(true).build();
         assert_eq!(config3.timeout(), None);
         assert_eq!(config3.max_redirect(), None);
-        assert_eq!(config3.cookie_store(), true);
+        assert!(config3.cookie_store());
     }
 }
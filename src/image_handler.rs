@@ -0,0 +1,460 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use reqwest::Url;
+use scraper::node::Text;
+use scraper::{ElementRef, Html, Node, Selector};
+
+use crate::table_converter::rename_element;
+
+/// Prefix of the placeholder that stands in for an image until the page has been
+/// converted and cleaned, so neither html2md nor the line filters touch it.
+const PLACEHOLDER_PREFIX: &str = "MDHARVESTIMAGE";
+
+static PLACEHOLDER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(&format!(r"{}(\d+)", PLACEHOLDER_PREFIX)).unwrap());
+
+/// Image URLs used for tracking and spacing rather than content.
+static TRACKING_URL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)(?:[/_.-]|^)(?:pixel|beacon|spacer|1x1|blank|tracking|track)(?:\.gif|\.png|[/?]|$)",
+    )
+    .unwrap()
+});
+
+/// Attributes holding the real source of a lazy-loaded image, in order of preference.
+pub(crate) const LAZY_SRC_ATTRIBUTES: [&str; 4] =
+    ["data-src", "data-lazy-src", "data-original", "data-url"];
+
+/// Attributes holding a `srcset` candidate list, the standard one first.
+pub(crate) const SRCSET_ATTRIBUTES: [&str; 3] = ["srcset", "data-srcset", "data-lazy-srcset"];
+
+/// How images are rendered in the output.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::{ContentProcessor, ExtractionConfig, ImageMode};
+///
+/// let html = r#"<html><body><article>
+///     <p>Quarterly revenue grew faster than expected in every region.</p>
+///     <figure><img src="/charts/q3.png" alt="Revenue chart"><figcaption>Revenue by quarter</figcaption></figure>
+/// </article></body></html>"#;
+///
+/// let placeholder = ContentProcessor::with_config(
+///     ExtractionConfig::builder().image_mode(ImageMode::Placeholder).build(),
+/// );
+/// let markdown = placeholder.html_to_markdown(html);
+/// assert!(markdown.contains("[Image: Revenue chart — Revenue by quarter]"));
+///
+/// let inline = ContentProcessor::with_config(
+///     ExtractionConfig::builder().image_mode(ImageMode::Markdown).build(),
+/// );
+/// let markdown = inline.html_to_markdown_with_url(html, "https://example.com/report");
+/// assert!(markdown.contains("![Revenue chart](https://example.com/charts/q3.png)"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageMode {
+    /// Remove images together with their alt text (the original behaviour).
+    #[default]
+    Remove,
+    /// Replace each image with `[Image: alt — caption]`, keeping the description
+    /// of a figure without its URL. Images with neither alt text nor caption are
+    /// removed.
+    Placeholder,
+    /// Keep images as `![alt](absolute-src)`, followed by the figure caption in
+    /// italics. Inline SVGs, which have no source, get a placeholder instead.
+    Markdown,
+}
+
+/// An image description collected from the page.
+#[derive(Debug, Clone, PartialEq)]
+struct Image {
+    alt: String,
+    src: Option<String>,
+    caption: Option<String>,
+}
+
+impl Image {
+    fn render(&self, mode: ImageMode) -> String {
+        match (mode, &self.src) {
+            (ImageMode::Markdown, Some(src)) => {
+                let alt = if self.alt.is_empty() {
+                    self.caption.as_deref().unwrap_or_default()
+                } else {
+                    &self.alt
+                };
+                let image = format!("![{}]({})", escape_brackets(alt), escape_url(src));
+                match &self.caption {
+                    Some(caption) => format!("{}\n\n*{}*", image, caption),
+                    None => image,
+                }
+            }
+            _ => self.placeholder(),
+        }
+    }
+
+    fn placeholder(&self) -> String {
+        let parts: Vec<&str> = std::iter::once(self.alt.as_str())
+            .chain(self.caption.as_deref())
+            .filter(|part| !part.is_empty())
+            .collect();
+
+        match parts.as_slice() {
+            [] => String::new(),
+            [alt, caption] if alt == caption => format!("[Image: {}]", escape_brackets(alt)),
+            parts => format!("[Image: {}]", escape_brackets(&parts.join(" — "))),
+        }
+    }
+}
+
+/// Replaces every content image in the document with a placeholder and
+/// returns the rendered images, to be put back with [`restore_images`].
+///
+/// `<figure>`s holding an image are replaced as a whole so their `<figcaption>`
+/// travels with the image. Lazy-load attributes and `srcset` candidates are
+/// preferred over `src`, which is often a blurred or blank stand-in, and
+/// sources are resolved against `base_url`. Tracking pixels are removed.
+pub(crate) fn extract_images(
+    document: &mut Html,
+    mode: ImageMode,
+    base_url: Option<&Url>,
+) -> Vec<String> {
+    let figure_selector = Selector::parse("figure").unwrap();
+    let caption_selector = Selector::parse("figcaption").unwrap();
+    let image_selector = Selector::parse("picture, img, svg").unwrap();
+
+    let mut found: Vec<(_, Option<Image>, bool)> = Vec::new();
+    for figure in document.root_element().select(&figure_selector) {
+        let Some(image) = figure.select(&image_selector).next() else {
+            continue;
+        };
+        let caption = figure
+            .select(&caption_selector)
+            .next()
+            .map(element_text)
+            .filter(|caption| !caption.is_empty());
+        let image = describe_image(image, base_url).map(|image| Image { caption, ..image });
+        found.push((figure.id(), image, true));
+    }
+    for image in document.root_element().select(&image_selector) {
+        let nested = image
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .any(|ancestor| matches!(ancestor.value().name(), "figure" | "picture" | "svg"));
+        if !nested {
+            found.push((image.id(), describe_image(image, base_url), false));
+        }
+    }
+
+    let mut images = Vec::with_capacity(found.len());
+    for (node_id, image, is_figure) in found {
+        let rendered = image.map(|image| image.render(mode)).unwrap_or_default();
+        if is_figure {
+            // The figure becomes a paragraph holding only the placeholder
+            let children: Vec<_> = match document.tree.get(node_id) {
+                Some(node) => node.children().map(|child| child.id()).collect(),
+                None => continue,
+            };
+            for child_id in children {
+                if let Some(mut child) = document.tree.get_mut(child_id) {
+                    child.detach();
+                }
+            }
+        }
+        let Some(mut node) = document.tree.get_mut(node_id) else {
+            continue;
+        };
+        if rendered.is_empty() {
+            node.detach();
+            continue;
+        }
+
+        let placeholder = Node::Text(Text {
+            text: format!("{}{}", PLACEHOLDER_PREFIX, images.len())
+                .as_str()
+                .into(),
+        });
+        if is_figure {
+            rename_element(node.value(), "p");
+            node.append(placeholder);
+        } else {
+            // An image stays inline, in place of the element
+            node.insert_before(placeholder);
+            node.detach();
+        }
+        images.push(rendered);
+    }
+
+    images
+}
+
+/// Substitutes the image placeholders in `markdown` with the rendered images.
+///
+/// Returns the Markdown and the number of images put back; images whose
+/// placeholder was removed along with its surroundings are not counted.
+pub(crate) fn restore_images(markdown: &str, images: &[String]) -> (String, usize) {
+    if images.is_empty() {
+        return (markdown.to_string(), 0);
+    }
+
+    let mut restored = 0;
+    let markdown = PLACEHOLDER_REGEX
+        .replace_all(markdown, |caps: &Captures| {
+            let image = caps[1]
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| images.get(index))
+                .cloned();
+            restored += usize::from(image.is_some());
+            image.unwrap_or_default()
+        })
+        .to_string();
+    (markdown, restored)
+}
+
+/// Reads the description and source of a `<picture>`, `<img>` or `<svg>`.
+///
+/// Returns `None` for tracking pixels.
+fn describe_image(element: ElementRef<'_>, base_url: Option<&Url>) -> Option<Image> {
+    match element.value().name() {
+        "svg" => {
+            let title_selector = Selector::parse("title").unwrap();
+            let alt = element
+                .value()
+                .attr("aria-label")
+                .map(str::to_string)
+                .or_else(|| element.select(&title_selector).next().map(element_text))
+                .unwrap_or_default();
+            Some(Image {
+                alt: normalize_whitespace(&alt),
+                src: None,
+                caption: None,
+            })
+        }
+        "picture" => {
+            let img_selector = Selector::parse("img").unwrap();
+            let source_selector = Selector::parse("source").unwrap();
+            let img = element.select(&img_selector).next();
+            let mut image = match img {
+                Some(img) => describe_image(img, base_url)?,
+                None => Image {
+                    alt: String::new(),
+                    src: None,
+                    caption: None,
+                },
+            };
+            // <source> candidates are listed by preference; fall back to the <img>
+            image.src = element
+                .select(&source_selector)
+                .find_map(best_srcset_candidate)
+                .and_then(|src| resolve_src(&src, base_url))
+                .or(image.src);
+            Some(image)
+        }
+        _ => {
+            if is_tracking_pixel(element) {
+                return None;
+            }
+            let src = image_source(element).and_then(|src| resolve_src(&src, base_url));
+            if src
+                .as_deref()
+                .is_some_and(|src| TRACKING_URL_REGEX.is_match(src))
+            {
+                return None;
+            }
+            let alt = element
+                .value()
+                .attr("alt")
+                .or_else(|| element.value().attr("title"))
+                .unwrap_or_default();
+            Some(Image {
+                alt: normalize_whitespace(alt),
+                src,
+                caption: None,
+            })
+        }
+    }
+}
+
+/// Picks the source of an `<img>`: a lazy-load attribute, the largest `srcset`
+/// candidate, then `src` unless it is an inline `data:` stand-in.
+fn image_source(img: ElementRef<'_>) -> Option<String> {
+    let attr = |name: &str| {
+        img.value()
+            .attr(name)
+            .map(str::trim)
+            .filter(|value| !value.is_empty() && !value.starts_with("data:"))
+    };
+
+    LAZY_SRC_ATTRIBUTES
+        .iter()
+        .find_map(|name| attr(name))
+        .map(str::to_string)
+        .or_else(|| best_srcset_candidate(img))
+        .or_else(|| attr("src").map(str::to_string))
+}
+
+/// Returns the URL of the widest (or densest) candidate in the element's `srcset`.
+fn best_srcset_candidate(element: ElementRef<'_>) -> Option<String> {
+    let srcset = SRCSET_ATTRIBUTES
+        .iter()
+        .find_map(|name| element.value().attr(name))?;
+
+    srcset
+        .split(',')
+        .filter_map(|candidate| {
+            let mut parts = candidate.split_whitespace();
+            let url = parts.next()?;
+            let size = parts
+                .next()
+                .and_then(|descriptor| descriptor.trim_end_matches(['w', 'x']).parse::<f64>().ok())
+                .unwrap_or(1.0);
+            (!url.starts_with("data:")).then_some((url, size))
+        })
+        .reduce(|best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+        .map(|(url, _)| url.to_string())
+}
+
+/// Returns `true` for images declared at most 1×1 pixels or hidden inline.
+fn is_tracking_pixel(img: ElementRef<'_>) -> bool {
+    let dimension = |name: &str| {
+        img.value()
+            .attr(name)
+            .and_then(|value| value.trim().trim_end_matches("px").parse::<u32>().ok())
+    };
+    let tiny = |size: Option<u32>| size.is_some_and(|size| size <= 1);
+    let style = img.value().attr("style").unwrap_or_default().to_lowercase();
+
+    tiny(dimension("width")) && tiny(dimension("height"))
+        || style.replace(' ', "").contains("display:none")
+}
+
+fn resolve_src(src: &str, base_url: Option<&Url>) -> Option<String> {
+    if let Ok(absolute) = Url::parse(src) {
+        return matches!(absolute.scheme(), "http" | "https").then(|| absolute.to_string());
+    }
+    match base_url {
+        Some(base_url) => base_url.join(src).ok().map(|url| url.to_string()),
+        None => Some(src.to_string()),
+    }
+}
+
+fn element_text(element: ElementRef<'_>) -> String {
+    normalize_whitespace(&element.text().collect::<String>())
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn escape_brackets(text: &str) -> String {
+    text.replace('[', "\\[").replace(']', "\\]")
+}
+
+fn escape_url(url: &str) -> String {
+    url.replace(' ', "%20")
+        .replace('(', "%28")
+        .replace(')', "%29")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(html: &str, mode: ImageMode) -> (String, Vec<String>) {
+        let mut document = Html::parse_document(html);
+        let base_url = Url::parse("https://example.com/blog/post").unwrap();
+        let images = extract_images(&mut document, mode, Some(&base_url));
+        (document.root_element().html(), images)
+    }
+
+    #[test]
+    fn test_extract_images_placeholder_keeps_alt_and_caption() {
+        let (html, images) = extract(
+            r#"<p>Before <img src="a.png" alt="Inline [icon]"> after</p>
+            <figure><img src="chart.png" alt="Latency chart">
+                <figcaption>P99 latency by region</figcaption></figure>
+            <figure><svg aria-label="Architecture diagram"></svg></figure>
+            <img src="blank.png">"#,
+            ImageMode::Placeholder,
+        );
+
+        assert_eq!(
+            images,
+            vec![
+                "[Image: Latency chart — P99 latency by region]",
+                "[Image: Architecture diagram]",
+                "[Image: Inline \\[icon\\]]",
+            ]
+        );
+        assert!(html.contains("<p>Before MDHARVESTIMAGE2 after</p>"));
+        assert!(html.contains("<p>MDHARVESTIMAGE0</p>"));
+        assert!(!html.contains("<img") && !html.contains("<figcaption"));
+    }
+
+    #[test]
+    fn test_extract_images_markdown_sources() {
+        let (_, images) = extract(
+            r#"<img src="data:image/gif;base64,R0lGOD" data-src="/img/lazy.jpg" alt="Lazy">
+            <img srcset="small.jpg 480w, large.jpg 1080w, medium.jpg 800w" alt="Responsive">
+            <picture><source srcset="photo.webp 2x, photo-small.webp 1x"><img src="photo.jpg" alt="Photo"></picture>
+            <img src="https://tracker.example.net/pixel.gif?id=1" alt="">
+            <img src="/ad.png" width="1" height="1">
+            <figure><img src="/a (1).png"><figcaption>Team photo</figcaption></figure>"#,
+            ImageMode::Markdown,
+        );
+
+        assert_eq!(
+            images,
+            vec![
+                "![Team photo](https://example.com/a%20%281%29.png)\n\n*Team photo*",
+                "![Lazy](https://example.com/img/lazy.jpg)",
+                "![Responsive](https://example.com/blog/large.jpg)",
+                "![Photo](https://example.com/blog/photo.webp)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_images_without_usable_source() {
+        let (html, images) = extract(
+            "<p>No images here.</p><figure><blockquote>Quoted</blockquote></figure>",
+            ImageMode::Markdown,
+        );
+        assert!(images.is_empty());
+        assert!(html.contains("<figure><blockquote>Quoted</blockquote></figure>"));
+
+        let (html, images) = extract(
+            r#"<img alt="No source">
+            <img src="javascript:void(0)" alt="Scripted">
+            <img srcset="" src="" alt="">
+            <img srcset="wide.jpg fast, sharp.jpg 2x" alt="Odd srcset">"#,
+            ImageMode::Markdown,
+        );
+        assert_eq!(
+            images,
+            vec![
+                "[Image: No source]",
+                "[Image: Scripted]",
+                "![Odd srcset](https://example.com/blog/sharp.jpg)",
+            ]
+        );
+        assert!(!html.contains("<img"));
+    }
+
+    #[test]
+    fn test_restore_images() {
+        let images = vec!["[Image: Chart]".to_string()];
+        assert_eq!(
+            restore_images("See MDHARVESTIMAGE0 and MDHARVESTIMAGE7.", &images),
+            ("See [Image: Chart] and .".to_string(), 1)
+        );
+        assert_eq!(restore_images("Plain", &[]), ("Plain".to_string(), 0));
+    }
+}
@@ -0,0 +1,264 @@
+use whatlang::Lang;
+
+/// Confidence given to a language declared in the page markup.
+const MARKUP_CONFIDENCE: f64 = 0.8;
+
+/// Confidence given to the `Content-Language` header, which is often a
+/// server-wide default rather than a statement about the page.
+const CONTENT_LANGUAGE_CONFIDENCE: f64 = 0.6;
+
+/// Texts shorter than this many characters are not analysed statistically.
+const MIN_STATISTICAL_CHARS: usize = 20;
+
+/// The language of a page, with an estimate of how certain it is.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::{ContentProcessor, LanguageSource};
+///
+/// let html = r#"<html lang="pt-BR"><body><article>
+///     <p>O novo sistema de transporte público começa a funcionar na próxima semana em toda a cidade.</p>
+/// </article></body></html>"#;
+/// let language = ContentProcessor::new().extract(html, None).language.unwrap();
+///
+/// assert_eq!(language.tag, "pt-BR");
+/// assert_eq!(language.primary_language(), "pt");
+/// assert_eq!(language.source, LanguageSource::Markup);
+/// assert!(language.confidence > 0.8);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DetectedLanguage {
+    /// BCP 47 language tag, such as `en` or `pt-BR`.
+    pub tag: String,
+    /// Between 0.0 and 1.0; higher when several signals agree.
+    pub confidence: f64,
+    /// The signal the tag was taken from.
+    pub source: LanguageSource,
+}
+
+/// Where a [`DetectedLanguage`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LanguageSource {
+    /// `<html lang>`, a `content-language` meta tag or `og:locale`.
+    Markup,
+    /// The `Content-Language` response header.
+    ContentLanguage,
+    /// Statistical analysis of the extracted text.
+    Statistical,
+}
+
+impl DetectedLanguage {
+    /// Returns the primary language subtag in lowercase, e.g. `pt` for `pt-BR`.
+    pub fn primary_language(&self) -> String {
+        primary_subtag(&self.tag)
+    }
+}
+
+/// Combines the declared languages and a statistical guess on `text`.
+///
+/// Signals naming the same primary language reinforce each other, so a
+/// declaration confirmed by the text scores higher than either alone, while a
+/// template default such as `lang="en"` on a German article is outvoted by a
+/// confident statistical result.
+pub(crate) fn detect_language(
+    markup: Option<&str>,
+    content_language: Option<&str>,
+    text: &str,
+) -> Option<DetectedLanguage> {
+    let mut candidates: Vec<DetectedLanguage> = Vec::new();
+
+    if let Some(tag) = markup.and_then(normalize_tag) {
+        candidates.push(DetectedLanguage {
+            tag,
+            confidence: MARKUP_CONFIDENCE,
+            source: LanguageSource::Markup,
+        });
+    }
+    if let Some(tag) = content_language
+        .and_then(|header| header.split(',').next())
+        .and_then(normalize_tag)
+    {
+        candidates.push(DetectedLanguage {
+            tag,
+            confidence: CONTENT_LANGUAGE_CONFIDENCE,
+            source: LanguageSource::ContentLanguage,
+        });
+    }
+    if let Some(detected) = detect_statistically(text) {
+        candidates.push(detected);
+    }
+
+    // Candidates are in priority order, so the first of each group supplies
+    // the most specific tag
+    let mut groups: Vec<DetectedLanguage> = Vec::new();
+    for candidate in candidates {
+        let primary = candidate.primary_language();
+        match groups
+            .iter_mut()
+            .find(|group| group.primary_language() == primary)
+        {
+            Some(group) => {
+                group.confidence = 1.0 - (1.0 - group.confidence) * (1.0 - candidate.confidence)
+            }
+            None => groups.push(candidate),
+        }
+    }
+
+    groups
+        .into_iter()
+        .reduce(|best, group| {
+            if group.confidence > best.confidence {
+                group
+            } else {
+                best
+            }
+        })
+        .map(|mut language| {
+            language.confidence = (language.confidence * 1000.0).round() / 1000.0;
+            language
+        })
+}
+
+fn detect_statistically(text: &str) -> Option<DetectedLanguage> {
+    if text.chars().filter(|c| c.is_alphabetic()).count() < MIN_STATISTICAL_CHARS {
+        return None;
+    }
+    let info = whatlang::detect(text)?;
+    Some(DetectedLanguage {
+        tag: iso_639_1(info.lang()).to_string(),
+        confidence: info.confidence(),
+        source: LanguageSource::Statistical,
+    })
+}
+
+/// Trims a declared tag and uses `-` as separator; `pt_BR` becomes `pt-BR`.
+fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().replace('_', "-");
+    let primary = primary_subtag(&tag);
+    if primary.is_empty() || !primary.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    Some(tag)
+}
+
+fn primary_subtag(tag: &str) -> String {
+    tag.split('-')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Maps whatlang's ISO 639-3 codes to the two-letter codes used in markup.
+fn iso_639_1(lang: Lang) -> &'static str {
+    match lang {
+        Lang::Epo => "eo",
+        Lang::Eng => "en",
+        Lang::Rus => "ru",
+        Lang::Cmn => "zh",
+        Lang::Spa => "es",
+        Lang::Por => "pt",
+        Lang::Ita => "it",
+        Lang::Ben => "bn",
+        Lang::Fra => "fr",
+        Lang::Deu => "de",
+        Lang::Ukr => "uk",
+        Lang::Kat => "ka",
+        Lang::Ara => "ar",
+        Lang::Hin => "hi",
+        Lang::Jpn => "ja",
+        Lang::Heb => "he",
+        Lang::Yid => "yi",
+        Lang::Pol => "pl",
+        Lang::Amh => "am",
+        Lang::Jav => "jv",
+        Lang::Kor => "ko",
+        Lang::Nob => "nb",
+        Lang::Dan => "da",
+        Lang::Swe => "sv",
+        Lang::Fin => "fi",
+        Lang::Tur => "tr",
+        Lang::Nld => "nl",
+        Lang::Hun => "hu",
+        Lang::Ces => "cs",
+        Lang::Ell => "el",
+        Lang::Bul => "bg",
+        Lang::Bel => "be",
+        Lang::Mar => "mr",
+        Lang::Kan => "kn",
+        Lang::Ron => "ro",
+        Lang::Slv => "sl",
+        Lang::Hrv => "hr",
+        Lang::Srp => "sr",
+        Lang::Mkd => "mk",
+        Lang::Lit => "lt",
+        Lang::Lav => "lv",
+        Lang::Est => "et",
+        Lang::Tam => "ta",
+        Lang::Vie => "vi",
+        Lang::Urd => "ur",
+        Lang::Tha => "th",
+        Lang::Guj => "gu",
+        Lang::Uzb => "uz",
+        Lang::Pan => "pa",
+        Lang::Aze => "az",
+        Lang::Ind => "id",
+        Lang::Tel => "te",
+        Lang::Pes => "fa",
+        Lang::Mal => "ml",
+        Lang::Ori => "or",
+        Lang::Mya => "my",
+        Lang::Nep => "ne",
+        Lang::Sin => "si",
+        Lang::Khm => "km",
+        Lang::Tuk => "tk",
+        Lang::Aka => "ak",
+        Lang::Zul => "zu",
+        Lang::Sna => "sn",
+        Lang::Afr => "af",
+        Lang::Lat => "la",
+        Lang::Slk => "sk",
+        Lang::Cat => "ca",
+        Lang::Tgl => "tl",
+        Lang::Hye => "hy",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GERMAN: &str = "Die Stadtverwaltung hat heute bekannt gegeben, dass die neue Straßenbahnlinie \
+        ab dem kommenden Montag in Betrieb genommen wird und die Innenstadt mit den Vororten verbindet.";
+
+    #[test]
+    fn test_detect_language_agreement_raises_confidence() {
+        let declared = detect_language(Some("de_DE"), None, "").unwrap();
+        assert_eq!(declared.tag, "de-DE");
+        assert_eq!(declared.confidence, MARKUP_CONFIDENCE);
+
+        let confirmed = detect_language(Some("de_DE"), Some("de"), GERMAN).unwrap();
+        assert_eq!(confirmed.tag, "de-DE");
+        assert_eq!(confirmed.source, LanguageSource::Markup);
+        assert!(confirmed.confidence > 0.9);
+    }
+
+    #[test]
+    fn test_detect_language_text_outvotes_wrong_declaration() {
+        let language = detect_language(Some("en"), None, GERMAN).unwrap();
+        assert_eq!(language.tag, "de");
+        assert_eq!(language.source, LanguageSource::Statistical);
+    }
+
+    #[test]
+    fn test_detect_language_without_signals() {
+        assert_eq!(detect_language(None, None, "Too short"), None);
+        assert_eq!(detect_language(Some("  "), Some(""), ""), None);
+
+        let header = detect_language(None, Some("fr-CA, en"), "").unwrap();
+        assert_eq!(header.tag, "fr-CA");
+        assert_eq!(header.source, LanguageSource::ContentLanguage);
+    }
+}
@@ -37,25 +37,91 @@
 //! ## Main Components
 //!
 //! - [`MarkdownHarvester`]: The main struct for processing URLs and extracting content
+//! - [`HarvestedDocument`]: Rich per-URL result (final URL, status, Markdown, plain text, metadata, timings) returned by the `*_documents` entry points
 //! - [`UserAgent`]: Enum providing various browser user agent strings
+//! - [`ExtractionConfig`]: Configurable selectors and text filters used by [`ContentProcessor`]
+//! - [`SiteExtractor`]: Site-specific content extraction consulted before the generic heuristics
+//! - [`PageMetadata`]: Title, author, dates, OpenGraph and JSON-LD data returned by [`ContentProcessor::extract`]
+//! - [`DetectedLanguage`]: Page language with a confidence score, from markup, headers and the text itself
+//! - [`BoilerplatePack`]: Per-language navigation, cookie, subscription and share phrases removed from the output
+//! - [`FrontMatter`]: YAML or TOML front matter with the URLs, metadata, fetch time and content hash of a document
+//! - [`OutputFormat`]: GFM, CommonMark, plain text or sanitized HTML output from the same pipeline
+//! - [`Feed`]: RSS and Atom feeds, harvested as one document per entry
+//! - [`SitemapConfig`]: Path and `lastmod` filters for sitemap-driven bulk harvesting
+//! - [`Crawler`]: Bounded link-following crawl from seed URLs, yielding documents as they are harvested
+//! - [`BodyKind`]: Markdown, plain-text and source-file responses passed through without HTML cleaning
+//! - [`Renderer`]: Optional JavaScript rendering of thin pages, e.g. with a headless Chromium (`chromium` feature)
 //! - Pattern functions: Helper functions that define cleaning patterns for HTML processing
 
+mod boilerplate;
+#[cfg(feature = "chromium")]
+mod chromium_renderer;
+mod code_block;
 mod content_processor;
+mod content_recovery;
+mod crawler;
+mod embedded_content;
+mod extraction_config;
+mod extraction_report;
+mod feed;
+mod front_matter;
+mod harvest_config;
+mod harvest_error;
+mod harvested_document;
 mod http_client;
 mod http_config;
 mod http_regex;
+mod image_handler;
+mod language;
+mod link_handler;
 mod markdown_harvester;
+mod output_format;
+mod page_metadata;
+mod pagination;
 mod patterns;
+#[cfg(feature = "pdf")]
+mod pdf_converter;
+mod plain_text;
+mod renderer;
+mod site_extractor;
+mod sitemap;
+mod table_converter;
+mod text_passthrough;
 mod user_agent;
 
-pub use content_processor::ContentProcessor;
-pub use http_client::HttpClient;
+pub use boilerplate::{BoilerplateLanguage, BoilerplatePack, BoilerplatePacks};
+#[cfg(feature = "chromium")]
+pub use chromium_renderer::{ChromiumRenderer, WaitCondition};
+pub use content_processor::{ContentProcessor, ExtractedContent, PageSpan};
+pub use crawler::{CrawlConfig, CrawlConfigBuilder, CrawlScope, Crawler};
+pub use extraction_config::{ExtractionConfig, ExtractionConfigBuilder};
+pub use extraction_report::{
+    CleaningReport, CleaningRule, ExtractionReport, ExtractionStrategy, Removal,
+};
+pub use feed::{Feed, FeedEntry, FeedError};
+pub use front_matter::{FrontMatter, FrontMatterField, FrontMatterFormat};
+pub use harvest_config::{HarvestConfig, HarvestConfigBuilder};
+pub use harvest_error::HarvestError;
+pub use harvested_document::{DocumentSizes, DocumentTimings, HarvestedDocument};
+pub use http_client::{FetchedPage, HttpClient};
 pub use http_config::HttpConfig;
 pub use http_config::HttpConfigBuilder;
 pub use http_regex::URL_REGEX;
+pub use image_handler::ImageMode;
+pub use language::{DetectedLanguage, LanguageSource};
+pub use link_handler::LinkMode;
 pub use markdown_harvester::MarkdownHarvester;
+pub use output_format::OutputFormat;
+pub use page_metadata::PageMetadata;
 pub use patterns::{
-    additional_cleanup, content_selectors, media_elements, text_selectors, unwanted_elements,
-    unwanted_text_patterns,
+    additional_cleanup, content_selectors, media_elements, navigation_terms, text_selectors,
+    unwanted_elements, unwanted_text_patterns,
 };
+#[cfg(feature = "pdf")]
+pub use pdf_converter::PdfError;
+pub use renderer::{DEFAULT_THIN_CONTENT_CHARS, NoopRenderer, RenderError, RenderPolicy, Renderer};
+pub use site_extractor::{SelectorExtractor, SiteExtractor, SiteExtractorRegistry};
+pub use sitemap::{SitemapConfig, SitemapConfigBuilder, SitemapUrl};
+pub use text_passthrough::BodyKind;
+pub use tokio_util::sync::CancellationToken;
 pub use user_agent::UserAgent;
@@ -0,0 +1,343 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use reqwest::Url;
+use scraper::{Html, Selector};
+
+/// Matches inline Markdown links and images: `[text](url)`, `[text](url "title")`
+/// and `![alt](src)`. One level of balanced parentheses is allowed in the URL so
+/// that addresses such as `Concurrency_(computer_science)` stay intact.
+static MARKDOWN_LINK_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(!?)\[([^\]]*)\]\(((?:[^()\s]|\([^()\s]*\))*)(?:\s+"[^"]*")?\)"#).unwrap()
+});
+
+/// How hyperlinks are rendered in the final Markdown.
+///
+/// Except for [`LinkMode::Strip`], link targets are resolved to absolute URLs
+/// against the page's `<base href>` or, when there is none, the page URL.
+/// In-page anchors (`#section`) and `javascript:` links are always reduced to their text.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::{ContentProcessor, ExtractionConfig, LinkMode};
+///
+/// let config = ExtractionConfig::builder().link_mode(LinkMode::Inline).build();
+/// let processor = ContentProcessor::with_config(config);
+///
+/// let html = r#"<html><body><p>Read the <a href="/guide">guide</a> first.</p></body></html>"#;
+/// let markdown = processor.html_to_markdown_with_url(html, "https://example.com/docs/");
+/// assert!(markdown.contains("[guide](https://example.com/guide)"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LinkMode {
+    /// Replace `[text](url)` with `text` and remove bare URLs (the original behaviour).
+    #[default]
+    Strip,
+    /// Keep links inline as `[text](absolute-url)`.
+    Inline,
+    /// Rewrite links as `[text][n]` and collect `[n]: absolute-url` definitions at the end.
+    Reference,
+    /// Keep only links pointing to another host; internal links are reduced to their text.
+    ExternalOnly,
+}
+
+/// Determines the URL relative links are resolved against.
+///
+/// A `<base href>` in the document wins (itself resolved against the page URL);
+/// otherwise the page URL is used. Returns `None` when neither yields an absolute URL.
+pub(crate) fn document_base_url(document: &Html, page_url: Option<&str>) -> Option<Url> {
+    let page_url = page_url.and_then(|url| Url::parse(url).ok());
+
+    let base_selector = Selector::parse("base[href]").unwrap();
+    let base_href = document
+        .select(&base_selector)
+        .next()
+        .and_then(|element| element.value().attr("href"));
+
+    let base_url = base_href.and_then(|href| match &page_url {
+        Some(page_url) => page_url.join(href.trim()).ok(),
+        None => Url::parse(href.trim()).ok(),
+    });
+
+    base_url.or(page_url)
+}
+
+/// Rewrites the Markdown links in `markdown` according to `mode`.
+///
+/// In [`LinkMode::Reference`] mode, link targets are numbered by their position
+/// in `references`, new targets being pushed to it, so that several fragments
+/// of a document can share the numbering; [`reference_definitions`] then gives
+/// the lines to append to the document. Images are left untouched.
+pub(crate) fn rewrite_links(
+    markdown: &str,
+    mode: LinkMode,
+    base_url: Option<&Url>,
+    references: &mut Vec<String>,
+) -> String {
+    let rewritten = MARKDOWN_LINK_REGEX.replace_all(markdown, |caps: &Captures| {
+        // Images are handled elsewhere
+        if !caps[1].is_empty() {
+            return caps[0].to_string();
+        }
+
+        let text = caps[2].trim();
+        if text.is_empty() {
+            return String::new();
+        }
+
+        let Some(target) = resolve_href(&caps[3], base_url) else {
+            return text.to_string();
+        };
+
+        match mode {
+            LinkMode::Strip => text.to_string(),
+            LinkMode::Inline => format!("[{}]({})", text, target),
+            LinkMode::ExternalOnly if is_external(&target, base_url) => {
+                format!("[{}]({})", text, target)
+            }
+            LinkMode::ExternalOnly => text.to_string(),
+            LinkMode::Reference => {
+                let index = match references.iter().position(|url| *url == target) {
+                    Some(position) => position + 1,
+                    None => {
+                        references.push(target);
+                        references.len()
+                    }
+                };
+                format!("[{}][{}]", text, index)
+            }
+        }
+    });
+
+    rewritten.to_string()
+}
+
+/// Returns the `[n]: url` definitions of the link targets collected by
+/// [`rewrite_links`].
+pub(crate) fn reference_definitions(references: &[String]) -> Vec<String> {
+    references
+        .iter()
+        .enumerate()
+        .map(|(index, url)| format!("[{}]: {}", index + 1, url))
+        .collect()
+}
+
+/// Collects the absolute `http(s)` targets of the Markdown links in `markdown`,
+/// in document order and without duplicates. Fragments are dropped, so links to
+/// sections of the same page count once. Images are not links.
+pub(crate) fn content_links(markdown: &str, base_url: Option<&Url>) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+
+    for caps in MARKDOWN_LINK_REGEX.captures_iter(markdown) {
+        if !caps[1].is_empty() {
+            continue;
+        }
+        let Some(mut target) = resolve_href(&caps[3], base_url)
+            .and_then(|target| Url::parse(&target).ok())
+            .filter(|target| matches!(target.scheme(), "http" | "https"))
+        else {
+            continue;
+        };
+        target.set_fragment(None);
+        let target = target.to_string();
+        if !links.contains(&target) {
+            links.push(target);
+        }
+    }
+
+    links
+}
+
+/// Resolves a link target against the base URL.
+///
+/// Returns `None` for targets that should not be kept as links: empty hrefs,
+/// in-page anchors and `javascript:` URLs. Relative targets are returned unchanged
+/// when there is no base URL to resolve them against.
+fn resolve_href(href: &str, base_url: Option<&Url>) -> Option<String> {
+    let href = href.trim();
+    let lower = href.to_lowercase();
+
+    if href.is_empty() || href.starts_with('#') || lower.starts_with("javascript:") {
+        return None;
+    }
+
+    if let Ok(absolute) = Url::parse(href) {
+        return Some(absolute.to_string());
+    }
+
+    match base_url {
+        Some(base_url) => base_url.join(href).ok().map(|url| url.to_string()),
+        None => Some(href.to_string()),
+    }
+}
+
+/// Returns `true` if `target` points to a different host than the base URL.
+///
+/// Targets that could not be resolved to an absolute URL are considered internal.
+fn is_external(target: &str, base_url: Option<&Url>) -> bool {
+    let Ok(target) = Url::parse(target) else {
+        return false;
+    };
+    let Some(target_host) = target.host_str() else {
+        return false;
+    };
+
+    match base_url.and_then(|base_url| base_url.host_str()) {
+        Some(base_host) => !target_host.eq_ignore_ascii_case(base_host),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rewrites a whole document, returning its reference definitions.
+    fn rewrite_links(
+        markdown: &str,
+        mode: LinkMode,
+        base_url: Option<&Url>,
+    ) -> (String, Vec<String>) {
+        let mut references = Vec::new();
+        let rewritten = super::rewrite_links(markdown, mode, base_url, &mut references);
+        (rewritten, reference_definitions(&references))
+    }
+
+    fn base() -> Url {
+        Url::parse("https://example.com/blog/post.html").unwrap()
+    }
+
+    #[test]
+    fn test_link_mode_default_is_strip() {
+        assert_eq!(LinkMode::default(), LinkMode::Strip);
+    }
+
+    #[test]
+    fn test_document_base_url_prefers_base_href() {
+        let document = Html::parse_document(
+            r#"<html><head><base href="/static/"></head><body></body></html>"#,
+        );
+        let base_url = document_base_url(&document, Some("https://example.com/a/b"));
+        assert_eq!(base_url.unwrap().as_str(), "https://example.com/static/");
+
+        let absolute_base = Html::parse_document(
+            r#"<html><head><base href="https://cdn.example.org/"></head><body></body></html>"#,
+        );
+        let base_url = document_base_url(&absolute_base, None);
+        assert_eq!(base_url.unwrap().as_str(), "https://cdn.example.org/");
+    }
+
+    #[test]
+    fn test_document_base_url_falls_back_to_page_url() {
+        let document = Html::parse_document("<html><body></body></html>");
+        let base_url = document_base_url(&document, Some("https://example.com/a/b"));
+        assert_eq!(base_url.unwrap().as_str(), "https://example.com/a/b");
+
+        assert!(document_base_url(&document, None).is_none());
+        assert!(document_base_url(&document, Some("not a url")).is_none());
+    }
+
+    #[test]
+    fn test_rewrite_links_strip() {
+        let (result, references) = rewrite_links(
+            "See [the docs](/docs) and [Rust](https://www.rust-lang.org).",
+            LinkMode::Strip,
+            Some(&base()),
+        );
+        assert_eq!(result, "See the docs and Rust.");
+        assert!(references.is_empty());
+    }
+
+    #[test]
+    fn test_rewrite_links_inline_resolves_relative() {
+        let (result, _) = rewrite_links(
+            r#"See [next](next.html "Next post"), [top](#top) and [run](javascript:void(0))."#,
+            LinkMode::Inline,
+            Some(&base()),
+        );
+        assert_eq!(
+            result,
+            "See [next](https://example.com/blog/next.html), top and run."
+        );
+    }
+
+    #[test]
+    fn test_rewrite_links_keeps_balanced_parentheses() {
+        let (result, _) = rewrite_links(
+            "[Concurrency](https://en.wikipedia.org/wiki/Concurrency_(computer_science))",
+            LinkMode::Inline,
+            None,
+        );
+        assert_eq!(
+            result,
+            "[Concurrency](https://en.wikipedia.org/wiki/Concurrency_(computer_science))"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_links_reference_deduplicates() {
+        let (result, references) = rewrite_links(
+            "[A](/a) then [B](https://other.org/b) and [A again](/a)",
+            LinkMode::Reference,
+            Some(&base()),
+        );
+        assert_eq!(result, "[A][1] then [B][2] and [A again][1]");
+        assert_eq!(
+            references,
+            vec![
+                "[1]: https://example.com/a".to_string(),
+                "[2]: https://other.org/b".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rewrite_links_external_only() {
+        let (result, _) = rewrite_links(
+            "[Home](/) [Archive](https://example.com/archive) [Crates](https://crates.io/)",
+            LinkMode::ExternalOnly,
+            Some(&base()),
+        );
+        assert_eq!(result, "Home Archive [Crates](https://crates.io/)");
+    }
+
+    #[test]
+    fn test_rewrite_links_without_base_url() {
+        let (inline, _) = rewrite_links("[Guide](/guide)", LinkMode::Inline, None);
+        assert_eq!(inline, "[Guide](/guide)");
+
+        let (external, _) = rewrite_links(
+            "[Guide](/guide) [Crates](https://crates.io/)",
+            LinkMode::ExternalOnly,
+            None,
+        );
+        assert_eq!(external, "Guide [Crates](https://crates.io/)");
+    }
+
+    #[test]
+    fn test_content_links_resolves_and_dedupes() {
+        let markdown = "See [part one](part-1.html), [its notes](part-1.html#notes), \
+            [mail](mailto:editor@example.com), [top](#top) and ![chart](/chart.png).\n\
+            Elsewhere: [Rust](https://www.rust-lang.org/).";
+
+        let links = content_links(markdown, Some(&base()));
+        assert_eq!(
+            links,
+            [
+                "https://example.com/blog/part-1.html",
+                "https://www.rust-lang.org/"
+            ]
+        );
+        assert!(content_links("[relative](/guide)", None).is_empty());
+    }
+
+    #[test]
+    fn test_rewrite_links_leaves_images_and_drops_empty_links() {
+        let (result, _) = rewrite_links(
+            "![Logo](/logo.png) [](/icon-link) text",
+            LinkMode::Inline,
+            Some(&base()),
+        );
+        assert_eq!(result, "![Logo](/logo.png)  text");
+    }
+}
@@ -1,6 +1,6 @@
+use futures::StreamExt;
 use markdown_harvest::{MarkdownHarvester, HttpConfig};
 use std::io::{self, Write};
-use std::sync::{Arc, Mutex};
 
 fn main() {
     println!("🦀 Markdown Harvest - Interactive CLI");
@@ -149,7 +149,7 @@ fn run_async_example_blocking() -> Result<(), Box<dyn std::error::Error>> {
 
 // Option 2: Asynchronous Processing
 async fn run_async_example() -> Result<(), Box<dyn std::error::Error>> {
-    println!("This example demonstrates the asynchronous get_hyperlinks_content_async function.");
+    println!("This example demonstrates the asynchronous harvest_stream function.");
     println!("URLs will be processed in parallel, with results streaming as they complete.");
     
     let text = get_user_input();
@@ -165,54 +165,51 @@ async fn run_async_example() -> Result<(), Box<dyn std::error::Error>> {
     println!();
 
     let start_time = std::time::Instant::now();
-    let processed_count = Arc::new(Mutex::new(0));
-    let processed_count_clone = processed_count.clone();
-
-    let callback = move |url: Option<String>, content: Option<String>| {
-        let processed_count = processed_count_clone.clone();
-        async move {
-            match (url, content) {
-                (Some(url), Some(content)) => {
-                    let mut count = processed_count.lock().unwrap();
-                    *count += 1;
-                    let current_count = *count;
-                    drop(count);
-
-                    println!("✅ Result #{}: {}", current_count, url);
-
-                    let preview = if content.chars().count() > 200 {
-                        let truncated: String = content.chars().take(150).collect();
-                        format!(
-                            "{}...\n\n📏 [Content truncated - {} total characters]",
-                            truncated,
-                            content.chars().count()
-                        )
-                    } else {
-                        content.clone()
-                    };
-
-                    println!("📝 Markdown content:");
-                    println!("{}", preview);
-                    println!("{}", "─".repeat(60));
-                    println!();
-                }
-                (None, None) => {
-                    println!("ℹ️  No URLs found in the provided text");
-                    println!("💡 Try entering text with URLs like: https://example.com");
-                    println!();
-                }
-                _ => unreachable!(),
+    let mut processed_count = 0;
+    let mut documents = MarkdownHarvester::harvest_stream(text, http_config.into());
+
+    while let Some(result) = documents.next().await {
+        processed_count += 1;
+        let document = match result {
+            Ok(document) => document,
+            Err(error) => {
+                println!("❌ Result #{}: {}", processed_count, error);
+                println!("{}", "─".repeat(60));
+                println!();
+                continue;
             }
-        }
-    };
+        };
+
+        println!("✅ Result #{}: {}", processed_count, document.final_url);
+
+        let content = document.content;
+        let preview = if content.chars().count() > 200 {
+            let truncated: String = content.chars().take(150).collect();
+            format!(
+                "{}...\n\n📏 [Content truncated - {} total characters]",
+                truncated,
+                content.chars().count()
+            )
+        } else {
+            content
+        };
+
+        println!("📝 Markdown content:");
+        println!("{}", preview);
+        println!("{}", "─".repeat(60));
+        println!();
+    }
 
-    MarkdownHarvester::get_hyperlinks_content_async(text, http_config, callback).await?;
+    if processed_count == 0 {
+        println!("ℹ️  No URLs found in the provided text");
+        println!("💡 Try entering text with URLs like: https://example.com");
+        println!();
+    }
 
     let duration = start_time.elapsed();
-    let final_count = *processed_count.lock().unwrap();
 
     println!("⏱️  Asynchronous processing completed in {:.2}ms", duration.as_millis());
-    println!("📊 Total URLs processed: {}", final_count);
+    println!("📊 Total URLs processed: {}", processed_count);
     println!("✅ Asynchronous processing example completed!");
     println!();
     println!("💡 Key characteristics of asynchronous processing:");
@@ -273,7 +270,7 @@ fn run_async_chunks_example_blocking() -> Result<(), Box<dyn std::error::Error>>
 // Option 4: Asynchronous Chunking
 #[cfg(feature = "chunks")]
 async fn run_async_chunks_example() -> Result<(), Box<dyn std::error::Error>> {
-    println!("This example demonstrates the asynchronous get_hyperlinks_content_as_chunks_async function.");
+    println!("This example demonstrates the asynchronous harvest_chunk_stream function.");
     println!("URLs will be processed in parallel and content split into semantic chunks for RAG systems.");
     
     let text = get_user_input();
@@ -296,69 +293,57 @@ async fn run_async_chunks_example() -> Result<(), Box<dyn std::error::Error>> {
     println!();
 
     let start_time = std::time::Instant::now();
-    let processed_count = Arc::new(Mutex::new(0));
-    let total_chunks = Arc::new(Mutex::new(0));
-    let processed_count_clone = processed_count.clone();
-    let total_chunks_clone = total_chunks.clone();
-
-    let callback = move |url: Option<String>, chunks: Option<Vec<String>>| {
-        let processed_count = processed_count_clone.clone();
-        let total_chunks = total_chunks_clone.clone();
-        async move {
-            match (url, chunks) {
-                (Some(url), Some(chunks)) => {
-                    let mut count = processed_count.lock().unwrap();
-                    *count += 1;
-                    let current_count = *count;
-                    drop(count);
-
-                    let mut total = total_chunks.lock().unwrap();
-                    *total += chunks.len();
-                    let current_total = *total;
-                    drop(total);
-
-                    println!("✅ Result #{}: {}", current_count, url);
-                    println!("📦 Chunks created: {} (Total so far: {})", chunks.len(), current_total);
-                    println!();
-
-                    for (chunk_idx, chunk) in chunks.iter().enumerate() {
-                        println!("   📝 Chunk #{}: {} characters", chunk_idx + 1, chunk.len());
-                        
-                        let preview = if chunk.chars().count() > 120 {
-                            let truncated: String = chunk.chars().take(80).collect();
-                            format!("{}...", truncated)
-                        } else {
-                            chunk.clone()
-                        };
-
-                        println!("   Content: {}", preview);
-                        println!();
-                    }
-                    
-                    println!("{}", "─".repeat(80));
-                    println!();
-                }
-                (None, None) => {
-                    println!("ℹ️  No URLs found in the provided text");
-                    println!("💡 Try entering text with URLs like: https://example.com");
-                    println!();
-                }
-                _ => unreachable!(),
+    let mut final_count = 0;
+    let mut final_total_chunks = 0;
+    let mut chunked = MarkdownHarvester::harvest_chunk_stream(
+        text,
+        http_config.into(),
+        chunk_size,
+        chunk_overlap,
+    );
+
+    while let Some(result) = chunked.next().await {
+        final_count += 1;
+        let (document, chunks) = match result {
+            Ok(chunked) => chunked,
+            Err(error) => {
+                println!("❌ Result #{}: {}", final_count, error);
+                println!("{}", "─".repeat(80));
+                println!();
+                continue;
             }
+        };
+        final_total_chunks += chunks.len();
+
+        println!("✅ Result #{}: {}", final_count, document.final_url);
+        println!("📦 Chunks created: {} (Total so far: {})", chunks.len(), final_total_chunks);
+        println!();
+
+        for (chunk_idx, chunk) in chunks.iter().enumerate() {
+            println!("   📝 Chunk #{}: {} characters", chunk_idx + 1, chunk.len());
+            
+            let preview = if chunk.chars().count() > 120 {
+                let truncated: String = chunk.chars().take(80).collect();
+                format!("{}...", truncated)
+            } else {
+                chunk.clone()
+            };
+
+            println!("   Content: {}", preview);
+            println!();
         }
-    };
+        
+        println!("{}", "─".repeat(80));
+        println!();
+    }
 
-    MarkdownHarvester::get_hyperlinks_content_as_chunks_async(
-        text, 
-        http_config, 
-        chunk_size, 
-        chunk_overlap,
-        callback
-    ).await?;
+    if final_count == 0 {
+        println!("ℹ️  No URLs found in the provided text");
+        println!("💡 Try entering text with URLs like: https://example.com");
+        println!();
+    }
 
     let duration = start_time.elapsed();
-    let final_count = *processed_count.lock().unwrap();
-    let final_total_chunks = *total_chunks.lock().unwrap();
 
     println!("⏱️  Asynchronous chunking completed in {:.2}ms", duration.as_millis());
     println!("📊 Total URLs processed: {}", final_count);
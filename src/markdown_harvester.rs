@@ -1,10 +1,43 @@
 use crate::{
-    content_processor::ContentProcessor, http_client::HttpClient, http_config::HttpConfig,
+    content_processor::ContentProcessor,
+    crawler::{CrawlConfig, Crawler},
+    feed::{align_articles, entry_documents, entry_links, parse_feed_page},
+    harvest_config::HarvestConfig,
+    harvest_error::HarvestError,
+    harvested_document::HarvestedDocument,
+    http_client::{
+        FetchedPage, HttpClient, browser_headers, build_client, build_client_async,
+        fetch_bytes_async, fetch_page, fetch_page_async,
+    },
+    http_config::HttpConfig,
+    pagination::stitch_pages,
+    renderer::harvest_page,
+    sitemap::{SitemapConfig, discover_sitemap_urls},
+    user_agent::UserAgent,
 };
-use std::future::Future;
+use futures::{
+    FutureExt, StreamExt,
+    future::{self, BoxFuture, Shared},
+    stream::{self, BoxStream},
+};
+use reqwest::{Client, blocking, header::HeaderMap};
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
+use tokio::{
+    runtime::Handle,
+    time::{Instant, sleep_until},
+};
+use tokio_util::sync::CancellationToken;
 
 #[cfg(feature = "chunks")]
-use text_splitter::{MarkdownSplitter, ChunkConfig};
+use text_splitter::{Characters, MarkdownSplitter, ChunkConfig};
 
 /// Main struct for extracting and converting web content from URLs to Markdown.
 ///
@@ -64,25 +97,264 @@ impl MarkdownHarvester {
     /// let results = MarkdownHarvester::get_hyperlinks_content(text.to_string(), HttpConfig::default());
     /// ```
     pub fn get_hyperlinks_content(text: String, http_config: HttpConfig) -> Vec<(String, String)> {
-        let http_client = HttpClient::new();
-        let content_processor = ContentProcessor::new();
+        let urls = HttpClient::new().extract_urls(&text);
+        let client = build_client(http_config);
+        let headers = browser_headers(UserAgent::random());
+        let pages = urls
+            .iter()
+            .map(|url| fetch_page(&client, url, headers.clone()))
+            .collect();
+        content_pairs(pages, &client, &headers, &http_config.into())
+    }
 
-        // Step 1: Extract URLs and fetch HTML content
-        let html_results = http_client.fetch_content_from_text(text.as_str(), http_config);
+    /// Extracts URLs from the given text and harvests each of them as a [`HarvestedDocument`].
+    ///
+    /// Besides the Markdown, each document carries the final URL after redirects, the
+    /// HTTP status and content type, the plain text, page metadata, language, timings,
+    /// byte sizes and a report of how the content was extracted.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Input text that may contain URLs
+    /// * `config` - HTTP settings, extraction rules and site extractors; an
+    ///   [`HttpConfig`] converts into a [`HarvestConfig`] with the default rules
+    ///
+    /// # Returns
+    ///
+    /// One result per URL found, in the order they appear in the text. URLs that could
+    /// not be fetched are reported as [`HarvestError`]s. An RSS or Atom feed yields one
+    /// result per entry instead, see [`HarvestConfig::fetch_feed_articles`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use markdown_harvest::{HarvestConfig, HttpConfig, MarkdownHarvester};
+    ///
+    /// let text = "Visit https://example.com for more info";
+    /// let config = HarvestConfig::from(HttpConfig::builder().timeout(5000).build());
+    ///
+    /// for result in MarkdownHarvester::get_hyperlinks_documents(text.to_string(), config) {
+    ///     match result {
+    ///         Ok(document) => println!("{} ({} bytes)", document.final_url, document.sizes.markdown_bytes),
+    ///         Err(error) => eprintln!("{}", error),
+    ///     }
+    /// }
+    /// ```
+    pub fn get_hyperlinks_documents(
+        text: String,
+        config: HarvestConfig,
+    ) -> Vec<Result<HarvestedDocument, HarvestError>> {
+        let urls = HttpClient::new().extract_urls(&text);
+        let client = build_client(config.http_config());
+        let headers = browser_headers(UserAgent::random());
+        let pages = urls
+            .iter()
+            .map(|url| fetch_page(&client, url, headers.clone()))
+            .collect();
+        harvest_pages(pages, &client, &headers, &config)
+    }
 
-        if html_results.is_empty() {
-            return Vec::new();
-        }
+    /// Asynchronous version of [`get_hyperlinks_documents`](Self::get_hyperlinks_documents).
+    ///
+    /// All URLs are fetched concurrently, at most [`HttpConfig::max_concurrency`]
+    /// at a time; results keep the order of the URLs in the text.
+    ///
+    /// When the [`HarvestConfig::deadline`] passes or its
+    /// [`HarvestConfig::cancellation_token`] is cancelled, the requests still
+    /// in flight are dropped and the documents harvested so far are returned,
+    /// each unfinished URL giving a [`HarvestError::Cancelled`] in its place.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use markdown_harvest::{HarvestConfig, MarkdownHarvester};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let text = "Compare https://example.com and https://httpbin.org/html";
+    ///     let results =
+    ///         MarkdownHarvester::get_hyperlinks_documents_async(text.to_string(), HarvestConfig::default()).await;
+    ///
+    ///     for document in results.into_iter().flatten() {
+    ///         println!("{}: {:?}", document.final_url, document.metadata.title);
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_hyperlinks_documents_async(
+        text: String,
+        config: HarvestConfig,
+    ) -> Vec<Result<HarvestedDocument, HarvestError>> {
+        let urls = HttpClient::new().extract_urls(&text);
+        let stop = batch_stop(&config);
+        harvest_urls_in_order(urls, config, stop).await
+    }
 
-        // Step 2: Process HTML content to Markdown
-        let mut markdown_results = Vec::new();
+    /// Harvests the URLs found in the text as a stream of results, yielded as
+    /// each page completes.
+    ///
+    /// Unlike [`get_hyperlinks_documents_async`](Self::get_hyperlinks_documents_async),
+    /// results come in completion order rather than the order of the URLs, so
+    /// the first document can be used while slower pages are still loading.
+    /// Pages are fetched concurrently, at most [`HttpConfig::max_concurrency`]
+    /// at a time, as the stream is polled. Text without URLs gives an empty
+    /// stream, and dropping the stream cancels the pages still in flight.
+    ///
+    /// The stream also ends, dropping the pages in flight, once the
+    /// [`HarvestConfig::deadline`] passes or its
+    /// [`HarvestConfig::cancellation_token`] is cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use markdown_harvest::{HarvestConfig, MarkdownHarvester};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let text = "Compare https://example.com and https://httpbin.org/html";
+    ///     let mut documents = MarkdownHarvester::harvest_stream(text.to_string(), HarvestConfig::default());
+    ///
+    ///     while let Some(result) = documents.next().await {
+    ///         match result {
+    ///             Ok(document) => println!("{}: {:?}", document.final_url, document.metadata.title),
+    ///             Err(error) => eprintln!("{}", error),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn harvest_stream(
+        text: String,
+        config: HarvestConfig,
+    ) -> BoxStream<'static, Result<HarvestedDocument, HarvestError>> {
+        let urls = HttpClient::new().extract_urls(&text);
+        let stop = batch_stop(&config);
 
-        for (url, html_content) in html_results {
-            let markdown_content = content_processor.html_to_markdown(&html_content);
-            markdown_results.push((url, markdown_content));
+        harvest_urls(urls, config, stop)
+            .flat_map(|(_, documents)| stream::iter(documents))
+            .boxed()
+    }
+
+    /// Harvests every page listed in the sitemaps of a site, through the same
+    /// pipeline as [`get_hyperlinks_documents`](Self::get_hyperlinks_documents).
+    ///
+    /// `url` may be a sitemap (plain, gzipped or a sitemap index), a robots.txt
+    /// file or any page of the site, whose sitemaps are then discovered from its
+    /// robots.txt or `/sitemap.xml`. Sitemap indexes are followed, and the URLs
+    /// are filtered by path and `lastmod` with `sitemap_config`.
+    ///
+    /// Pages are fetched on up to [`HttpConfig::max_concurrency`] threads, one
+    /// at a time when it is not set, and each page is harvested as soon as it
+    /// is fetched, so only the pages in flight are held in memory. For a
+    /// deadline or cancellation, use
+    /// [`get_sitemap_documents_async`](Self::get_sitemap_documents_async).
+    ///
+    /// # Returns
+    ///
+    /// One result per URL, in sitemap order, or a single [`HarvestError`] when
+    /// no sitemap could be found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use markdown_harvest::{HarvestConfig, MarkdownHarvester, SitemapConfig};
+    ///
+    /// let sitemap_config = SitemapConfig::builder().include_path(r"^/docs/").max_urls(50).build();
+    /// let documents = MarkdownHarvester::get_sitemap_documents(
+    ///     "https://docs.example.com/",
+    ///     HarvestConfig::default(),
+    ///     &sitemap_config,
+    /// );
+    ///
+    /// for document in documents.into_iter().flatten() {
+    ///     println!("{}: {} bytes", document.final_url, document.sizes.markdown_bytes);
+    /// }
+    /// ```
+    pub fn get_sitemap_documents(
+        url: &str,
+        config: HarvestConfig,
+        sitemap_config: &SitemapConfig,
+    ) -> Vec<Result<HarvestedDocument, HarvestError>> {
+        let http_client = HttpClient::new();
+        let urls = match http_client.fetch_sitemap_urls(url, config.http_config(), sitemap_config) {
+            Ok(urls) => urls.into_iter().map(|url| url.loc).collect::<Vec<_>>(),
+            Err(error) => return vec![Err(error)],
+        };
+        harvest_urls_blocking(&urls, &config)
+    }
+
+    /// Asynchronous version of [`get_sitemap_documents`](Self::get_sitemap_documents).
+    ///
+    /// Pages are fetched concurrently, at most
+    /// [`HttpConfig::max_concurrency`] at a time; results keep the sitemap order.
+    /// The [`HarvestConfig::deadline`] and [`HarvestConfig::cancellation_token`]
+    /// apply as in
+    /// [`get_hyperlinks_documents_async`](Self::get_hyperlinks_documents_async),
+    /// a batch stopped during discovery giving a single
+    /// [`HarvestError::Cancelled`] for `url`.
+    pub async fn get_sitemap_documents_async(
+        url: &str,
+        config: HarvestConfig,
+        sitemap_config: &SitemapConfig,
+    ) -> Vec<Result<HarvestedDocument, HarvestError>> {
+        let stop = batch_stop(&config);
+        let sitemap_url = url.to_string();
+        let sitemap_config = sitemap_config.clone();
+        let client = build_client_async(config.http_config());
+        let headers = browser_headers(UserAgent::random());
+        let runtime = Handle::current();
+        let signal = stop.signal.clone();
+        // Sitemaps are parsed on a blocking thread but fetched by the async
+        // client, so the fetch in flight is dropped when the batch stops
+        let urls = run_blocking(move || {
+            discover_sitemap_urls(&sitemap_url, &sitemap_config, |url| {
+                let bytes = fetch_bytes_async(client.clone(), url.to_string(), headers.clone());
+                runtime.block_on(until_stopped(bytes, signal.clone(), url))
+            })
+        })
+        .await;
+        if let Some(reason) = stop.signal.peek() {
+            return vec![Err(HarvestError::Cancelled {
+                url: url.to_string(),
+                message: reason.to_string(),
+            })];
         }
+        let urls = match urls {
+            Ok(urls) => urls.into_iter().map(|url| url.loc).collect::<Vec<_>>(),
+            Err(error) => return vec![Err(error)],
+        };
+        harvest_urls_in_order(urls, config, stop).await
+    }
 
-        markdown_results
+    /// Crawls from the seed URLs, following the links in the extracted content
+    /// of each page within the limits of `crawl_config`.
+    ///
+    /// The returned [`Crawler`] is an iterator: pages are fetched as it is
+    /// advanced, so each document can be used as soon as it is harvested, and
+    /// the crawl stops when the iterator is dropped. URLs are deduplicated after
+    /// normalization, and requests to the same host are spaced by
+    /// [`CrawlConfig::host_delay`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use markdown_harvest::{CrawlConfig, CrawlScope, HarvestConfig, MarkdownHarvester};
+    ///
+    /// let crawl_config = CrawlConfig::builder()
+    ///     .max_depth(2)
+    ///     .max_pages(30)
+    ///     .scope(CrawlScope::SamePrefix)
+    ///     .build();
+    /// let seeds = ["https://docs.example.com/guide/".to_string()];
+    ///
+    /// for result in MarkdownHarvester::crawl(&seeds, HarvestConfig::default(), crawl_config) {
+    ///     match result {
+    ///         Ok(document) => println!("{}: {} links", document.final_url, document.links.len()),
+    ///         Err(error) => eprintln!("{}", error),
+    ///     }
+    /// }
+    /// ```
+    pub fn crawl(seeds: &[String], config: HarvestConfig, crawl_config: CrawlConfig) -> Crawler {
+        Crawler::new(seeds, config, crawl_config)
     }
 
     /// Extracts URLs from text and processes their content asynchronously with custom callback handling.
@@ -92,6 +364,9 @@ impl MarkdownHarvester {
     /// concurrently and streams results through a user-provided callback, making it ideal
     /// for high-throughput scenarios and real-time processing applications.
     ///
+    /// [`harvest_stream`](Self::harvest_stream) yields the same results as a `Stream`,
+    /// which composes with `futures` combinators and reports failed URLs as errors.
+    ///
     /// # Performance
     ///
     /// - Processes URLs in parallel instead of sequentially
@@ -242,6 +517,14 @@ impl MarkdownHarvester {
     /// - **Throughput**: Higher throughput potential when processing multiple URLs
     /// - **Scalability**: Performance benefits scale with the number of concurrent URLs
     ///
+    /// # Cancellation
+    ///
+    /// This method takes an [`HttpConfig`], so [`HarvestConfig::deadline`] and
+    /// [`HarvestConfig::cancellation_token`] do not apply; dropping the returned
+    /// future stops it. For a batch with a deadline, use
+    /// [`get_hyperlinks_documents_async`](Self::get_hyperlinks_documents_async)
+    /// or [`harvest_stream`](Self::harvest_stream).
+    ///
     /// # When to Use
     ///
     /// Choose this async version when:
@@ -285,7 +568,8 @@ impl MarkdownHarvester {
                         if let (Some(url), Some(content)) = (url, content) {
                             // Create a new ContentProcessor for each URL processing
                             let content_processor = ContentProcessor::new();
-                            let markdown_content = content_processor.html_to_markdown(&content);
+                            let markdown_content =
+                                content_processor.html_to_markdown_with_url(&content, &url);
                             future(Some(url), Some(markdown_content)).await;
                         }
                     }
@@ -382,31 +666,13 @@ impl MarkdownHarvester {
             return Vec::new();
         }
 
-        // Validate overlap parameter
-        if let Some(overlap) = chunk_overlap {
-            if overlap >= chunk_size {
-                // Return error as empty result for now - in a real implementation,
-                // we would return a proper Result type
-                eprintln!("Warning: chunk_overlap ({}) must be smaller than chunk_size ({})", overlap, chunk_size);
+        let splitter = match markdown_splitter(chunk_size, chunk_overlap) {
+            Ok(splitter) => splitter,
+            Err(message) => {
+                eprintln!("Warning: {}", message);
                 return Vec::new();
             }
-        }
-
-        // Initialize Markdown splitter with ChunkConfig including overlap
-        let config = match chunk_overlap {
-            Some(overlap) => {
-                match ChunkConfig::new(chunk_size).with_overlap(overlap) {
-                    Ok(config) => config,
-                    Err(_) => {
-                        // This should not happen due to our validation above, but handle gracefully
-                        eprintln!("Failed to create ChunkConfig with overlap");
-                        return Vec::new();
-                    }
-                }
-            },
-            None => ChunkConfig::new(chunk_size),
         };
-        let splitter = MarkdownSplitter::new(config);
         
         let mut chunked_results = Vec::new();
         
@@ -430,6 +696,8 @@ impl MarkdownHarvester {
     /// Unlike the synchronous version, this method processes URLs concurrently and streams 
     /// chunked results through a user-provided callback.
     ///
+    /// [`harvest_chunk_stream`](Self::harvest_chunk_stream) yields the chunks as a `Stream` instead.
+    ///
     /// **Feature Required**: This method is only available when the `chunks` feature is enabled.
     ///
     /// # Performance
@@ -565,6 +833,13 @@ impl MarkdownHarvester {
     /// - **Overlap Strategy**: Consider document structure instead of arbitrary overlap
     /// - **Quality Filtering**: Filter chunks by semantic completeness and meaningful content
     ///
+    /// # Cancellation
+    ///
+    /// Like [`get_hyperlinks_content_async`](Self::get_hyperlinks_content_async),
+    /// this method has no deadline or cancellation token; drop the returned
+    /// future to stop it, or use [`harvest_chunk_stream`](Self::harvest_chunk_stream),
+    /// which honours [`HarvestConfig::deadline`].
+    ///
     /// # See Also
     ///
     /// - [`get_hyperlinks_content_as_chunks`](Self::get_hyperlinks_content_as_chunks) - Synchronous version
@@ -592,29 +867,13 @@ impl MarkdownHarvester {
                 async move {
                     match (url, content) {
                         (Some(url), Some(content)) => {
-                            // Validate overlap parameter
-                            if let Some(overlap) = chunk_overlap {
-                                if overlap >= chunk_size {
-                                    eprintln!("Warning: chunk_overlap ({}) must be smaller than chunk_size ({})", overlap, chunk_size);
+                            let splitter = match markdown_splitter(chunk_size, chunk_overlap) {
+                                Ok(splitter) => splitter,
+                                Err(message) => {
+                                    eprintln!("Warning: {}", message);
                                     return;
                                 }
-                            }
-
-                            // Initialize Markdown splitter with ChunkConfig including overlap
-                            let config = match chunk_overlap {
-                                Some(overlap) => {
-                                    match ChunkConfig::new(chunk_size).with_overlap(overlap) {
-                                        Ok(config) => config,
-                                        Err(_) => {
-                                            // This should not happen due to our validation above, but handle gracefully
-                                            eprintln!("Failed to create ChunkConfig with overlap");
-                                            return;
-                                        }
-                                    }
-                                },
-                                None => ChunkConfig::new(chunk_size),
                             };
-                            let splitter = MarkdownSplitter::new(config);
                             
                             // Split content into semantic Markdown chunks
                             let chunks: Vec<String> = splitter
@@ -637,12 +896,490 @@ impl MarkdownHarvester {
             },
         ).await
     }
+
+    /// Harvests the URLs found in the text as a stream of documents with their
+    /// Markdown split into semantic chunks, yielded as each page completes.
+    ///
+    /// The stream counterpart of
+    /// [`get_hyperlinks_content_as_chunks_async`](Self::get_hyperlinks_content_as_chunks_async),
+    /// built on [`harvest_stream`](Self::harvest_stream): each item holds the
+    /// whole [`HarvestedDocument`], so chunks can be stored with its URL and
+    /// metadata. An overlap that is not smaller than `chunk_size` gives a
+    /// stream of a single [`HarvestError::InvalidConfig`].
+    ///
+    /// **Feature Required**: This method is only available when the `chunks` feature is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use markdown_harvest::{HarvestConfig, MarkdownHarvester};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let text = "Index https://example.com/guide";
+    ///     let mut chunked =
+    ///         MarkdownHarvester::harvest_chunk_stream(text.to_string(), HarvestConfig::default(), 1000, Some(100));
+    ///
+    ///     while let Some(Ok((document, chunks))) = chunked.next().await {
+    ///         println!("{}: {} chunks", document.final_url, chunks.len());
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "chunks")]
+    pub fn harvest_chunk_stream(
+        text: String,
+        config: HarvestConfig,
+        chunk_size: usize,
+        chunk_overlap: Option<usize>,
+    ) -> BoxStream<'static, Result<(HarvestedDocument, Vec<String>), HarvestError>> {
+        let splitter = match markdown_splitter(chunk_size, chunk_overlap) {
+            Ok(splitter) => splitter,
+            Err(message) => {
+                return stream::once(future::ready(Err(HarvestError::InvalidConfig { message })))
+                    .boxed();
+            }
+        };
+
+        Self::harvest_stream(text, config)
+            .map(move |document| {
+                document.map(|document| {
+                    let chunks = splitter
+                        .chunks(&document.markdown)
+                        .map(|chunk| chunk.to_string())
+                        .collect();
+                    (document, chunks)
+                })
+            })
+            .boxed()
+    }
+}
+
+/// Builds the Markdown splitter for the chunking methods, failing with a
+/// message when the overlap is not smaller than the chunk size.
+#[cfg(feature = "chunks")]
+fn markdown_splitter(
+    chunk_size: usize,
+    chunk_overlap: Option<usize>,
+) -> Result<MarkdownSplitter<Characters>, String> {
+    let invalid_overlap = |overlap| {
+        format!(
+            "chunk_overlap ({}) must be smaller than chunk_size ({})",
+            overlap, chunk_size
+        )
+    };
+    let config = match chunk_overlap {
+        Some(overlap) if overlap >= chunk_size => return Err(invalid_overlap(overlap)),
+        Some(overlap) => ChunkConfig::new(chunk_size)
+            .with_overlap(overlap)
+            .map_err(|_| invalid_overlap(overlap))?,
+        None => ChunkConfig::new(chunk_size),
+    };
+    Ok(MarkdownSplitter::new(config))
+}
+
+/// Harvests fetched pages into one `(url, markdown)` pair per URL, joining the
+/// entries of a feed. Failures are printed and skipped.
+fn content_pairs(
+    pages: Vec<Result<FetchedPage, HarvestError>>,
+    client: &blocking::Client,
+    headers: &HeaderMap,
+    config: &HarvestConfig,
+) -> Vec<(String, String)> {
+    pages
+        .into_iter()
+        .filter_map(|page| {
+            let url = match &page {
+                Ok(page) => page.requested_url.clone(),
+                Err(error) => {
+                    eprintln!("{}", error);
+                    return None;
+                }
+            };
+            let mut markdown = Vec::new();
+            for result in harvest_pages(vec![page], client, headers, config) {
+                match result {
+                    Ok(document) => markdown.push(document.markdown),
+                    Err(error) => eprintln!("{}", error),
+                }
+            }
+            (!markdown.is_empty()).then(|| (url, markdown.join("\n\n")))
+        })
+        .collect()
+}
+
+/// Harvests fetched pages, expanding feeds into one result per entry. Feed
+/// articles and the following pages of paginated articles are fetched with
+/// `client` and `headers`, keeping the session the pages were fetched in.
+pub(crate) fn harvest_pages(
+    pages: Vec<Result<FetchedPage, HarvestError>>,
+    client: &blocking::Client,
+    headers: &HeaderMap,
+    config: &HarvestConfig,
+) -> Vec<Result<HarvestedDocument, HarvestError>> {
+    let content_processor = config.content_processor();
+    let cancel = CancellationToken::new();
+
+    pages
+        .into_iter()
+        .flat_map(|page| match page {
+            Ok(page) if page.is_feed() => {
+                harvest_feed(page, client, headers, &content_processor, config)
+            }
+            page => vec![page.and_then(|page| {
+                harvest_article(page, &content_processor, config, &cancel, None, |url| {
+                    fetch_page(client, url, headers.clone())
+                })
+            })],
+        })
+        .collect()
+}
+
+/// Fetches and harvests the URLs on up to [`HttpConfig::max_concurrency`]
+/// threads, sequentially when it is not set, harvesting each page as soon as
+/// it is fetched. Results keep the order of the URLs.
+fn harvest_urls_blocking(
+    urls: &[String],
+    config: &HarvestConfig,
+) -> Vec<Result<HarvestedDocument, HarvestError>> {
+    let workers = config
+        .http_config()
+        .max_concurrency()
+        .unwrap_or(1)
+        .clamp(1, urls.len().max(1));
+    let client = build_client(config.http_config());
+    let headers = browser_headers(UserAgent::random());
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (client, headers, next) = (&client, &headers, &next);
+            scope.spawn(move || {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(url) = urls.get(index) else {
+                        break;
+                    };
+                    let page = fetch_page(client, url, headers.clone());
+                    let documents = harvest_pages(vec![page], client, headers, config);
+                    let _ = sender.send((index, documents));
+                }
+            });
+        }
+    });
+    drop(sender);
+
+    let mut harvested: Vec<_> = receiver.into_iter().collect();
+    harvested.sort_by_key(|(index, _)| *index);
+    harvested
+        .into_iter()
+        .flat_map(|(_, documents)| documents)
+        .collect()
+}
+
+/// Signal that an async batch must stop, resolving to the reason.
+type StopSignal = Shared<BoxFuture<'static, &'static str>>;
+
+/// Stops an async batch and the blocking work it started.
+#[derive(Clone)]
+struct BatchStop {
+    /// Resolves with the reason the batch stopped.
+    signal: StopSignal,
+    /// Cancelled when `signal` resolves, for the rendering and pagination of
+    /// the pages being harvested to check between steps.
+    token: CancellationToken,
+}
+
+/// Stops the batch once the [`HarvestConfig::deadline`], counted from now,
+/// passes or the [`HarvestConfig::cancellation_token`] is cancelled; never
+/// when neither is set.
+fn batch_stop(config: &HarvestConfig) -> BatchStop {
+    let deadline = config
+        .deadline()
+        .map(|deadline| Instant::now() + Duration::from_millis(deadline));
+    let token = config.cancellation_token().cloned();
+    // A child of the caller's token is cancelled along with it, even before
+    // the signal is polled
+    let stopped = token
+        .as_ref()
+        .map(CancellationToken::child_token)
+        .unwrap_or_default();
+    let batch_token = stopped.clone();
+
+    let signal = async move {
+        let expired = async {
+            match deadline {
+                Some(deadline) => sleep_until(deadline).await,
+                None => future::pending().await,
+            }
+        };
+        let cancelled = async {
+            match &token {
+                Some(token) => token.cancelled().await,
+                None => future::pending().await,
+            }
+        };
+        let reason = tokio::select! {
+            _ = expired => "batch deadline exceeded",
+            _ = cancelled => "harvest cancelled",
+        };
+        stopped.cancel();
+        reason
+    }
+    .boxed()
+    .shared();
+
+    BatchStop {
+        signal,
+        token: batch_token,
+    }
+}
+
+/// Runs `request` until `stop` resolves, a request still in flight then
+/// failing with [`HarvestError::Cancelled`] for `url`.
+async fn until_stopped<T>(
+    request: impl Future<Output = Result<T, HarvestError>>,
+    stop: StopSignal,
+    url: &str,
+) -> Result<T, HarvestError> {
+    tokio::select! {
+        result = request => result,
+        reason = stop => Err(HarvestError::Cancelled {
+            url: url.to_string(),
+            message: reason.to_string(),
+        }),
+    }
+}
+
+/// Fetches and harvests the URLs concurrently, at most
+/// [`HttpConfig::max_concurrency`] at a time, yielding the results of each URL
+/// with its index as it completes, until `stop` resolves.
+fn harvest_urls(
+    urls: Vec<String>,
+    config: HarvestConfig,
+    stop: BatchStop,
+) -> BoxStream<'static, (usize, Vec<Result<HarvestedDocument, HarvestError>>)> {
+    let max_concurrency = config
+        .http_config()
+        .max_concurrency()
+        .unwrap_or(urls.len())
+        .max(1);
+    let client = build_client_async(config.http_config());
+    let headers = browser_headers(UserAgent::random());
+    let content_processor = config.content_processor();
+    let signal = stop.signal.clone();
+
+    stream::iter(urls.into_iter().enumerate())
+        .map(move |(index, url)| {
+            let page = fetch_page_async(client.clone(), url, headers.clone());
+            let client = client.clone();
+            let content_processor = content_processor.clone();
+            let config = config.clone();
+            let stop = stop.clone();
+            async move {
+                let page = page.await;
+                let documents =
+                    harvest_page_async(page, client, content_processor, config, stop).await;
+                (index, documents)
+            }
+        })
+        .buffer_unordered(max_concurrency)
+        .take_until(signal)
+        .boxed()
+}
+
+/// Collects the results of [`harvest_urls`] in the order of the URLs, each
+/// URL left unfinished when `stop` resolved giving a [`HarvestError::Cancelled`].
+async fn harvest_urls_in_order(
+    urls: Vec<String>,
+    config: HarvestConfig,
+    stop: BatchStop,
+) -> Vec<Result<HarvestedDocument, HarvestError>> {
+    let mut harvested: Vec<Option<Vec<_>>> = vec![None; urls.len()];
+    let mut completed = harvest_urls(urls.clone(), config, stop.clone());
+    while let Some((index, documents)) = completed.next().await {
+        harvested[index] = Some(documents);
+    }
+    let reason = stop.signal.peek().copied().unwrap_or("harvest stopped");
+
+    urls.into_iter()
+        .zip(harvested)
+        .flat_map(|(url, documents)| {
+            documents.unwrap_or_else(|| {
+                vec![Err(HarvestError::Cancelled {
+                    url,
+                    message: reason.to_string(),
+                })]
+            })
+        })
+        .collect()
+}
+
+/// Harvests one fetched page, or each entry of a feed, until `stop` resolves.
+async fn harvest_page_async(
+    page: Result<FetchedPage, HarvestError>,
+    client: Client,
+    content_processor: ContentProcessor,
+    config: HarvestConfig,
+    stop: BatchStop,
+) -> Vec<Result<HarvestedDocument, HarvestError>> {
+    match page {
+        Ok(page) if page.is_feed() => {
+            harvest_feed_async(page, content_processor, config, stop.token).await
+        }
+        // Extraction and rendering block, so they run off the async workers,
+        // while the following pages of an article are fetched by the async
+        // client so that they are dropped when the batch stops
+        Ok(page) => {
+            let runtime = Handle::current();
+            let headers = browser_headers(UserAgent::random());
+            let cancel = stop.token.clone();
+            let signal = stop.signal.clone();
+            let fetch = move |url: &str| {
+                let page = fetch_page_async(client.clone(), url.to_string(), headers.clone());
+                runtime.block_on(until_stopped(page, stop.signal.clone(), url))
+            };
+            vec![
+                run_blocking(move || {
+                    harvest_article(
+                        page,
+                        &content_processor,
+                        &config,
+                        &cancel,
+                        Some(&signal),
+                        fetch,
+                    )
+                })
+                .await,
+            ]
+        }
+        Err(error) => vec![Err(error)],
+    }
+}
+
+/// Harvests a page that is not a feed, merging the following pages of a
+/// paginated article, fetched with `fetch`, when
+/// [`HarvestConfig::pagination_pages`] is set.
+///
+/// Once `cancel` is cancelled, the page is no longer rendered nor followed by
+/// its next pages, and a page not yet extracted gives a
+/// [`HarvestError::Cancelled`] with the reason `signal` resolved with.
+fn harvest_article(
+    page: FetchedPage,
+    content_processor: &ContentProcessor,
+    config: &HarvestConfig,
+    cancel: &CancellationToken,
+    signal: Option<&StopSignal>,
+    fetch: impl FnMut(&str) -> Result<FetchedPage, HarvestError>,
+) -> Result<HarvestedDocument, HarvestError> {
+    if cancel.is_cancelled() {
+        // The token of a batch is cancelled along with the caller's token,
+        // possibly before the signal was polled
+        let reason = signal
+            .and_then(|signal| signal.peek().copied())
+            .unwrap_or("harvest cancelled");
+        return Err(HarvestError::Cancelled {
+            url: page.requested_url,
+            message: reason.to_string(),
+        });
+    }
+    let max_pages = config.pagination_pages();
+    let html = (max_pages > 0 && page.pdf.is_none() && page.body_kind().is_html())
+        .then(|| page.body.clone());
+    let document = harvest_page(
+        page,
+        content_processor,
+        config.front_matter(),
+        config.renderer(),
+        config.render_policy(),
+        cancel,
+    )?;
+
+    let Some(html) = html else {
+        return Ok(document);
+    };
+    Ok(stitch_pages(
+        document,
+        &html,
+        max_pages,
+        content_processor,
+        config.front_matter(),
+        cancel,
+        fetch,
+    ))
+}
+
+/// Harvests one document per entry of a fetched feed, from the entries'
+/// articles when [`HarvestConfig::fetch_feed_articles`] is set.
+fn harvest_feed(
+    page: FetchedPage,
+    client: &blocking::Client,
+    headers: &HeaderMap,
+    content_processor: &ContentProcessor,
+    config: &HarvestConfig,
+) -> Vec<Result<HarvestedDocument, HarvestError>> {
+    let feed = match parse_feed_page(&page) {
+        Ok(feed) => feed,
+        Err(error) => return vec![Err(error)],
+    };
+    let articles = if config.fetch_feed_articles() {
+        let links = entry_links(&page, &feed);
+        let fetched = links
+            .iter()
+            .flatten()
+            .map(|url| fetch_page(client, url, headers.clone()))
+            .collect();
+        align_articles(&links, fetched)
+    } else {
+        Vec::new()
+    };
+    let cancel = CancellationToken::new();
+    entry_documents(&page, &feed, articles, content_processor, config, &cancel)
+}
+
+/// Asynchronous version of [`harvest_feed`], fetching the articles
+/// concurrently and rendering them until `cancel` is cancelled.
+async fn harvest_feed_async(
+    page: FetchedPage,
+    content_processor: ContentProcessor,
+    config: HarvestConfig,
+    cancel: CancellationToken,
+) -> Vec<Result<HarvestedDocument, HarvestError>> {
+    let feed = match parse_feed_page(&page) {
+        Ok(feed) => feed,
+        Err(error) => return vec![Err(error)],
+    };
+    let articles = if config.fetch_feed_articles() {
+        let links = entry_links(&page, &feed);
+        let urls: Vec<String> = links.iter().flatten().cloned().collect();
+        let fetched = HttpClient::new()
+            .fetch_pages_async(&urls, config.http_config())
+            .await;
+        align_articles(&links, fetched)
+    } else {
+        Vec::new()
+    };
+    run_blocking(move || {
+        entry_documents(&page, &feed, articles, &content_processor, &config, &cancel)
+    })
+    .await
+}
+
+/// Runs blocking extraction or rendering off the async workers, propagating panics.
+async fn run_blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    match tokio::task::spawn_blocking(f).await {
+        Ok(value) => value,
+        Err(error) => std::panic::resume_unwind(error.into_panic()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::HttpConfig;
+    use crate::http_client::test_page;
 
     #[test]
     fn test_markdown_harvester_new() {
@@ -667,6 +1404,286 @@ mod tests {
         assert!(results.is_empty());
     }
 
+    #[test]
+    fn test_get_hyperlinks_documents_with_no_urls() {
+        let text = "This is just plain text without any URLs.".to_string();
+        let results = MarkdownHarvester::get_hyperlinks_documents(text, HarvestConfig::default());
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_get_hyperlinks_documents_reports_unreachable_url() {
+        let text = "Broken link: http://127.0.0.1/unreachable".to_string();
+        let config = HarvestConfig::from(HttpConfig::builder().timeout(2000).build());
+        let results = MarkdownHarvester::get_hyperlinks_documents(text, config);
+
+        assert_eq!(results.len(), 1);
+        let error = results[0].as_ref().unwrap_err();
+        assert_eq!(error.url(), "http://127.0.0.1/unreachable");
+        assert!(matches!(error, HarvestError::Request { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_get_hyperlinks_documents_async_with_no_urls() {
+        let text = "No links in here.".to_string();
+        let results =
+            MarkdownHarvester::get_hyperlinks_documents_async(text, HarvestConfig::default()).await;
+        assert!(results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_harvest_stream_yields_results_as_they_complete() {
+        let empty = MarkdownHarvester::harvest_stream(
+            "No links here.".to_string(),
+            HarvestConfig::default(),
+        );
+        assert_eq!(empty.count().await, 0);
+
+        let text = "Broken links: http://127.0.0.1/first and http://127.0.0.1/second".to_string();
+        let config = HarvestConfig::from(
+            HttpConfig::builder()
+                .timeout(2000)
+                .max_concurrency(1)
+                .build(),
+        );
+        let mut urls: Vec<String> = MarkdownHarvester::harvest_stream(text, config)
+            .map(|result| result.unwrap_err().url().to_string())
+            .collect()
+            .await;
+        urls.sort();
+        assert_eq!(urls, ["http://127.0.0.1/first", "http://127.0.0.1/second"]);
+    }
+
+    #[test]
+    fn test_get_sitemap_documents_on_worker_threads() {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let site = format!("http://{}", listener.local_addr().unwrap());
+        let page_urls: Vec<String> = (1..=5).map(|n| format!("{}/docs/{}", site, n)).collect();
+        let sitemap = format!(
+            r#"<?xml version="1.0"?><urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">{}</urlset>"#,
+            page_urls
+                .iter()
+                .map(|url| format!("<url><loc>{}</loc></url>", url))
+                .collect::<String>()
+        );
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = [0; 1024];
+                let read = stream.read(&mut request).unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..read]);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+                let (content_type, body) = match path {
+                    "/sitemap.xml" => ("application/xml", sitemap.clone()),
+                    _ => (
+                        "text/html",
+                        format!(
+                            "<html><body><article><p>Documentation for {}.</p></article></body></html>",
+                            path
+                        ),
+                    ),
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    content_type,
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        let config = HarvestConfig::from(HttpConfig::builder().max_concurrency(2).build());
+        let documents = MarkdownHarvester::get_sitemap_documents(
+            &format!("{}/sitemap.xml", site),
+            config,
+            &SitemapConfig::default(),
+        );
+        let documents: Vec<HarvestedDocument> = documents.into_iter().map(Result::unwrap).collect();
+
+        // Results keep the sitemap order, whichever worker fetched them
+        let final_urls: Vec<&str> = documents.iter().map(|d| d.final_url.as_str()).collect();
+        assert_eq!(final_urls, page_urls);
+        assert_eq!(documents[2].markdown, "Documentation for /docs/3.");
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn test_content_pairs_pdf_and_markdown() {
+        use crate::pdf_converter::tests::report_pdf;
+
+        let pages = vec![
+            Ok(FetchedPage {
+                pdf: Some(report_pdf()),
+                ..test_page("https://example.com/report.pdf", "application/pdf", "")
+            }),
+            Err(HarvestError::Request {
+                url: "https://example.com/broken".to_string(),
+                message: "connection refused".to_string(),
+            }),
+            Ok(test_page(
+                "https://example.com/README.md",
+                "text/markdown",
+                "# Project\n\nSome `code`.",
+            )),
+        ];
+
+        let config = HarvestConfig::default();
+        let client = build_client(config.http_config());
+        let headers = browser_headers(UserAgent::random());
+        let pairs = content_pairs(pages, &client, &headers, &config);
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0].0, "https://example.com/report.pdf");
+        assert!(pairs[0].1.starts_with("# Annual Report\n\n## Introduction"));
+        assert_eq!(
+            pairs[1],
+            (
+                "https://example.com/README.md".to_string(),
+                "# Project\n\nSome `code`.".to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deadline_returns_partial_results() {
+        // Accepts connections but never answers, so its page stays in flight
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let slow_url = format!("http://{}/slow", listener.local_addr().unwrap());
+        let urls = vec!["http://127.0.0.1/broken".to_string(), slow_url.clone()];
+        let config = HarvestConfig::builder().deadline(300).build();
+
+        let started = std::time::Instant::now();
+        let stop = batch_stop(&config);
+        let results = harvest_urls_in_order(urls, config, stop).await;
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[0], Err(HarvestError::Request { .. })));
+        match &results[1] {
+            Err(HarvestError::Cancelled { url, message }) => {
+                assert_eq!(url, &slow_url);
+                assert_eq!(message, "batch deadline exceeded");
+            }
+            other => panic!("expected a cancelled URL, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_deadline_stops_pagination_in_flight() {
+        use std::io::{Read, Write};
+
+        // Serves the first page of an article and never answers for the next
+        // one, reporting when the client gives that request up
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let story_url = format!("http://{}/story", listener.local_addr().unwrap());
+        let (given_up, abandoned) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = [0; 1024];
+                let read = stream.read(&mut request).unwrap_or(0);
+                if !request[..read].starts_with(b"GET /story ") {
+                    let given_up = given_up.clone();
+                    std::thread::spawn(move || {
+                        let _ = stream.read(&mut request);
+                        let _ = given_up.send(());
+                    });
+                    continue;
+                }
+                let body = r#"<html><head><link rel="next" href="/story?page=2"></head>
+                    <body><article><p>The first part of the story.</p></article></body></html>"#;
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+            }
+        });
+        let config = HarvestConfig::builder()
+            .pagination_pages(3)
+            .deadline(500)
+            .build();
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let results = runtime.block_on(async {
+            let stop = batch_stop(&config);
+            harvest_urls_in_order(vec![story_url.clone()], config, stop).await
+        });
+        assert_eq!(results.len(), 1);
+        match &results[0] {
+            Err(HarvestError::Cancelled { url, message }) => {
+                assert_eq!(url, &story_url);
+                assert_eq!(message, "batch deadline exceeded");
+            }
+            other => panic!("expected a cancelled URL, got {:?}", other),
+        }
+        // The request for the second page is dropped with the batch
+        assert!(abandoned.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_token_stops_batch() {
+        let text = "Pages: http://127.0.0.1/first and http://127.0.0.1/second".to_string();
+        let token = crate::CancellationToken::new();
+        let config = HarvestConfig::builder()
+            .cancellation_token(token.clone())
+            .build();
+        token.cancel();
+
+        let documents = MarkdownHarvester::harvest_stream(text.clone(), config.clone());
+        assert_eq!(documents.count().await, 0);
+
+        let results = MarkdownHarvester::get_hyperlinks_documents_async(text, config).await;
+        let cancelled: Vec<&str> = results
+            .iter()
+            .map(|result| match result {
+                Err(HarvestError::Cancelled { url, message }) => {
+                    assert_eq!(message, "harvest cancelled");
+                    url.as_str()
+                }
+                other => panic!("expected a cancelled URL, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            cancelled,
+            ["http://127.0.0.1/first", "http://127.0.0.1/second"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_harvest_article_reports_stop_reason() {
+        let page = test_page(
+            "https://example.com/story",
+            "text/html",
+            "<html><body><p>The story.</p></body></html>",
+        );
+        let config = HarvestConfig::default();
+        let content_processor = config.content_processor();
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let signal: StopSignal = future::ready("batch deadline exceeded").boxed().shared();
+        signal.clone().await;
+
+        let result = harvest_article(
+            page,
+            &content_processor,
+            &config,
+            &cancel,
+            Some(&signal),
+            |_| unreachable!(),
+        );
+        match result {
+            Err(HarvestError::Cancelled { url, message }) => {
+                assert_eq!(url, "https://example.com/story");
+                assert_eq!(message, "batch deadline exceeded");
+            }
+            other => panic!("expected a cancelled page, got {:?}", other),
+        }
+    }
+
     #[cfg(feature = "chunks")]
     mod chunks_tests {
         use super::*;
@@ -818,7 +1835,7 @@ mod tests {
             for chunk_size in chunk_sizes {
                 let results = MarkdownHarvester::get_hyperlinks_content_as_chunks(
                     text.clone(), 
-                    config.clone(), 
+                    config, 
                     chunk_size,
                     None
                 );
@@ -871,7 +1888,7 @@ mod tests {
             for overlap in valid_overlaps {
                 let results = MarkdownHarvester::get_hyperlinks_content_as_chunks(
                     text.clone(), 
-                    config.clone(), 
+                    config, 
                     chunk_size, 
                     Some(overlap)
                 );
@@ -895,7 +1912,7 @@ mod tests {
             for overlap in invalid_overlaps {
                 let results = MarkdownHarvester::get_hyperlinks_content_as_chunks(
                     text.clone(), 
-                    config.clone(), 
+                    config, 
                     chunk_size, 
                     Some(overlap)
                 );
@@ -942,7 +1959,7 @@ mod tests {
             let chunk_size = 500;
             let invalid_overlap = Some(500); // Invalid: overlap >= chunk_size
             
-            let callback = |url: Option<String>, chunks: Option<Vec<String>>| {
+            let callback = |_url: Option<String>, _chunks: Option<Vec<String>>| {
                 async move {
                     // Should handle invalid overlap gracefully
                     // Note: In actual implementation, this might not be called for invalid overlap
@@ -955,6 +1972,39 @@ mod tests {
             
             assert!(result.is_ok());
         }
+
+        #[tokio::test]
+        async fn test_harvest_chunk_stream() {
+            let text = "Broken link: http://127.0.0.1/unreachable".to_string();
+            let config = HarvestConfig::from(HttpConfig::builder().timeout(2000).build());
+            let results: Vec<_> = MarkdownHarvester::harvest_chunk_stream(
+                text.clone(),
+                config.clone(),
+                500,
+                Some(50),
+            )
+            .collect()
+            .await;
+            assert_eq!(results.len(), 1);
+            assert_eq!(
+                results[0].as_ref().unwrap_err().url(),
+                "http://127.0.0.1/unreachable"
+            );
+
+            // An overlap that is not smaller than the chunk size is reported once
+            let invalid: Vec<_> =
+                MarkdownHarvester::harvest_chunk_stream(text, config, 500, Some(500))
+                    .collect()
+                    .await;
+            assert_eq!(invalid.len(), 1);
+            assert_eq!(
+                invalid[0].as_ref().unwrap_err(),
+                &HarvestError::InvalidConfig {
+                    message: "chunk_overlap (500) must be smaller than chunk_size (500)"
+                        .to_string()
+                }
+            );
+        }
     }
 
     // Integration-style tests that would work with a real HTTP mock
@@ -967,7 +2017,7 @@ mod tests {
             .build();
 
         // Test synchronous version
-        let sync_results = MarkdownHarvester::get_hyperlinks_content(text.clone(), config.clone());
+        let sync_results = MarkdownHarvester::get_hyperlinks_content(text.clone(), config);
         
         // Test that it returns the expected structure (empty in unit tests since no real HTTP)
         assert!(sync_results.is_empty() || sync_results.iter().all(|(url, content)| {
@@ -989,5 +2039,6 @@ mod tests {
                 !url.is_empty() && chunks.iter().all(|chunk| !chunk.is_empty())
             }));
         }
+
     }
 }
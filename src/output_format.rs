@@ -0,0 +1,150 @@
+use crate::plain_text::markdown_to_plain_text;
+
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, html};
+
+/// URL schemes kept in links and images of [`OutputFormat::Html`]; any other
+/// scheme, such as `javascript:`, leaves an empty target.
+const SAFE_URL_SCHEMES: [&str; 4] = ["http", "https", "mailto", "tel"];
+
+/// The format of the content produced by the extraction pipeline.
+///
+/// Every format is rendered from the same cleaned content, so the selectors,
+/// text filters and link handling of the [`ExtractionConfig`](crate::ExtractionConfig)
+/// apply to all of them.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::{ContentProcessor, ExtractionConfig, OutputFormat};
+///
+/// let config = ExtractionConfig::builder()
+///     .output_format(OutputFormat::Html)
+///     .build();
+/// let processor = ContentProcessor::with_config(config);
+///
+/// let html = r#"<html><body><article><h2>Setup</h2>
+///     <p>Install the <strong>latest</strong> release before upgrading.</p>
+/// </article></body></html>"#;
+/// let output = processor.html_to_output(html);
+/// assert!(output.contains("<h2>Setup</h2>"));
+/// assert!(output.contains("<strong>latest</strong>"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutputFormat {
+    /// Strict CommonMark. Tables, which CommonMark lacks, become key-value lists.
+    CommonMark,
+    /// GitHub Flavored Markdown, with tables (the original behaviour).
+    #[default]
+    Gfm,
+    /// Text without Markdown syntax. Headings and paragraphs become lines of
+    /// text, list items keep a `•` bullet or their number, and tables become
+    /// lists of labelled values.
+    PlainText,
+    /// Minimal HTML rendered from the cleaned content. Raw HTML is escaped and
+    /// links with unsafe schemes lose their target, so the result can be shown
+    /// as is.
+    Html,
+}
+
+impl OutputFormat {
+    /// Returns `true` for the Markdown formats.
+    pub(crate) fn is_markdown(self) -> bool {
+        matches!(self, OutputFormat::CommonMark | OutputFormat::Gfm)
+    }
+
+    /// Returns `true` when content in this format is rendered with GFM tables.
+    pub(crate) fn uses_gfm_tables(self) -> bool {
+        matches!(self, OutputFormat::Gfm | OutputFormat::Html)
+    }
+
+    /// Renders the cleaned Markdown in this format.
+    pub(crate) fn render(self, markdown: &str) -> String {
+        match self {
+            OutputFormat::CommonMark | OutputFormat::Gfm => markdown.to_string(),
+            OutputFormat::PlainText => markdown_to_plain_text(markdown),
+            OutputFormat::Html => markdown_to_html(markdown),
+        }
+    }
+}
+
+fn markdown_to_html(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+    let events = Parser::new_ext(markdown, options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        event => event,
+    });
+
+    let mut output = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut output, events);
+    output.trim_end().to_string()
+}
+
+/// Keeps relative URLs and those with a scheme from [`SAFE_URL_SCHEMES`].
+fn safe_url(url: CowStr<'_>) -> CowStr<'_> {
+    let scheme = url
+        .split_once(':')
+        .map(|(scheme, _)| scheme)
+        .filter(|scheme| !scheme.contains(['/', '?', '#']));
+
+    match scheme {
+        Some(scheme) if !SAFE_URL_SCHEMES.contains(&scheme.to_ascii_lowercase().as_str()) => {
+            CowStr::Borrowed("")
+        }
+        _ => url,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_html_escapes_raw_html() {
+        let markdown = "## Notes\n\nSee <script>alert(1)</script> and \
+            [this](javascript:alert(1)) or [docs](https://example.com/docs).\n\n\
+            | Plan | Price |\n| --- | --- |\n| Pro | $10 |";
+
+        let html = OutputFormat::Html.render(markdown);
+        assert!(html.starts_with("<h2>Notes</h2>"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(html.contains(r#"<a href="">this</a>"#));
+        assert!(html.contains(r#"<a href="https://example.com/docs">docs</a>"#));
+        assert!(html.contains("<td>Pro</td>"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn test_render_markdown_and_plain_text() {
+        let markdown = "# Title\n\n- **One**\n- Two";
+
+        assert_eq!(OutputFormat::Gfm.render(markdown), markdown);
+        assert_eq!(OutputFormat::CommonMark.render(markdown), markdown);
+        assert_eq!(
+            OutputFormat::PlainText.render(markdown),
+            "Title\n\n• One\n• Two"
+        );
+    }
+}
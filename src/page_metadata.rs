@@ -0,0 +1,524 @@
+use std::collections::BTreeMap;
+
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+use serde_json::Value;
+
+/// schema.org types whose JSON-LD node describes the page's main content.
+const ARTICLE_TYPES: [&str; 8] = [
+    "Article",
+    "NewsArticle",
+    "BlogPosting",
+    "TechArticle",
+    "ScholarlyArticle",
+    "Report",
+    "LiveBlogPosting",
+    "DiscussionForumPosting",
+];
+
+/// `<meta name>` values that carry a publication date on pages without OpenGraph.
+const DATE_META_NAMES: [&str; 6] = [
+    "date",
+    "pubdate",
+    "publish-date",
+    "publish_date",
+    "dc.date",
+    "dc.date.issued",
+];
+
+/// Title separators used to append the site name, e.g. `Post title | Site`.
+const TITLE_SEPARATORS: [&str; 5] = [" | ", " - ", " – ", " — ", " · "];
+
+/// Structured metadata describing a page, gathered from its `<head>` and markup.
+///
+/// Each field is filled from the most specific source available: schema.org
+/// JSON-LD (`Article`, `NewsArticle`, `BlogPosting`, ...) first, then OpenGraph
+/// and Twitter card tags, then standard `<meta>` tags and the document itself.
+/// Dates are kept as published by the page, usually in ISO 8601 form. URLs are
+/// resolved against the page URL when it is known.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::ContentProcessor;
+///
+/// let html = r#"<html><head>
+///     <title>Release notes | Example Blog</title>
+///     <meta property="og:site_name" content="Example Blog">
+///     <meta name="author" content="Jane Doe">
+///     <meta property="article:published_time" content="2026-03-01T09:00:00Z">
+/// </head><body><article><p>Version 2.0 is out with many improvements.</p></article></body></html>"#;
+///
+/// let extracted = ContentProcessor::new().extract(html, Some("https://blog.example.com/v2"));
+/// assert_eq!(extracted.metadata.title.as_deref(), Some("Release notes"));
+/// assert_eq!(extracted.metadata.author.as_deref(), Some("Jane Doe"));
+/// assert_eq!(
+///     extracted.metadata.published_time.as_deref(),
+///     Some("2026-03-01T09:00:00Z")
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageMetadata {
+    /// Page title, without a trailing site name.
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Author names, comma separated when there are several.
+    pub author: Option<String>,
+    pub published_time: Option<String>,
+    pub modified_time: Option<String>,
+    pub site_name: Option<String>,
+    pub canonical_url: Option<String>,
+    /// Lead image of the page.
+    pub image: Option<String>,
+    /// schema.org `@type` or OpenGraph `og:type`, e.g. `NewsArticle` or `article`.
+    pub page_type: Option<String>,
+    pub keywords: Vec<String>,
+    /// Language tag declared by the page (`<html lang>`, `Content-Language` meta
+    /// or `og:locale`), e.g. `en-US`.
+    pub language: Option<String>,
+    /// Every `og:*` and `article:*` property found on the page.
+    pub open_graph: BTreeMap<String, String>,
+    /// Every `twitter:*` card property found on the page.
+    pub twitter_card: BTreeMap<String, String>,
+}
+
+/// Extracts the page metadata from a parsed document.
+pub(crate) fn extract_page_metadata(document: &Html, base_url: Option<&Url>) -> PageMetadata {
+    let meta = MetaTags::collect(document);
+    let json_ld = find_article_json_ld(document);
+    let json_ld = json_ld.as_ref();
+    let json_field = |field: &str| json_ld.and_then(|node| node.get(field)).and_then(json_text);
+
+    let site_name = meta
+        .first(&["og:site_name"])
+        .or_else(|| {
+            json_ld
+                .and_then(|node| node.get("publisher"))
+                .and_then(json_text)
+        })
+        .or_else(|| meta.first(&["application-name"]));
+
+    let title = json_field("headline")
+        .or_else(|| json_field("name"))
+        .or_else(|| meta.first(&["og:title", "twitter:title"]))
+        .or_else(|| first_text(document, "title"))
+        .or_else(|| first_text(document, "h1"))
+        .map(|title| strip_site_name(&title, site_name.as_deref()));
+
+    let author = json_ld
+        .and_then(|node| node.get("author"))
+        .and_then(json_names)
+        .or_else(|| meta.first(&["author", "article:author", "twitter:creator"]))
+        .filter(|author| !author.starts_with("http"))
+        .or_else(|| first_text(document, "[rel='author'], [itemprop='author']"));
+
+    let published_time = json_field("datePublished")
+        .or_else(|| meta.first(&["article:published_time"]))
+        .or_else(|| meta.first(&DATE_META_NAMES))
+        .or_else(|| {
+            first_attr(
+                document,
+                "time[itemprop='datePublished'], time[pubdate]",
+                "datetime",
+            )
+        });
+
+    let mut keywords = json_ld
+        .and_then(|node| node.get("keywords"))
+        .map(json_list)
+        .unwrap_or_default();
+    if keywords.is_empty() {
+        keywords = meta.all("article:tag");
+    }
+    if keywords.is_empty() {
+        keywords = meta
+            .first(&["keywords"])
+            .map(|value| split_keywords(&value))
+            .unwrap_or_default();
+    }
+    let mut seen = Vec::new();
+    keywords.retain(|keyword| {
+        let duplicate = seen.contains(keyword);
+        seen.push(keyword.clone());
+        !duplicate
+    });
+
+    PageMetadata {
+        title,
+        description: json_field("description")
+            .or_else(|| meta.first(&["og:description", "twitter:description", "description"])),
+        author,
+        published_time,
+        modified_time: json_field("dateModified")
+            .or_else(|| meta.first(&["article:modified_time", "og:updated_time"])),
+        site_name,
+        canonical_url: first_attr(document, "link[rel='canonical'][href]", "href")
+            .or_else(|| meta.first(&["og:url"]))
+            .map(|url| resolve_url(&url, base_url)),
+        image: json_ld
+            .and_then(|node| node.get("image"))
+            .and_then(json_text)
+            .or_else(|| meta.first(&["og:image", "og:image:url", "twitter:image"]))
+            .map(|url| resolve_url(&url, base_url)),
+        page_type: json_ld
+            .and_then(|node| node.get("@type"))
+            .and_then(json_text)
+            .or_else(|| meta.first(&["og:type"])),
+        keywords,
+        language: document
+            .root_element()
+            .attr("lang")
+            .map(str::trim)
+            .filter(|language| !language.is_empty())
+            .map(str::to_string)
+            .or_else(|| first_attr(document, "meta[http-equiv='content-language' i]", "content"))
+            .or_else(|| meta.first(&["og:locale"]))
+            .map(|language| language.replace('_', "-")),
+        open_graph: meta.with_prefixes(&["og:", "article:"]),
+        twitter_card: meta.with_prefixes(&["twitter:"]),
+    }
+}
+
+/// `<meta>` tags keyed by their lowercased `property` or `name`, in document order.
+struct MetaTags {
+    tags: Vec<(String, String)>,
+}
+
+impl MetaTags {
+    fn collect(document: &Html) -> Self {
+        let selector = Selector::parse("meta[content]").unwrap();
+        let tags = document
+            .root_element()
+            .select(&selector)
+            .filter_map(|meta| {
+                let element = meta.value();
+                let key = element.attr("property").or_else(|| element.attr("name"))?;
+                let content = element.attr("content")?.trim();
+                (!content.is_empty()).then(|| (key.trim().to_lowercase(), content.to_string()))
+            })
+            .collect();
+
+        Self { tags }
+    }
+
+    /// Returns the content of the first tag matching one of `keys`, tried in order.
+    fn first(&self, keys: &[&str]) -> Option<String> {
+        keys.iter().find_map(|key| {
+            self.tags
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, content)| content.clone())
+        })
+    }
+
+    fn all(&self, key: &str) -> Vec<String> {
+        self.tags
+            .iter()
+            .filter(|(name, _)| name == key)
+            .map(|(_, content)| content.clone())
+            .collect()
+    }
+
+    /// Returns the tags whose key starts with one of `prefixes`; the first
+    /// occurrence of a repeated key wins.
+    fn with_prefixes(&self, prefixes: &[&str]) -> BTreeMap<String, String> {
+        let mut properties = BTreeMap::new();
+        for (name, content) in &self.tags {
+            if prefixes.iter().any(|prefix| name.starts_with(prefix)) {
+                properties
+                    .entry(name.clone())
+                    .or_insert_with(|| content.clone());
+            }
+        }
+        properties
+    }
+}
+
+/// Finds the first JSON-LD node describing an article, looking inside arrays
+/// and `@graph` containers. Scripts that fail to parse are skipped.
+pub(crate) fn find_article_json_ld(document: &Html) -> Option<Value> {
+    let selector = Selector::parse("script[type]").unwrap();
+
+    document
+        .root_element()
+        .select(&selector)
+        .filter(|script| {
+            script
+                .value()
+                .attr("type")
+                .is_some_and(|kind| kind.trim().eq_ignore_ascii_case("application/ld+json"))
+        })
+        .filter_map(|script| serde_json::from_str::<Value>(&script.text().collect::<String>()).ok())
+        .find_map(find_article_node)
+}
+
+fn find_article_node(value: Value) -> Option<Value> {
+    match value {
+        Value::Array(items) => items.into_iter().find_map(find_article_node),
+        Value::Object(mut object) => {
+            let is_article = object.get("@type").is_some_and(|kind| match kind {
+                Value::String(kind) => is_article_type(kind),
+                Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).any(is_article_type),
+                _ => false,
+            });
+            if is_article {
+                return Some(Value::Object(object));
+            }
+            object.remove("@graph").and_then(find_article_node)
+        }
+        _ => None,
+    }
+}
+
+fn is_article_type(kind: &str) -> bool {
+    let kind = kind
+        .trim_start_matches("http://schema.org/")
+        .trim_start_matches("https://schema.org/");
+    ARTICLE_TYPES.contains(&kind) || kind.ends_with("NewsArticle")
+}
+
+/// Reads a JSON-LD value as text: strings directly, objects through their
+/// `name`, `url` or `@value`, arrays through their first usable item.
+fn json_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.trim().to_string()).filter(|text| !text.is_empty()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Object(object) => ["name", "url", "@value"]
+            .iter()
+            .find_map(|key| object.get(*key).and_then(json_text)),
+        Value::Array(items) => items.iter().find_map(json_text),
+        _ => None,
+    }
+}
+
+/// Reads one or several JSON-LD people or organizations as a comma-separated list of names.
+fn json_names(value: &Value) -> Option<String> {
+    let names = match value {
+        Value::Array(items) => items.iter().filter_map(json_text).collect::<Vec<_>>(),
+        other => json_text(other).into_iter().collect(),
+    };
+    (!names.is_empty()).then(|| names.join(", "))
+}
+
+fn json_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(text) => split_keywords(text),
+        Value::Array(items) => items.iter().filter_map(json_text).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn split_keywords(keywords: &str) -> Vec<String> {
+    keywords
+        .split(',')
+        .map(str::trim)
+        .filter(|keyword| !keyword.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn first_text(document: &Html, selector: &str) -> Option<String> {
+    let selector = Selector::parse(selector).unwrap();
+    document
+        .root_element()
+        .select(&selector)
+        .map(element_text)
+        .find(|text| !text.is_empty())
+}
+
+fn first_attr(document: &Html, selector: &str, attribute: &str) -> Option<String> {
+    let selector = Selector::parse(selector).unwrap();
+    document
+        .root_element()
+        .select(&selector)
+        .filter_map(|element| element.value().attr(attribute))
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn element_text(element: ElementRef) -> String {
+    element
+        .text()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Removes a trailing (or leading) site name, e.g. `Post title | Site` becomes `Post title`.
+fn strip_site_name(title: &str, site_name: Option<&str>) -> String {
+    let title = title.trim();
+    let Some(site_name) = site_name.filter(|name| !name.is_empty()) else {
+        return title.to_string();
+    };
+
+    for separator in TITLE_SEPARATORS {
+        if let Some(stripped) = title.strip_suffix(&format!("{}{}", separator, site_name))
+            && !stripped.trim().is_empty()
+        {
+            return stripped.trim().to_string();
+        }
+        if let Some(stripped) = title.strip_prefix(&format!("{}{}", site_name, separator))
+            && !stripped.trim().is_empty()
+        {
+            return stripped.trim().to_string();
+        }
+    }
+
+    title.to_string()
+}
+
+fn resolve_url(url: &str, base_url: Option<&Url>) -> String {
+    base_url
+        .and_then(|base_url| base_url.join(url).ok())
+        .map(|url| url.to_string())
+        .unwrap_or_else(|| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(html: &str) -> PageMetadata {
+        let base_url = Url::parse("https://news.example.com/world/story").unwrap();
+        extract_page_metadata(&Html::parse_document(html), Some(&base_url))
+    }
+
+    #[test]
+    fn test_extract_page_metadata_from_open_graph() {
+        let metadata = extract(
+            r#"<html><head>
+                <title>Fallback title</title>
+                <meta name="description" content="Plain description">
+                <meta property="og:title" content="Storm reaches the coast">
+                <meta property="og:description" content="OpenGraph description">
+                <meta property="og:type" content="article">
+                <meta property="og:image" content="/img/storm.jpg">
+                <meta property="og:site_name" content="Example News">
+                <meta property="article:published_time" content="2026-02-10T08:30:00Z">
+                <meta property="article:modified_time" content="2026-02-10T12:00:00Z">
+                <meta property="article:tag" content="weather">
+                <meta property="article:tag" content="coast">
+                <meta name="twitter:card" content="summary_large_image">
+                <meta name="author" content="Ana Silva">
+                <link rel="canonical" href="/world/storm-coast">
+            </head><body></body></html>"#,
+        );
+
+        assert_eq!(metadata.title.as_deref(), Some("Storm reaches the coast"));
+        assert_eq!(
+            metadata.description.as_deref(),
+            Some("OpenGraph description")
+        );
+        assert_eq!(metadata.author.as_deref(), Some("Ana Silva"));
+        assert_eq!(
+            metadata.published_time.as_deref(),
+            Some("2026-02-10T08:30:00Z")
+        );
+        assert_eq!(
+            metadata.modified_time.as_deref(),
+            Some("2026-02-10T12:00:00Z")
+        );
+        assert_eq!(metadata.site_name.as_deref(), Some("Example News"));
+        assert_eq!(
+            metadata.canonical_url.as_deref(),
+            Some("https://news.example.com/world/storm-coast")
+        );
+        assert_eq!(
+            metadata.image.as_deref(),
+            Some("https://news.example.com/img/storm.jpg")
+        );
+        assert_eq!(metadata.page_type.as_deref(), Some("article"));
+        assert_eq!(metadata.keywords, vec!["weather", "coast"]);
+        assert_eq!(metadata.language, None);
+        assert_eq!(
+            metadata.open_graph.get("og:type").map(String::as_str),
+            Some("article")
+        );
+        assert_eq!(
+            metadata
+                .twitter_card
+                .get("twitter:card")
+                .map(String::as_str),
+            Some("summary_large_image")
+        );
+    }
+
+    #[test]
+    fn test_extract_page_metadata_prefers_json_ld_article() {
+        let metadata = extract(
+            r#"<html><head>
+                <meta property="og:title" content="OG title">
+                <meta property="og:locale" content="pt_BR">
+                <script type="application/ld+json">{ not valid json </script>
+                <script type="application/ld+json">
+                {"@context": "https://schema.org", "@graph": [
+                    {"@type": "WebSite", "name": "Example News"},
+                    {"@type": ["NewsArticle"], "headline": "JSON-LD headline",
+                     "datePublished": "2026-01-05", "dateModified": "2026-01-06",
+                     "author": [{"@type": "Person", "name": "Ana Silva"}, {"name": "Rui Costa"}],
+                     "publisher": {"@type": "Organization", "name": "Example News"},
+                     "image": {"@type": "ImageObject", "url": "https://cdn.example.com/a.jpg"},
+                     "keywords": "politics, elections"}
+                ]}
+                </script>
+            </head><body></body></html>"#,
+        );
+
+        assert_eq!(metadata.title.as_deref(), Some("JSON-LD headline"));
+        assert_eq!(metadata.author.as_deref(), Some("Ana Silva, Rui Costa"));
+        assert_eq!(metadata.published_time.as_deref(), Some("2026-01-05"));
+        assert_eq!(metadata.modified_time.as_deref(), Some("2026-01-06"));
+        assert_eq!(metadata.site_name.as_deref(), Some("Example News"));
+        assert_eq!(
+            metadata.image.as_deref(),
+            Some("https://cdn.example.com/a.jpg")
+        );
+        assert_eq!(metadata.page_type.as_deref(), Some("NewsArticle"));
+        assert_eq!(metadata.keywords, vec!["politics", "elections"]);
+        assert_eq!(metadata.language.as_deref(), Some("pt-BR"));
+    }
+
+    #[test]
+    fn test_extract_page_metadata_from_plain_html() {
+        let metadata = extract(
+            r#"<html lang="en"><head>
+                <title>Changelog - Example Docs</title>
+                <meta name="application-name" content="Example Docs">
+                <meta name="keywords" content="rust, parsing , ">
+            </head><body>
+                <p>By <a rel="author" href="/team/ana">Ana  Silva</a></p>
+                <time pubdate datetime="2025-12-24">Dec 24</time>
+            </body></html>"#,
+        );
+
+        assert_eq!(metadata.title.as_deref(), Some("Changelog"));
+        assert_eq!(metadata.author.as_deref(), Some("Ana Silva"));
+        assert_eq!(metadata.published_time.as_deref(), Some("2025-12-24"));
+        assert_eq!(metadata.keywords, vec!["rust", "parsing"]);
+        assert_eq!(metadata.language.as_deref(), Some("en"));
+        assert!(metadata.canonical_url.is_none());
+        assert!(metadata.open_graph.is_empty());
+    }
+
+    #[test]
+    fn test_extract_page_metadata_empty_document() {
+        let metadata = extract_page_metadata(&Html::parse_document(""), None);
+        assert_eq!(metadata, PageMetadata::default());
+    }
+
+    #[test]
+    fn test_strip_site_name() {
+        assert_eq!(strip_site_name("Post | Site", Some("Site")), "Post");
+        assert_eq!(strip_site_name("Site — Post", Some("Site")), "Post");
+        assert_eq!(strip_site_name("Site", Some("Site")), "Site");
+        assert_eq!(
+            strip_site_name(" Post - Other ", Some("Site")),
+            "Post - Other"
+        );
+        assert_eq!(strip_site_name("Post | Site", None), "Post | Site");
+    }
+}
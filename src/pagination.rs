@@ -0,0 +1,496 @@
+use std::collections::HashSet;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+use tokio_util::sync::CancellationToken;
+
+use crate::content_processor::{ContentProcessor, extract_main_content};
+use crate::extraction_config::ExtractionConfig;
+use crate::front_matter::FrontMatter;
+use crate::harvest_error::HarvestError;
+use crate::harvested_document::HarvestedDocument;
+use crate::http_client::FetchedPage;
+use crate::link_handler::document_base_url;
+
+/// Texts of "next page" anchors, once arrows and surrounding whitespace are trimmed.
+static NEXT_PAGE_TEXT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^(next|next page|next part|older posts?|older entries|page suivante|suivant|página siguiente|siguiente|próxima página|próxima|nächste seite|weiter)$",
+    )
+    .unwrap()
+});
+
+/// Query parameters holding a page number.
+static PAGE_PARAMETER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(page|p|pg|paged|pagina|seite)$").unwrap());
+
+/// A page number at the end of the path: `/2`, `/2/` or `/page/2/`.
+static PAGE_PATH: Lazy<Regex> = Lazy::new(|| Regex::new(r"/(?:page/)?(\d{1,3})/?$").unwrap());
+
+/// An explicit page path, `/page/2/`, as opposed to any number ending a path.
+static PAGED_PATH: Lazy<Regex> = Lazy::new(|| Regex::new(r"/page/\d{1,3}/?$").unwrap());
+
+/// Class, id or label of the elements holding pagination controls.
+static PAGINATION_BLOCK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)(^|[\s_-])(pagination|paginator|pager|paging|page-numbers|page-links)($|[\s_-])",
+    )
+    .unwrap()
+});
+
+/// Blocks made only of pagination controls and counters: "1 2 3 Next »",
+/// "‹ Previous", "Page 2 of 5".
+static PAGINATION_CONTROLS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(?:\s|\d+|next|previous|prev|page|of|first|last|[‹›«»←→<>…|/*_-])+$").unwrap()
+});
+
+/// Finds the link to the page following `page_url` in a paginated article.
+///
+/// A `rel="next"` link anywhere in the document wins. Otherwise the anchors of
+/// the content region are searched for a "Next page" text, then for the URL of
+/// the next page number (`?page=3` after `?page=2`, `/2/` after the first page).
+/// Anchors are only followed from a page that is already paginated, by a page
+/// parameter or a `/page/N` path, or from a pagination control block, so that
+/// `/posts/43` linked from `/posts/42` is not taken for a next page. A "Next"
+/// anchor is also followed to a page of the same article, one sharing the
+/// page's URL once page numbers are removed. Only links to the same host are
+/// returned.
+pub(crate) fn next_page_url(html: &str, page_url: &str, config: &ExtractionConfig) -> Option<Url> {
+    let page_url = Url::parse(page_url).ok()?;
+    let document = Html::parse_document(html);
+    let base_url = document_base_url(&document, Some(page_url.as_str()))?;
+    let is_candidate = |url: &Url| {
+        url.host() == page_url.host()
+            && matches!(url.scheme(), "http" | "https")
+            && without_fragment(url) != without_fragment(&page_url)
+    };
+    let resolve = |href: &str| {
+        base_url
+            .join(href.trim())
+            .ok()
+            .filter(|url| is_candidate(url))
+    };
+
+    let rel_next = Selector::parse(r#"link[rel~="next"][href], a[rel~="next"][href]"#).unwrap();
+    if let Some(url) = document
+        .select(&rel_next)
+        .find_map(|element| resolve(element.value().attr("href")?))
+    {
+        return Some(url);
+    }
+
+    let (content_html, _) = extract_main_content(&document, config);
+    let content = Html::parse_fragment(&content_html);
+    let anchor = Selector::parse("a[href]").unwrap();
+    let anchors: Vec<(String, Url, bool)> = content
+        .select(&anchor)
+        .filter_map(|element| {
+            let url = resolve(element.value().attr("href")?)?;
+            let text = element.text().collect::<String>();
+            let text = text
+                .trim_matches(|c: char| c.is_whitespace() || "›»→>".contains(c))
+                .to_string();
+            Some((text, url, in_pagination_block(element)))
+        })
+        .collect();
+
+    let paginated = is_paginated(&page_url);
+    let (base, number) = split_page_number(&page_url);
+    let by_text = anchors.iter().find(|(text, url, in_controls)| {
+        NEXT_PAGE_TEXT.is_match(text)
+            && (paginated || *in_controls || split_page_number(url).0 == base)
+    });
+    let by_number = || {
+        anchors.iter().find(|(_, url, in_controls)| {
+            (paginated || *in_controls)
+                && split_page_number(url) == (base.clone(), Some(number.unwrap_or(1) + 1))
+        })
+    };
+    by_text.or_else(by_number).map(|(_, url, _)| url.clone())
+}
+
+/// Returns `true` when the URL names its page, with a page parameter or a
+/// `/page/N` path.
+fn is_paginated(url: &Url) -> bool {
+    url.query_pairs()
+        .any(|(name, value)| PAGE_PARAMETER.is_match(&name) && value.parse::<u32>().is_ok())
+        || PAGED_PATH.is_match(url.path())
+}
+
+/// Returns `true` when the anchor sits in a pagination control block, such as
+/// `<div class="pagination">` or `<nav aria-label="Pagination">`.
+fn in_pagination_block(anchor: ElementRef) -> bool {
+    anchor
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|element| {
+            ["class", "id", "aria-label"]
+                .iter()
+                .filter_map(|name| element.value().attr(name))
+                .any(|value| PAGINATION_BLOCK.is_match(value))
+        })
+}
+
+/// Splits a URL into the URL without its page number and the page number.
+fn split_page_number(url: &Url) -> (String, Option<u32>) {
+    let mut base = without_fragment(url);
+    let mut number = None;
+
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    if let Some((name, value)) = pairs.iter().find(|(name, _)| PAGE_PARAMETER.is_match(name))
+        && let Ok(value) = value.parse()
+    {
+        number = Some(value);
+        let rest = pairs.iter().filter(|(other, _)| other != name);
+        base.query_pairs_mut().clear().extend_pairs(rest);
+    } else if let Some(captures) = PAGE_PATH.captures(url.path()) {
+        number = captures[1].parse().ok();
+        let path = url.path()[..captures.get(0).unwrap().start()].to_string();
+        base.set_path(&path);
+    }
+    if base.query() == Some("") {
+        base.set_query(None);
+    }
+
+    let base = base.as_str().trim_end_matches('/').to_string();
+    (base, number)
+}
+
+fn without_fragment(url: &Url) -> Url {
+    let mut url = url.clone();
+    url.set_fragment(None);
+    url
+}
+
+fn is_pagination_controls(block: &str) -> bool {
+    block.chars().any(char::is_alphanumeric) && PAGINATION_CONTROLS.is_match(block)
+}
+
+/// Splits Markdown into its blank-line separated blocks, keeping each fenced
+/// code block whole.
+fn markdown_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks: Vec<String> = Vec::new();
+    let mut in_fence = false;
+    for block in markdown.split("\n\n") {
+        match blocks.last_mut() {
+            Some(last) if in_fence => {
+                last.push_str("\n\n");
+                last.push_str(block);
+            }
+            _ => blocks.push(block.to_string()),
+        }
+        let fences = block
+            .lines()
+            .map(str::trim_start)
+            .filter(|line| line.starts_with("```") || line.starts_with("~~~"))
+            .count();
+        in_fence ^= fences % 2 == 1;
+    }
+    blocks
+}
+
+/// Removes the blocks a following page repeats at its start, such as the
+/// article title, byline and intro, along with the pagination controls and
+/// page counters leading it.
+pub(crate) fn without_repeated_lead(merged: &str, continuation: &str) -> String {
+    let seen: HashSet<String> = markdown_blocks(merged)
+        .iter()
+        .map(|block| block.trim().to_string())
+        .collect();
+
+    let blocks: Vec<String> = markdown_blocks(continuation)
+        .iter()
+        .map(|block| block.trim().to_string())
+        .filter(|block| !block.is_empty())
+        .collect();
+    let lead = blocks
+        .iter()
+        .take_while(|block| seen.contains(*block) || is_pagination_controls(block))
+        .count();
+
+    blocks[lead..].join("\n\n")
+}
+
+/// Removes the pagination controls and page counters ending a page, where the
+/// next page is appended or the article ends. Fenced code is kept.
+pub(crate) fn without_trailing_controls(markdown: &str) -> String {
+    let mut blocks = markdown_blocks(markdown);
+    while blocks.last().is_some_and(|block| {
+        let block = block.trim();
+        block.is_empty() || is_pagination_controls(block)
+    }) {
+        blocks.pop();
+    }
+    blocks.join("\n\n")
+}
+
+/// Follows the pagination of the article `document` was harvested from,
+/// appending up to `max_pages` following pages to it.
+///
+/// `html` is the fetched HTML of the first page. Stitching stops at the first
+/// page that cannot be fetched or is not HTML, at links back to a page
+/// already merged, and once `cancel` is cancelled.
+pub(crate) fn stitch_pages(
+    mut document: HarvestedDocument,
+    html: &str,
+    max_pages: usize,
+    processor: &ContentProcessor,
+    front_matter: Option<&FrontMatter>,
+    cancel: &CancellationToken,
+    mut fetch: impl FnMut(&str) -> Result<FetchedPage, HarvestError>,
+) -> HarvestedDocument {
+    let mut merged: HashSet<String> = [&document.requested_url, &document.final_url]
+        .into_iter()
+        .filter_map(|url| Url::parse(url).ok())
+        .map(|url| without_fragment(&url).to_string())
+        .collect();
+    let mut html = html.to_string();
+    let mut page_url = document.final_url.clone();
+
+    for _ in 0..max_pages {
+        if cancel.is_cancelled() {
+            break;
+        }
+        let Some(next_url) = next_page_url(&html, &page_url, processor.config()) else {
+            break;
+        };
+        if !merged.insert(next_url.to_string()) {
+            break;
+        }
+        let Ok(page) = fetch(next_url.as_str()) else {
+            break;
+        };
+        if !(200..300).contains(&page.status) || page.pdf.is_some() || !page.body_kind().is_html() {
+            break;
+        }
+        if let Ok(final_url) = Url::parse(&page.final_url)
+            && final_url != next_url
+            && !merged.insert(without_fragment(&final_url).to_string())
+        {
+            break;
+        }
+
+        html = page.body.clone();
+        page_url = page.final_url.clone();
+        let Ok(continuation) = HarvestedDocument::from_page(page, processor, None) else {
+            break;
+        };
+        document.append_continuation(continuation);
+    }
+
+    if !document.continuation_urls.is_empty() {
+        document.markdown = without_trailing_controls(&document.markdown);
+        document.refresh_content(processor, front_matter);
+    }
+    document
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_client::test_page;
+    use std::collections::HashMap;
+
+    fn page(url: &str, body: &str) -> FetchedPage {
+        test_page(url, "text/html; charset=utf-8", body)
+    }
+
+    fn article_page(number: u32, body: &str, pagination: &str) -> String {
+        format!(
+            r#"<html><head><title>Deploying Rust services</title></head><body>
+            <nav><a href="/">Home</a></nav>
+            <article>
+            <h1>Deploying Rust services to production</h1>
+            <p>A guide to shipping a Rust web service, from building the binary to monitoring it.</p>
+            <p>Page {} of 3</p>
+            <p>{}</p>
+            <div class="pagination">{}</div>
+            </article></body></html>"#,
+            number, body, pagination
+        )
+    }
+
+    #[test]
+    fn test_next_page_url_prefers_rel_next() {
+        let config = ExtractionConfig::default();
+        let html = r#"<html><head><link rel="next" href="/guide?page=2"></head>
+            <body><article><p>Intro</p><a href="/other">Next</a></article></body></html>"#;
+
+        let next = next_page_url(html, "https://example.com/guide", &config);
+        assert_eq!(next.unwrap().as_str(), "https://example.com/guide?page=2");
+    }
+
+    #[test]
+    fn test_next_page_url_from_anchor_text_and_page_numbers() {
+        let config = ExtractionConfig::default();
+
+        let by_text = r#"<html><body><article><p>Body</p>
+            <a href="/story/1/">1</a> <a href="/story/2/">Next page »</a></article></body></html>"#;
+        let next = next_page_url(by_text, "https://example.com/story/", &config);
+        assert_eq!(next.unwrap().as_str(), "https://example.com/story/2/");
+
+        let by_number = r#"<html><body><article><p>Body</p>
+            <a href="?id=7&amp;page=1">1</a> <a href="?id=7&amp;page=2">2</a> <a href="?id=7&amp;page=3">3</a>
+            <a href="https://ads.example.net/?page=3">3</a></article></body></html>"#;
+        let next = next_page_url(by_number, "https://example.com/read?id=7&page=2", &config);
+        assert_eq!(
+            next.unwrap().as_str(),
+            "https://example.com/read?id=7&page=3"
+        );
+
+        // Page numbers are followed from a pagination block
+        let in_controls = r#"<html><body><article><p>Body</p>
+            <div class="pagination"><a href="/story/2/">2</a></div></article></body></html>"#;
+        let next = next_page_url(in_controls, "https://example.com/story/", &config);
+        assert_eq!(next.unwrap().as_str(), "https://example.com/story/2/");
+
+        // Sibling articles with numeric IDs are not pages
+        let sibling = r#"<html><body><article><p>Body</p>
+            <p>Read on: <a href="/posts/43">the follow-up</a></p></article></body></html>"#;
+        assert!(next_page_url(sibling, "https://example.com/posts/42", &config).is_none());
+
+        // "Next" links to another post are not pages of this one
+        let next_post = r#"<html><body><article><p>Body</p>
+            <p><a href="/posts/b.html">Next</a></p></article></body></html>"#;
+        assert!(next_page_url(next_post, "https://example.com/posts/a.html", &config).is_none());
+
+        // Pagination outside the content region is not followed
+        let outside = r#"<html><body><article><p>Body</p></article>
+            <footer><a href="/page/2/">Next</a></footer></body></html>"#;
+        assert!(next_page_url(outside, "https://example.com/", &config).is_none());
+    }
+
+    #[test]
+    fn test_without_repeated_lead() {
+        let merged = "# Deploying\n\nA guide to shipping.\n\nPage 1 of 2\n\nBuild the binary.";
+        let continuation = "# Deploying\n\nA guide to shipping.\n\n**Page 2 of 2**\n\nShip it.\n\nA guide to shipping.";
+
+        assert_eq!(
+            without_repeated_lead(merged, continuation),
+            "Ship it.\n\nA guide to shipping."
+        );
+    }
+
+    #[test]
+    fn test_without_trailing_controls() {
+        let markdown = "Intro\n\nPage 2 of 3\n\nNext steps are covered below.\n\n‹ Previous 1 2 3 Next ›\n\nPage 2 of 3";
+        assert_eq!(
+            without_trailing_controls(markdown),
+            "Intro\n\nPage 2 of 3\n\nNext steps are covered below."
+        );
+
+        // Fenced code is kept whole, even when it ends the page unclosed
+        let code = "Counting:\n\n```text\n1 2 3\n\n4 5 6\n```\n\nNext »";
+        assert_eq!(
+            without_trailing_controls(code),
+            "Counting:\n\n```text\n1 2 3\n\n4 5 6\n```"
+        );
+        let unclosed = "Counting:\n\n```text\n1 2 3\n\n4 5 6";
+        assert_eq!(without_trailing_controls(unclosed), unclosed);
+    }
+
+    #[test]
+    fn test_stitch_pages_merges_following_pages() {
+        let first_url = "https://example.com/deploy";
+        let site: HashMap<&str, String> = HashMap::from([
+            (
+                "https://example.com/deploy?page=2",
+                article_page(
+                    2,
+                    "Containers keep the runtime environment identical across machines.",
+                    r#"<a href="/deploy">1</a> <a href="/deploy?page=3">Next</a>"#,
+                ),
+            ),
+            (
+                "https://example.com/deploy?page=3",
+                article_page(
+                    3,
+                    "Metrics and structured logs tell you when a release misbehaves.",
+                    r#"<a href="/deploy?page=2">Previous</a>"#,
+                ),
+            ),
+        ]);
+        let first_html = article_page(
+            1,
+            "Build a release binary with cargo and strip the debug symbols.",
+            r#"<a href="/deploy?page=2">Next page</a>"#,
+        );
+
+        let processor = ContentProcessor::new();
+        let document =
+            HarvestedDocument::from_page(page(first_url, &first_html), &processor, None).unwrap();
+        let cancel = CancellationToken::new();
+        let mut fetched = Vec::new();
+        let document = stitch_pages(document, &first_html, 5, &processor, None, &cancel, |url| {
+            fetched.push(url.to_string());
+            Ok(page(url, &site[url]))
+        });
+
+        assert_eq!(
+            fetched,
+            [
+                "https://example.com/deploy?page=2",
+                "https://example.com/deploy?page=3"
+            ]
+        );
+        assert_eq!(document.continuation_urls, fetched);
+        assert_eq!(
+            document.markdown.matches("Deploying Rust services").count(),
+            1
+        );
+        assert_eq!(document.markdown.matches("A guide to shipping").count(), 1);
+        assert!(!document.markdown.contains("Page 2 of 3"));
+        assert!(!document.markdown.contains("Next"));
+        let release = document.markdown.find("release binary").unwrap();
+        let containers = document.markdown.find("Containers keep").unwrap();
+        let metrics = document.markdown.find("Metrics and structured").unwrap();
+        assert!(release < containers && containers < metrics);
+        assert_eq!(document.content, document.markdown);
+        assert!(document.text.contains("Metrics and structured logs"));
+
+        // The limit is honoured
+        let first =
+            HarvestedDocument::from_page(page(first_url, &first_html), &processor, None).unwrap();
+        let limited = stitch_pages(first, &first_html, 1, &processor, None, &cancel, |url| {
+            Ok(page(url, &site[url]))
+        });
+        assert_eq!(limited.continuation_urls.len(), 1);
+
+        // No page is fetched once the harvest is cancelled
+        let first =
+            HarvestedDocument::from_page(page(first_url, &first_html), &processor, None).unwrap();
+        let stitched = stitch_pages(first, &first_html, 5, &processor, None, &cancel, |url| {
+            cancel.cancel();
+            Ok(page(url, &site[url]))
+        });
+        assert_eq!(
+            stitched.continuation_urls,
+            ["https://example.com/deploy?page=2"]
+        );
+    }
+
+    #[test]
+    fn test_stitch_pages_ignores_next_post_links() {
+        let post_url = "https://blog.example.com/posts/rust-errors.html";
+        let html = r#"<html><body><article><h1>Rust error handling</h1>
+            <p>Errors in Rust are values, returned through Result and propagated with the question mark operator.</p>
+            <p><a href="/posts/rust-errors.html">Permalink</a> · <a href="/posts/tomatoes.html">Next »</a></p>
+            </article></body></html>"#;
+
+        let processor = ContentProcessor::new();
+        let document =
+            HarvestedDocument::from_page(page(post_url, html), &processor, None).unwrap();
+        let cancel = CancellationToken::new();
+        let document = stitch_pages(document, html, 3, &processor, None, &cancel, |url| {
+            panic!("fetched {url}")
+        });
+
+        assert!(document.continuation_urls.is_empty());
+        assert!(document.markdown.contains("Errors in Rust are values"));
+    }
+}
@@ -1,84 +1,101 @@
-/// Returns regex patterns for removing media elements from HTML content.
+/// Returns CSS selectors for removing media elements from HTML content.
 ///
-/// This function provides patterns to identify and remove non-textual elements
+/// This function provides selectors to identify and remove non-textual elements
 /// like images, videos, iframes, and other media that don't contribute to the
-/// readable text content. These elements are typically not useful for text
-/// extraction and Markdown conversion.
+/// readable text content. Matching elements are removed from the parsed document
+/// together with their whole subtree.
 ///
 /// # Returns
 ///
-/// An array of regex pattern strings that match various media elements.
+/// An array of CSS selector strings that match various media elements.
 ///
 /// # Examples
 ///
 /// ```rust
 /// use markdown_harvest::media_elements;
-/// use regex::Regex;
+/// use scraper::{Html, Selector};
 ///
-/// let patterns = media_elements();
-/// assert_eq!(patterns.len(), 9);
+/// let selectors = media_elements();
+/// assert_eq!(selectors.len(), 9);
+/// assert!(selectors.contains(&"img"));
 ///
-/// // Example usage in HTML cleaning
-/// let mut html = r#"<div>Text content <img src="image.jpg"> more text</div>"#.to_string();
-/// for pattern in patterns.iter() {
-///     let regex = Regex::new(pattern).unwrap();
-///     html = regex.replace_all(&html, "").to_string();
-/// }
-/// assert!(!html.contains("<img"));
+/// // Example usage with scraper
+/// let html = Html::parse_fragment(r#"<div>Text content <img src="image.jpg"> more text</div>"#);
+/// let img_selector = Selector::parse(selectors[0]).unwrap();
+/// assert_eq!(html.select(&img_selector).count(), 1);
 /// ```
 pub fn media_elements() -> [&'static str; 9] {
     [
-        r"(?i)<img[^>]*>",
-        r"(?i)<iframe[^>]*>[\s\S]*?</iframe>",
-        r"(?i)<iframe[^>]*/>",
-        r"(?i)<video[^>]*>[\s\S]*?</video>",
-        r"(?i)<audio[^>]*>[\s\S]*?</audio>",
-        r"(?i)<canvas[^>]*>[\s\S]*?</canvas>",
-        r"(?i)<svg[^>]*>[\s\S]*?</svg>",
-        r"(?i)<embed[^>]*>",
-        r"(?i)<object[^>]*>[\s\S]*?</object>",
+        "img", "picture", "iframe", "video", "audio", "canvas", "svg", "embed", "object",
     ]
 }
 
-/// Returns regex patterns for removing unwanted structural elements from HTML.
+/// Returns CSS selectors for removing unwanted structural elements from HTML.
 ///
-/// This function provides patterns to identify and remove navigation bars, headers,
+/// This function provides selectors to identify and remove navigation bars, headers,
 /// footers, sidebars, advertisements, and other structural elements that don't
-/// contain the main content. These elements often contain navigation links,
-/// promotional content, or metadata that shouldn't be included in the extracted text.
+/// contain the main content. Rules match by tag name, ARIA role, or an exact class
+/// or id token, so a class such as `header-gradient` or `loading` is not mistaken
+/// for `header` or `ad`.
 ///
 /// # Returns
 ///
-/// An array of regex pattern strings that match unwanted structural elements.
+/// An array of CSS selector strings that match unwanted structural elements.
 ///
 /// # Examples
 ///
 /// ```rust
 /// use markdown_harvest::unwanted_elements;
-/// use regex::Regex;
-///
-/// let patterns = unwanted_elements();
-/// assert_eq!(patterns.len(), 7);
+/// use scraper::{Html, Selector};
 ///
-/// // Example usage
-/// let mut html = r#"<nav>Navigation</nav><div>Main content</div><footer>Footer</footer>"#.to_string();
-/// for pattern in patterns.iter() {
-///     let regex = Regex::new(pattern).unwrap();
-///     html = regex.replace_all(&html, "").to_string();
-/// }
-/// assert!(!html.contains("<nav>"));
-/// assert!(!html.contains("<footer>"));
-/// assert!(html.contains("Main content"));
+/// let selectors = unwanted_elements();
+/// assert_eq!(selectors.len(), 32);
+/// assert!(selectors.contains(&"nav"));
+/// assert!(selectors.contains(&"footer"));
+///
+/// // Class rules match whole class tokens only
+/// let html = Html::parse_fragment(
+///     r#"<div class="ad">Buy now</div><div class="loading header-gradient">Main content</div>"#,
+/// );
+/// let ad_selector = Selector::parse(":is(div, section, ul, ol):is(.ad, #ad)").unwrap();
+/// let matches: Vec<_> = html.select(&ad_selector).collect();
+/// assert_eq!(matches.len(), 1);
+/// assert_eq!(matches[0].text().collect::<String>(), "Buy now");
 /// ```
-pub fn unwanted_elements() -> [&'static str; 7] {
+pub fn unwanted_elements() -> [&'static str; 32] {
     [
-        r"(?i)<nav[^>]*>[\s\S]*?</nav>",
-        r"(?i)<header[^>]*>[\s\S]*?</header>",
-        r"(?i)<footer[^>]*>[\s\S]*?</footer>",
-        r"(?i)<aside[^>]*>[\s\S]*?</aside>",
-        r"(?i)<div[^>]*class=[^>]*(?:nav|menu|sidebar|advertisement|ad|sponsor|cookie|privacy|social|share|comment|related|avatar|wp-image)[^>]*>[\s\S]*?</div>",
-        r"(?i)<div[^>]*id=[^>]*(?:nav|menu|sidebar|advertisement|ad|sponsor|cookie|privacy|social|share|comment|related)[^>]*>[\s\S]*?</div>",
-        r"(?i)<section[^>]*class=[^>]*(?:nav|menu|sidebar|advertisement|ad|sponsor|cookie|privacy|social|share|comment|related)[^>]*>[\s\S]*?</section>",
+        "nav",
+        "header",
+        "footer",
+        "aside",
+        "[role='navigation']",
+        "[role='banner']",
+        "[role='contentinfo']",
+        "[role='complementary']",
+        "[role='search']",
+        ":is(div, section, ul, ol):is(.nav, #nav)",
+        ":is(div, section, ul, ol):is(.navbar, #navbar)",
+        ":is(div, section, ul, ol):is(.navigation, #navigation)",
+        ":is(div, section, ul, ol):is(.menu, #menu)",
+        ":is(div, section, ul, ol):is(.sidebar, #sidebar)",
+        ":is(div, section, ul, ol):is(.advertisement, #advertisement)",
+        ":is(div, section, ul, ol):is(.ad, #ad)",
+        ":is(div, section, ul, ol):is(.ads, #ads)",
+        ":is(div, section, ul, ol):is(.sponsor, #sponsor)",
+        ":is(div, section, ul, ol):is(.sponsored, #sponsored)",
+        ":is(div, section, ul, ol):is(.cookie, #cookie)",
+        ":is(div, section, ul, ol):is(.cookie-banner, #cookie-banner)",
+        ":is(div, section, ul, ol):is(.cookie-consent, #cookie-consent)",
+        ":is(div, section, ul, ol):is(.privacy, #privacy)",
+        ":is(div, section, ul, ol):is(.social, #social)",
+        ":is(div, section, ul, ol):is(.share, #share)",
+        ":is(div, section, ul, ol):is(.sharing, #sharing)",
+        ":is(div, section, ul, ol):is(.comment, #comment)",
+        ":is(div, section, ul, ol):is(.comments, #comments)",
+        ":is(div, section, ul, ol):is(.related, #related)",
+        ":is(div, section, ul, ol):is(.related-posts, #related-posts)",
+        ":is(div, section, ul, ol):is(.avatar, #avatar)",
+        ":is(div, section, ul, ol):is(.wp-image, #wp-image)",
     ]
 }
 
@@ -161,40 +178,38 @@ pub fn text_selectors() -> [&'static str; 6] {
     ]
 }
 
-/// Returns regex patterns for final cleanup of HTML elements before Markdown conversion.
+/// Returns CSS selectors for final cleanup of HTML elements before Markdown conversion.
 ///
-/// This function provides patterns for removing specific unwanted elements that
-/// might have survived the initial cleaning passes. These patterns target elements
-/// with specific classes or styles that are typically used for non-content purposes
+/// This function provides selectors for removing specific unwanted elements that
+/// might have survived the initial cleaning passes. These selectors target elements
+/// with specific classes or attributes that are typically used for non-content purposes
 /// like user avatars, social sharing buttons, and hidden elements.
 ///
 /// # Returns
 ///
-/// An array of regex pattern strings for final cleanup.
+/// An array of CSS selector strings for final cleanup.
 ///
 /// # Examples
 ///
 /// ```rust
 /// use markdown_harvest::additional_cleanup;
-/// use regex::Regex;
+/// use scraper::{Html, Selector};
 ///
-/// let patterns = additional_cleanup();
-/// assert_eq!(patterns.len(), 3);
+/// let selectors = additional_cleanup();
+/// assert_eq!(selectors.len(), 3);
 ///
 /// // Example usage
-/// let mut html = r#"<div>Content</div><a class="avatar">Avatar</a>"#.to_string();
-/// for pattern in patterns.iter() {
-///     let regex = Regex::new(pattern).unwrap();
-///     html = regex.replace_all(&html, "").to_string();
-/// }
-/// assert!(!html.contains("Avatar"));
-/// assert!(html.contains("Content"));
+/// let html = Html::parse_fragment(r#"<div>Content</div><a class="avatar">Avatar</a>"#);
+/// let avatar_selector = Selector::parse(selectors[0]).unwrap();
+/// let matches: Vec<_> = html.select(&avatar_selector).collect();
+/// assert_eq!(matches.len(), 1);
+/// assert_eq!(matches[0].text().collect::<String>(), "Avatar");
 /// ```
 pub fn additional_cleanup() -> [&'static str; 3] {
     [
-        r"(?i)<a[^>]*class=[^>]*(?:avatar|wp-image|button|btn)[^>]*>[\s\S]*?</a>",
-        r"(?i)<span[^>]*class=[^>]*(?:avatar|wp-image|social|share)[^>]*>[\s\S]*?</span>",
-        r"(?i)<div[^>]*style=[^>]*(?:display:\s*none|visibility:\s*hidden)[^>]*>[\s\S]*?</div>",
+        "a:is(.avatar, .wp-image, .button, .btn)",
+        "span:is(.avatar, .wp-image, .social, .share)",
+        "[hidden], [aria-hidden='true'], [style*='display:none'], [style*='display: none'], [style*='visibility:hidden'], [style*='visibility: hidden']",
     ]
 }
 
@@ -206,6 +221,10 @@ pub fn additional_cleanup() -> [&'static str; 3] {
 /// "follow us", "advertisement", and other non-content text while preserving
 /// the main article content.
 ///
+/// These English patterns apply to every page. Phrases in other languages are
+/// provided by [`BoilerplatePack`](crate::BoilerplatePack)s and only applied to
+/// pages in that language.
+///
 /// # Returns
 ///
 /// An array of regex pattern strings for text cleanup.
@@ -217,7 +236,7 @@ pub fn additional_cleanup() -> [&'static str; 3] {
 /// use regex::Regex;
 ///
 /// let patterns = unwanted_text_patterns();
-/// assert_eq!(patterns.len(), 7);
+/// assert_eq!(patterns.len(), 4);
 ///
 /// // Example usage
 /// let mut text = "This is content. Subscribe to our newsletter. More content here.".to_string();
@@ -229,14 +248,56 @@ pub fn additional_cleanup() -> [&'static str; 3] {
 /// assert!(text.contains("More content"));
 /// assert!(!text.contains("Subscribe"));
 /// ```
-pub fn unwanted_text_patterns() -> [&'static str; 7] {
+pub fn unwanted_text_patterns() -> [&'static str; 4] {
     [
-        r"(?i)\b(advertisement|sponsored|cookie policy|privacy policy|terms of service|subscribe|newsletter|follow us|share this|related articles|recommended|créditos|tópicos|inscreva-se|mantenha-se informado|acesso livre|editor/a)\b",
+        r"(?i)\b(advertisement|sponsored|cookie policy|privacy policy|terms of service|subscribe|newsletter|follow us|share this|related articles|recommended)\b",
         r"(?i)\bclick here\b",
         r"(?i)\bread more\b",
         r"(?i)\bsee also\b",
-        r"(?i)\bver tópicos\b",
-        r"(?i)\bimagem do banner\b",
-        r"(?i)\bfoto:\b.*$",
+    ]
+}
+
+/// Returns single-word terms that identify navigation or metadata lines.
+///
+/// A line consisting of exactly one of these words (case-insensitive) is treated
+/// as a leftover navigation link, button label, or banner and is removed from the
+/// final Markdown. Lines containing other words are never affected.
+///
+/// # Returns
+///
+/// An array of lowercase navigation terms.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::navigation_terms;
+///
+/// let terms = navigation_terms();
+/// assert_eq!(terms.len(), 20);
+/// assert!(terms.contains(&"home"));
+/// assert!(terms.contains(&"subscribe"));
+/// ```
+pub fn navigation_terms() -> [&'static str; 20] {
+    [
+        "home",
+        "about",
+        "contact",
+        "menu",
+        "search",
+        "login",
+        "register",
+        "subscribe",
+        "share",
+        "follow",
+        "back",
+        "next",
+        "prev",
+        "more",
+        "advertisement",
+        "ads",
+        "sponsored",
+        "cookie",
+        "privacy",
+        "terms",
     ]
 }
@@ -0,0 +1,566 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+use once_cell::sync::Lazy;
+use pdf_extract::{Document, MediaBox, OutputDev, OutputError, Transform, decode_text_string};
+use regex::Regex;
+
+use crate::content_processor::PageSpan;
+
+/// Lines set at least this much larger than the body text are headings.
+const HEADING_SIZE_RATIO: f64 = 1.15;
+
+/// Larger lines with more characters than this are kept as paragraphs.
+const MAX_HEADING_CHARS: usize = 120;
+
+/// A vertical gap of more than this many font sizes between two lines starts a
+/// new paragraph.
+const PARAGRAPH_GAP_RATIO: f64 = 1.8;
+
+/// Characters that open a list item when followed by a space.
+const LIST_BULLETS: [char; 6] = ['•', '◦', '▪', '‣', '–', '-'];
+
+/// Lines holding nothing but a page number, such as `12`, `Page 3` or `4 of 10`.
+static PAGE_NUMBER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^(?:page\s+)?\d+(?:\s*(?:/|of)\s*\d+)?$").unwrap());
+
+/// Error raised when a PDF document cannot be converted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PdfError {
+    /// The document is protected by a password.
+    Encrypted,
+    /// The document could not be parsed.
+    Invalid(String),
+}
+
+impl fmt::Display for PdfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PdfError::Encrypted => write!(f, "PDF document is encrypted"),
+            PdfError::Invalid(message) => write!(f, "Invalid PDF document: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for PdfError {}
+
+/// Markdown rebuilt from the text of a PDF document.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct PdfContent {
+    pub(crate) markdown: String,
+    /// Where each page's content lies in `markdown`.
+    pub(crate) pages: Vec<PageSpan>,
+    /// From the document information dictionary.
+    pub(crate) title: Option<String>,
+    pub(crate) author: Option<String>,
+}
+
+/// Extracts the text of a PDF document and rebuilds its structure as Markdown.
+///
+/// Lines set noticeably larger than the body text become headings, one level
+/// per size, lines opening with a bullet become list items, and the remaining
+/// lines are joined into paragraphs, split where the vertical gap between lines
+/// widens. Page numbers and running headers or footers repeated on most pages
+/// are dropped; each page's place in the Markdown is kept as a [`PageSpan`].
+pub(crate) fn convert_pdf(bytes: &[u8]) -> Result<PdfContent, PdfError> {
+    let mut document =
+        Document::load_mem(bytes).map_err(|error| PdfError::Invalid(error.to_string()))?;
+    if document.is_encrypted() && document.decrypt("").is_err() {
+        return Err(PdfError::Encrypted);
+    }
+
+    // The parser panics on some malformed documents instead of failing
+    let mut collector = LineCollector::default();
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        pdf_extract::output_doc(&document, &mut collector)
+    }))
+    .map_err(|_| PdfError::Invalid("unsupported document structure".to_string()))?
+    .map_err(|error| PdfError::Invalid(error.to_string()))?;
+    collector.finish_line();
+
+    let (markdown, pages) = lines_to_markdown(collector.lines);
+    Ok(PdfContent {
+        markdown,
+        pages,
+        title: info_text(&document, b"Title"),
+        author: info_text(&document, b"Author"),
+    })
+}
+
+/// Reads a text entry of the document information dictionary.
+fn info_text(document: &Document, key: &[u8]) -> Option<String> {
+    let info = document.trailer.get(b"Info").ok()?;
+    let info = match info.as_reference() {
+        Ok(id) => document.get_dictionary(id).ok()?,
+        Err(_) => info.as_dict().ok()?,
+    };
+    let text = decode_text_string(info.get(key).ok()?).ok()?;
+    Some(text.trim().to_string()).filter(|text| !text.is_empty())
+}
+
+/// A line of text on a page, with the largest font size used in it.
+#[derive(Debug, Clone, PartialEq)]
+struct TextLine {
+    page: u32,
+    text: String,
+    font_size: f64,
+    /// Baseline position, measured from the top of the page.
+    top: f64,
+}
+
+/// Collects the characters drawn on each page into lines, breaking lines where
+/// the text moves vertically and inserting spaces where it jumps forward.
+#[derive(Default)]
+struct LineCollector {
+    lines: Vec<TextLine>,
+    line: Option<TextLine>,
+    page: u32,
+    page_height: f64,
+    last_end: f64,
+    last_top: f64,
+    new_word: bool,
+}
+
+impl LineCollector {
+    fn finish_line(&mut self) {
+        if let Some(mut line) = self.line.take() {
+            line.text = line.text.trim().to_string();
+            if !line.text.is_empty() {
+                self.lines.push(line);
+            }
+        }
+    }
+}
+
+impl OutputDev for LineCollector {
+    fn begin_page(
+        &mut self,
+        page_num: u32,
+        media_box: &MediaBox,
+        _art_box: Option<(f64, f64, f64, f64)>,
+    ) -> Result<(), OutputError> {
+        self.finish_line();
+        self.page = page_num;
+        self.page_height = media_box.ury - media_box.lly;
+        Ok(())
+    }
+
+    fn end_page(&mut self) -> Result<(), OutputError> {
+        self.finish_line();
+        Ok(())
+    }
+
+    fn output_character(
+        &mut self,
+        trm: &Transform,
+        width: f64,
+        _spacing: f64,
+        font_size: f64,
+        char: &str,
+    ) -> Result<(), OutputError> {
+        let (x, top) = (trm.m31, self.page_height - trm.m32);
+        // Side of the square with the area of the transformed font box
+        let size_x = font_size * trm.m11 + font_size * trm.m21;
+        let size_y = font_size * trm.m12 + font_size * trm.m22;
+        let size = (size_x * size_y).abs().sqrt();
+
+        if self.new_word
+            && let Some(line) = &mut self.line
+        {
+            if (top - self.last_top).abs() > size * 0.5 {
+                self.finish_line();
+            } else if x > self.last_end + size * 0.1 && !line.text.ends_with(' ') {
+                line.text.push(' ');
+            }
+        }
+        let line = self.line.get_or_insert_with(|| TextLine {
+            page: self.page,
+            text: String::new(),
+            font_size: size,
+            top,
+        });
+        line.text.push_str(char);
+        if !char.trim().is_empty() {
+            line.font_size = line.font_size.max(size);
+        }
+
+        self.new_word = false;
+        self.last_top = top;
+        self.last_end = x + width * size;
+        Ok(())
+    }
+
+    fn begin_word(&mut self) -> Result<(), OutputError> {
+        self.new_word = true;
+        Ok(())
+    }
+
+    fn end_word(&mut self) -> Result<(), OutputError> {
+        Ok(())
+    }
+
+    fn end_line(&mut self) -> Result<(), OutputError> {
+        Ok(())
+    }
+}
+
+/// A block of Markdown rebuilt from one or more lines.
+enum Block {
+    Heading(usize, String),
+    ListItem(String),
+    Paragraph(String),
+}
+
+impl Block {
+    fn render(&self) -> String {
+        match self {
+            Block::Heading(level, text) => format!("{} {}", "#".repeat(*level), text),
+            Block::ListItem(text) => format!("- {}", text),
+            Block::Paragraph(text) => text.clone(),
+        }
+    }
+}
+
+fn lines_to_markdown(lines: Vec<TextLine>) -> (String, Vec<PageSpan>) {
+    let lines = drop_page_furniture(lines);
+    let body_size = body_font_size(&lines);
+    let mut heading_sizes: Vec<i64> = lines
+        .iter()
+        .filter(|line| is_heading(line, body_size))
+        .map(|line| size_key(line.font_size))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    heading_sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut markdown = String::new();
+    let mut pages = Vec::new();
+    for page_lines in lines.chunk_by(|a, b| a.page == b.page) {
+        let blocks = page_blocks(page_lines, body_size, &heading_sizes);
+        if blocks.is_empty() {
+            continue;
+        }
+        if !markdown.is_empty() {
+            markdown.push_str("\n\n");
+        }
+        let start = markdown.len();
+        for (index, block) in blocks.iter().enumerate() {
+            if index > 0 {
+                let tight = matches!(
+                    (&blocks[index - 1], block),
+                    (Block::ListItem(_), Block::ListItem(_))
+                );
+                markdown.push_str(if tight { "\n" } else { "\n\n" });
+            }
+            markdown.push_str(&block.render());
+        }
+        pages.push(PageSpan {
+            page: page_lines[0].page,
+            start,
+            end: markdown.len(),
+        });
+    }
+    (markdown, pages)
+}
+
+fn page_blocks(lines: &[TextLine], body_size: f64, heading_sizes: &[i64]) -> Vec<Block> {
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut previous: Option<&TextLine> = None;
+
+    for line in lines {
+        let close = previous.is_some_and(|previous| {
+            let gap = line.top - previous.top;
+            gap > 0.0 && gap <= line.font_size.max(previous.font_size) * PARAGRAPH_GAP_RATIO
+        });
+        let same_size = previous
+            .is_some_and(|previous| size_key(previous.font_size) == size_key(line.font_size));
+        previous = Some(line);
+
+        if is_heading(line, body_size) {
+            let level = heading_sizes
+                .iter()
+                .position(|size| *size == size_key(line.font_size))
+                .map_or(1, |index| (index + 1).min(6));
+            match blocks.last_mut() {
+                Some(Block::Heading(last_level, text)) if *last_level == level && close => {
+                    text.push(' ');
+                    text.push_str(&line.text);
+                }
+                _ => blocks.push(Block::Heading(level, line.text.clone())),
+            }
+            continue;
+        }
+
+        if let Some(item) = list_item_text(&line.text) {
+            blocks.push(Block::ListItem(item.to_string()));
+            continue;
+        }
+
+        match blocks.last_mut() {
+            Some(Block::Paragraph(text) | Block::ListItem(text)) if close && same_size => {
+                join_line(text, &line.text);
+            }
+            _ => blocks.push(Block::Paragraph(line.text.clone())),
+        }
+    }
+    blocks
+}
+
+/// Appends a wrapped line, rejoining words hyphenated across the break.
+fn join_line(text: &mut String, line: &str) {
+    let hyphenated = text.ends_with('-')
+        && !text.ends_with(" -")
+        && line.chars().next().is_some_and(char::is_lowercase);
+    if hyphenated {
+        text.pop();
+    } else {
+        text.push(' ');
+    }
+    text.push_str(line);
+}
+
+fn list_item_text(text: &str) -> Option<&str> {
+    let mut chars = text.chars();
+    let bullet = chars.next()?;
+    let rest = chars.as_str();
+    (LIST_BULLETS.contains(&bullet) && rest.starts_with(char::is_whitespace))
+        .then(|| rest.trim_start())
+        .filter(|item| !item.is_empty())
+}
+
+fn is_heading(line: &TextLine, body_size: f64) -> bool {
+    line.font_size >= body_size * HEADING_SIZE_RATIO
+        && line.text.chars().count() <= MAX_HEADING_CHARS
+}
+
+/// Font sizes rounded to half points, to compare sizes set alike.
+fn size_key(font_size: f64) -> i64 {
+    (font_size * 2.0).round() as i64
+}
+
+/// The font size most of the text is set in.
+fn body_font_size(lines: &[TextLine]) -> f64 {
+    let mut chars_by_size: HashMap<i64, usize> = HashMap::new();
+    for line in lines {
+        *chars_by_size.entry(size_key(line.font_size)).or_default() += line.text.chars().count();
+    }
+    chars_by_size
+        .into_iter()
+        .max_by_key(|(size, chars)| (*chars, -size))
+        .map_or(0.0, |(size, _)| size as f64 / 2.0)
+}
+
+/// Drops page numbers, and running headers and footers: lines repeated on at
+/// least half of the pages of a document of three pages or more.
+fn drop_page_furniture(lines: Vec<TextLine>) -> Vec<TextLine> {
+    let mut pages_by_text: HashMap<&str, HashSet<u32>> = HashMap::new();
+    for line in &lines {
+        pages_by_text
+            .entry(&line.text)
+            .or_default()
+            .insert(line.page);
+    }
+    let page_count = lines
+        .iter()
+        .map(|line| line.page)
+        .collect::<HashSet<_>>()
+        .len();
+    let repeated: HashSet<String> = pages_by_text
+        .into_iter()
+        .filter(|(_, pages)| page_count >= 3 && pages.len() * 2 >= page_count)
+        .map(|(text, _)| text.to_string())
+        .collect();
+
+    lines
+        .into_iter()
+        .filter(|line| !PAGE_NUMBER_REGEX.is_match(&line.text) && !repeated.contains(&line.text))
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use pdf_extract::content::{Content, Operation};
+    use pdf_extract::{Object, Stream, dictionary};
+
+    /// Builds a PDF with one page per entry, each line given as
+    /// `(font size, baseline from the bottom, text)`.
+    pub(crate) fn build_pdf(title: &str, pages: &[&[(i64, i64, &str)]]) -> Vec<u8> {
+        let mut document = Document::with_version("1.5");
+        let pages_id = document.new_object_id();
+        let font_id = document.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+            "Encoding" => "WinAnsiEncoding",
+        });
+        let resources_id = document.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font_id },
+        });
+
+        let kids: Vec<Object> = pages
+            .iter()
+            .map(|lines| {
+                let mut operations = vec![Operation::new("BT", vec![])];
+                for (size, baseline, text) in lines.iter() {
+                    operations.extend([
+                        Operation::new("Tf", vec!["F1".into(), (*size).into()]),
+                        Operation::new(
+                            "Tm",
+                            vec![
+                                1.into(),
+                                0.into(),
+                                0.into(),
+                                1.into(),
+                                72.into(),
+                                (*baseline).into(),
+                            ],
+                        ),
+                        Operation::new("Tj", vec![Object::string_literal(win_ansi(text))]),
+                    ]);
+                }
+                operations.push(Operation::new("ET", vec![]));
+                let content = Content { operations };
+                let content_id =
+                    document.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+                document
+                    .add_object(dictionary! {
+                        "Type" => "Page",
+                        "Parent" => pages_id,
+                        "Contents" => content_id,
+                    })
+                    .into()
+            })
+            .collect();
+
+        let count = kids.len() as i64;
+        document.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => count,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+            }),
+        );
+        let catalog_id = document.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        let info_id = document.add_object(dictionary! {
+            "Title" => Object::string_literal(title),
+        });
+        document.trailer.set("Root", catalog_id);
+        document.trailer.set("Info", info_id);
+
+        let mut bytes = Vec::new();
+        document.save_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    /// Encodes the few non-ASCII characters the fixtures use in WinAnsiEncoding.
+    fn win_ansi(text: &str) -> Vec<u8> {
+        text.chars()
+            .map(|c| match c {
+                '•' => 0x95,
+                '–' => 0x96,
+                c => c as u8,
+            })
+            .collect()
+    }
+
+    pub(crate) fn report_pdf() -> Vec<u8> {
+        build_pdf(
+            "Annual Report 2025",
+            &[
+                &[
+                    (24, 700, "Annual Report"),
+                    (16, 660, "Introduction"),
+                    (11, 636, "This report summarises the year for the whole"),
+                    (11, 622, "organisation and its partners across every re-"),
+                    (11, 608, "gion we operate in."),
+                    (11, 580, "A second paragraph follows after a wider gap."),
+                    (9, 40, "1"),
+                ],
+                &[
+                    (16, 700, "Results"),
+                    (11, 676, "Revenue and reach both grew:"),
+                    (11, 656, "• Revenue grew by 12 percent"),
+                    (11, 642, "• Reach doubled in new markets"),
+                    (9, 40, "Page 2 of 2"),
+                ],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_convert_pdf_rebuilds_structure() {
+        let pdf = convert_pdf(&report_pdf()).unwrap();
+
+        assert_eq!(
+            pdf.markdown,
+            "# Annual Report\n\n\
+             ## Introduction\n\n\
+             This report summarises the year for the whole organisation and its partners \
+             across every region we operate in.\n\n\
+             A second paragraph follows after a wider gap.\n\n\
+             ## Results\n\n\
+             Revenue and reach both grew:\n\n\
+             - Revenue grew by 12 percent\n\
+             - Reach doubled in new markets"
+        );
+        assert_eq!(pdf.title.as_deref(), Some("Annual Report 2025"));
+        assert_eq!(pdf.author, None);
+
+        assert_eq!(pdf.pages.len(), 2);
+        let second = &pdf.pages[1];
+        assert_eq!(second.page, 2);
+        assert!(pdf.markdown[second.start..second.end].starts_with("## Results"));
+        assert_eq!(second.end, pdf.markdown.len());
+    }
+
+    #[test]
+    fn test_convert_pdf_drops_running_headers() {
+        let page = |text: &'static str| -> [(i64, i64, &'static str); 2] {
+            [(9, 760, "Quarterly Bulletin"), (11, 700, text)]
+        };
+        let (first, second, third) = (
+            page("First page text."),
+            page("Second page text."),
+            page("Third page text."),
+        );
+        let pdf = convert_pdf(&build_pdf("", &[&first, &second, &third])).unwrap();
+
+        assert_eq!(
+            pdf.markdown,
+            "First page text.\n\nSecond page text.\n\nThird page text."
+        );
+        assert_eq!(pdf.title, None);
+    }
+
+    #[test]
+    fn test_convert_pdf_rejects_invalid_documents() {
+        assert!(matches!(
+            convert_pdf(b"<html>not a pdf</html>"),
+            Err(PdfError::Invalid(_))
+        ));
+        assert!(matches!(convert_pdf(b""), Err(PdfError::Invalid(_))));
+
+        let report = report_pdf();
+        assert!(matches!(
+            convert_pdf(&report[..report.len() / 2]),
+            Err(PdfError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_convert_pdf_without_text() {
+        let pdf = convert_pdf(&build_pdf("  ", &[&[], &[]])).unwrap();
+
+        assert_eq!(pdf.markdown, "");
+        assert!(pdf.pages.iter().all(|span| span.start == span.end));
+        assert_eq!(pdf.title, None);
+    }
+}
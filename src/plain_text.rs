@@ -0,0 +1,158 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Match, Regex};
+
+static IMAGE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"!\[[^\]]*\]\([^)]*\)").unwrap());
+
+static INLINE_LINK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[([^\]]*)\]\((?:[^()\s]|\([^()\s]*\))*(?:\s+[^)]*)?\)").unwrap());
+
+static REFERENCE_LINK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[([^\]]*)\]\[[^\]]*\]").unwrap());
+
+static REFERENCE_DEFINITION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s{0,3}\[[^\]]+\]:\s+\S+").unwrap());
+
+static EMPHASIS_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\*\*|__|\*|~~)([^*_~\n]+?)(\*\*|__|\*|~~)").unwrap());
+
+static LIST_MARKER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\s*)(?:[-*+]|(\d+)[.)])\s+").unwrap());
+
+static SETEXT_UNDERLINE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(?:=+|-+)\s*$").unwrap());
+
+static TABLE_SEPARATOR_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\|?(?:\s*:?-{3,}:?\s*\|)+\s*:?-{0,}:?\s*\|?$").unwrap());
+
+static ESCAPE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\\([\\`*_{}\[\]()#+\-.!|>~])").unwrap());
+
+/// Reduces Markdown to plain text.
+///
+/// Headings, emphasis, quote markers, link and image syntax, reference
+/// definitions and table rules are removed. List items keep a `•` bullet or
+/// their number, indented two spaces per nesting level. Code inside fenced
+/// blocks is kept verbatim without its fences, and table cells are separated
+/// by tabs.
+pub(crate) fn markdown_to_plain_text(markdown: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut fence: Option<String> = None;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+
+        if let Some(open) = &fence {
+            if trimmed.starts_with(open.as_str())
+                && trimmed.trim_start_matches('`').trim().is_empty()
+            {
+                fence = None;
+            } else {
+                lines.push(line.to_string());
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            let ticks = trimmed.chars().take_while(|c| *c == '`').count();
+            fence = Some("`".repeat(ticks));
+            continue;
+        }
+
+        if SETEXT_UNDERLINE_REGEX.is_match(line)
+            || TABLE_SEPARATOR_REGEX.is_match(trimmed)
+            || REFERENCE_DEFINITION_REGEX.is_match(line)
+        {
+            continue;
+        }
+
+        lines.push(inline_to_plain_text(line));
+    }
+
+    let text = lines.join("\n");
+    let blank_lines = Regex::new(r"\n{3,}").unwrap();
+    blank_lines.replace_all(text.trim(), "\n\n").to_string()
+}
+
+fn inline_to_plain_text(line: &str) -> String {
+    let mut text = line.trim_start().to_string();
+
+    // Block markers
+    text = text.trim_start_matches('>').trim_start().to_string();
+    if text.starts_with('#') {
+        text = text
+            .trim_start_matches('#')
+            .trim()
+            .trim_end_matches('#')
+            .trim()
+            .to_string();
+    }
+    text = LIST_MARKER_REGEX
+        .replace(&text, |caps: &Captures| list_marker(line, caps.get(2)))
+        .to_string();
+
+    // Table rows become tab separated cells
+    if text.starts_with('|') && text.ends_with('|') && text.len() > 1 {
+        text = text[1..text.len() - 1]
+            .split(" | ")
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join("\t");
+    }
+
+    // Inline markup
+    text = IMAGE_REGEX.replace_all(&text, "").to_string();
+    text = INLINE_LINK_REGEX.replace_all(&text, "$1").to_string();
+    text = REFERENCE_LINK_REGEX.replace_all(&text, "$1").to_string();
+    text = EMPHASIS_REGEX.replace_all(&text, "$2").to_string();
+    text = text.replace('`', "");
+    text = ESCAPE_REGEX.replace_all(&text, "$1").to_string();
+
+    text.trim_end().to_string()
+}
+
+/// Renders a list marker textually, indented by the nesting level of `line`.
+fn list_marker(line: &str, number: Option<Match>) -> String {
+    let indent = line.len() - line.trim_start().len();
+    let marker = match number {
+        Some(number) => format!("{}.", number.as_str()),
+        None => "•".to_string(),
+    };
+    format!("{}{} ", "  ".repeat(indent / 2), marker)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_plain_text() {
+        let markdown = "Release notes\n==========\n\n## Highlights ##\n\n\
+            - **Faster** parsing with [the new engine](https://example.com/engine)\n\
+            - See the [guide][1] and ![logo](/logo.png)\n\n\
+            > Quoted `code` and snake\\_case\n\n\
+            ```rust\nlet a = *b;\n```\n\n\
+            | Plan | Price |\n| --- | --- |\n| Pro | $10 |\n\n\
+            [1]: https://example.com/guide";
+
+        assert_eq!(
+            markdown_to_plain_text(markdown),
+            "Release notes\n\nHighlights\n\n• Faster parsing with the new engine\n\
+             • See the guide and\n\nQuoted code and snake_case\n\nlet a = *b;\n\n\
+             Plan\tPrice\nPro\t$10"
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_plain_text_nested_lists() {
+        let markdown = "1. Install\n2. Configure\n    * Proxy\n    * Timeouts";
+
+        assert_eq!(
+            markdown_to_plain_text(markdown),
+            "1. Install\n2. Configure\n    • Proxy\n    • Timeouts"
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_plain_text_empty() {
+        assert_eq!(markdown_to_plain_text(""), "");
+    }
+}
@@ -0,0 +1,357 @@
+use std::fmt;
+use std::time::Instant;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::content_processor::ContentProcessor;
+use crate::front_matter::FrontMatter;
+use crate::harvest_error::HarvestError;
+use crate::harvested_document::HarvestedDocument;
+use crate::http_client::FetchedPage;
+
+/// Pages with less plain text than this are rendered under the default
+/// [`RenderPolicy`].
+pub const DEFAULT_THIN_CONTENT_CHARS: usize = 500;
+
+/// Renders pages whose content only appears once their JavaScript has run.
+///
+/// A renderer sits between fetching and the [`ContentProcessor`]: when the
+/// [`RenderPolicy`] asks for it, the rendered HTML replaces the fetched body
+/// and the extraction runs again. The default renderer, [`NoopRenderer`],
+/// renders nothing; with the `chromium` feature, `ChromiumRenderer` drives a
+/// locally installed headless Chromium.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::{
+///     CancellationToken, FetchedPage, HarvestConfig, RenderError, RenderPolicy, Renderer,
+/// };
+///
+/// /// Serves pre-rendered snapshots, e.g. from a prerendering service.
+/// struct SnapshotRenderer;
+///
+/// impl Renderer for SnapshotRenderer {
+///     fn name(&self) -> &str {
+///         "snapshots"
+///     }
+///
+///     fn render(
+///         &self,
+///         page: &FetchedPage,
+///         _cancel: &CancellationToken,
+///     ) -> Result<Option<String>, RenderError> {
+///         if page.final_url.starts_with("https://app.example.com/") {
+///             Ok(Some("<html><body><article><p>Rendered.</p></article></body></html>".into()))
+///         } else {
+///             Ok(None)
+///         }
+///     }
+/// }
+///
+/// let config = HarvestConfig::builder()
+///     .renderer(SnapshotRenderer)
+///     .render_policy(RenderPolicy::IfThin(200))
+///     .build();
+/// assert_eq!(config.renderer().name(), "snapshots");
+/// ```
+pub trait Renderer: Send + Sync {
+    /// Short identifier of the renderer, e.g. `"chromium"`.
+    fn name(&self) -> &str;
+
+    /// Returns the HTML of the fetched `page` once rendered, or `None` to keep
+    /// the fetched HTML.
+    ///
+    /// `page` carries the URL the fetch ended on after redirects and the
+    /// fetched body, so a renderer can load the final URL directly or render
+    /// the body it was given instead of fetching the page again. `cancel` is
+    /// cancelled when the asynchronous batch harvesting the page passes its
+    /// [deadline](crate::HarvestConfigBuilder::deadline) or is cancelled; a
+    /// renderer should then stop with [`RenderError::Cancelled`].
+    fn render(
+        &self,
+        page: &FetchedPage,
+        cancel: &CancellationToken,
+    ) -> Result<Option<String>, RenderError>;
+
+    /// Whether this renderer never renders anything, in which case pages are
+    /// extracted once without checking the [`RenderPolicy`].
+    fn is_noop(&self) -> bool {
+        false
+    }
+}
+
+/// The default [`Renderer`], which leaves every page as fetched.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopRenderer;
+
+impl Renderer for NoopRenderer {
+    fn name(&self) -> &str {
+        "noop"
+    }
+
+    fn render(
+        &self,
+        _page: &FetchedPage,
+        _cancel: &CancellationToken,
+    ) -> Result<Option<String>, RenderError> {
+        Ok(None)
+    }
+
+    fn is_noop(&self) -> bool {
+        true
+    }
+}
+
+/// When the configured [`Renderer`] is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RenderPolicy {
+    /// Never render; the fetched HTML is always used.
+    Never,
+    /// Render every page.
+    Always,
+    /// Render pages whose static extraction yields fewer plain-text
+    /// characters than this.
+    IfThin(usize),
+}
+
+impl Default for RenderPolicy {
+    fn default() -> Self {
+        RenderPolicy::IfThin(DEFAULT_THIN_CONTENT_CHARS)
+    }
+}
+
+/// Error raised by a [`Renderer`].
+///
+/// A failed rendering never fails the harvest: the document extracted from the
+/// fetched HTML is kept and the error is recorded in
+/// [`HarvestedDocument::render_error`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderError {
+    /// The browser could not be started.
+    Launch(String),
+    /// The browser did not render the page in time.
+    Timeout,
+    /// The browser failed to load or report the page.
+    Protocol(String),
+    /// The harvest stopped before the page was rendered.
+    Cancelled,
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderError::Launch(message) => write!(f, "Error launching browser: {}", message),
+            RenderError::Timeout => write!(f, "Timed out rendering page"),
+            RenderError::Protocol(message) => write!(f, "Error rendering page: {}", message),
+            RenderError::Cancelled => write!(f, "Rendering cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+/// Extracts a fetched page, rendering it first when `policy` asks for it.
+///
+/// The fetched HTML is always extracted first, so [`RenderPolicy::IfThin`]
+/// can compare its text against the threshold and a failed rendering can fall
+/// back to it. Only HTML pages are rendered, never PDF, text or source files.
+/// With a no-op renderer the page is extracted once and nothing is kept for
+/// rendering; once `cancel` is cancelled, pages are no longer rendered.
+pub(crate) fn harvest_page(
+    page: FetchedPage,
+    processor: &ContentProcessor,
+    front_matter: Option<&FrontMatter>,
+    renderer: &dyn Renderer,
+    policy: RenderPolicy,
+    cancel: &CancellationToken,
+) -> Result<HarvestedDocument, HarvestError> {
+    let policy = if renderer.is_noop() {
+        RenderPolicy::Never
+    } else {
+        policy
+    };
+    let fetched = match policy {
+        RenderPolicy::Never => None,
+        _ if page.pdf.is_some() || !page.body_kind().is_html() => None,
+        _ => Some(page.clone()),
+    };
+    let document = HarvestedDocument::from_page(page, processor, front_matter)?;
+
+    let needs_rendering = match policy {
+        RenderPolicy::Never => false,
+        RenderPolicy::Always => true,
+        RenderPolicy::IfThin(min_chars) => document.text.chars().count() < min_chars,
+    };
+    let Some(fetched) = fetched.filter(|_| needs_rendering && !cancel.is_cancelled()) else {
+        return Ok(document);
+    };
+
+    let started = Instant::now();
+    match renderer.render(&fetched, cancel) {
+        Ok(Some(body)) => {
+            let render_ms = started.elapsed().as_millis() as u64;
+            let page = FetchedPage { body, ..fetched };
+            let mut rendered = HarvestedDocument::from_page(page, processor, front_matter)?;
+            rendered.rendered_by = Some(renderer.name().to_string());
+            rendered.timings.render_ms = render_ms;
+            Ok(rendered)
+        }
+        Ok(None) => Ok(document),
+        Err(error) => Ok(HarvestedDocument {
+            render_error: Some(error.to_string()),
+            ..document
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_client::test_page;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// Serves canned HTML and records the URLs it was asked to render.
+    #[derive(Default)]
+    struct StubRenderer {
+        html: Option<&'static str>,
+        rendered: Mutex<Vec<String>>,
+    }
+
+    impl Renderer for StubRenderer {
+        fn name(&self) -> &str {
+            "stub"
+        }
+
+        fn render(
+            &self,
+            page: &FetchedPage,
+            _cancel: &CancellationToken,
+        ) -> Result<Option<String>, RenderError> {
+            self.rendered.lock().unwrap().push(page.final_url.clone());
+            match self.html {
+                Some(html) => Ok(Some(html.to_string())),
+                None => Err(RenderError::Timeout),
+            }
+        }
+    }
+
+    const RENDERED_HTML: &str = "<html><body><article><h1>Dashboard</h1>\
+        <p>Rendered by the browser once the application bundle has loaded.</p>\
+        </article></body></html>";
+
+    fn page(body: &str) -> FetchedPage {
+        FetchedPage {
+            requested_url: "https://app.example.com/".to_string(),
+            fetch_duration: Duration::from_millis(20),
+            ..test_page("https://app.example.com/home", "text/html", body)
+        }
+    }
+
+    #[test]
+    fn test_harvest_page_renders_thin_pages() {
+        let processor = ContentProcessor::new();
+        let renderer = StubRenderer {
+            html: Some(RENDERED_HTML),
+            ..StubRenderer::default()
+        };
+        let shell = page(r#"<html><body><div id="root"></div></body></html>"#);
+        let cancel = CancellationToken::new();
+
+        let document = harvest_page(
+            shell.clone(),
+            &processor,
+            None,
+            &renderer,
+            RenderPolicy::default(),
+            &cancel,
+        )
+        .unwrap();
+        assert!(document.markdown.contains("Rendered by the browser"));
+        assert_eq!(document.rendered_by.as_deref(), Some("stub"));
+        assert_eq!(document.sizes.html_bytes, RENDERED_HTML.len());
+        assert_eq!(document.timings.fetch_ms, 20);
+        assert_eq!(
+            *renderer.rendered.lock().unwrap(),
+            ["https://app.example.com/home"]
+        );
+
+        let document = harvest_page(
+            shell.clone(),
+            &processor,
+            None,
+            &renderer,
+            RenderPolicy::Never,
+            &cancel,
+        )
+        .unwrap();
+        assert_eq!(document.markdown, "");
+        assert_eq!(document.rendered_by, None);
+        assert_eq!(renderer.rendered.lock().unwrap().len(), 1);
+
+        // Once the harvest is cancelled, the fetched HTML is kept
+        cancel.cancel();
+        let document = harvest_page(
+            shell,
+            &processor,
+            None,
+            &renderer,
+            RenderPolicy::Always,
+            &cancel,
+        )
+        .unwrap();
+        assert_eq!(document.rendered_by, None);
+        assert_eq!(renderer.rendered.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_harvest_page_keeps_static_content() {
+        let processor = ContentProcessor::new();
+        let renderer = StubRenderer::default();
+        let cancel = CancellationToken::new();
+        let article = page(
+            "<html><body><article><p>The static page already carries the article.</p>\
+             </article></body></html>",
+        );
+
+        let document = harvest_page(
+            article.clone(),
+            &processor,
+            None,
+            &renderer,
+            RenderPolicy::IfThin(10),
+            &cancel,
+        )
+        .unwrap();
+        assert_eq!(document.rendered_by, None);
+        assert!(renderer.rendered.lock().unwrap().is_empty());
+
+        // A failed rendering falls back to the fetched HTML
+        let document = harvest_page(
+            article,
+            &processor,
+            None,
+            &renderer,
+            RenderPolicy::Always,
+            &cancel,
+        )
+        .unwrap();
+        assert!(document.markdown.contains("already carries the article"));
+        let error = document.render_error.as_deref();
+        assert_eq!(error, Some("Timed out rendering page"));
+
+        let document = harvest_page(
+            page("<html><body></body></html>"),
+            &processor,
+            None,
+            &NoopRenderer,
+            RenderPolicy::Always,
+            &cancel,
+        )
+        .unwrap();
+        assert_eq!(document.rendered_by, None);
+        assert_eq!(document.render_error, None);
+    }
+}
@@ -0,0 +1,503 @@
+use crate::content_processor::remove_elements;
+use regex::Regex;
+use scraper::{Html, Selector};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+/// Extracts the main content of pages from a specific site.
+///
+/// Site extractors are consulted by [`ContentProcessor`](crate::ContentProcessor) before
+/// its generic extraction heuristics. When an extractor matches the page URL and returns
+/// `Some(html)`, that HTML replaces the generic main-content detection; it still goes
+/// through the regular cleaning and Markdown conversion steps.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::{ContentProcessor, SiteExtractor};
+/// use scraper::{Html, Selector};
+///
+/// struct ChangelogExtractor;
+///
+/// impl SiteExtractor for ChangelogExtractor {
+///     fn name(&self) -> &str {
+///         "changelog"
+///     }
+///
+///     fn matches(&self, host: &str, path: &str) -> bool {
+///         host == "example.com" && path.starts_with("/changelog")
+///     }
+///
+///     fn extract(&self, document: &Html) -> Option<String> {
+///         let selector = Selector::parse("#releases").ok()?;
+///         document.root_element().select(&selector).next().map(|element| element.html())
+///     }
+/// }
+///
+/// let mut processor = ContentProcessor::new();
+/// processor.register_site_extractor(ChangelogExtractor);
+///
+/// let html = r#"<html><body><div class="promo"><p>Try the beta!</p></div>
+///     <div id="releases"><p>Version 2.0 released today.</p></div></body></html>"#;
+/// let markdown = processor.html_to_markdown_with_url(html, "https://example.com/changelog");
+/// assert!(markdown.contains("Version 2.0 released today."));
+/// assert!(!markdown.contains("Try the beta!"));
+/// ```
+pub trait SiteExtractor: Send + Sync {
+    /// Short identifier of the extractor, e.g. `"wikipedia"`.
+    fn name(&self) -> &str;
+
+    /// Returns `true` if this extractor handles pages at the given host and path.
+    fn matches(&self, host: &str, path: &str) -> bool;
+
+    /// Returns the HTML of the page's main content, or `None` to fall back to
+    /// the generic extraction pipeline.
+    fn extract(&self, document: &Html) -> Option<String>;
+}
+
+/// A [`SiteExtractor`] driven by host and path patterns plus CSS selectors.
+///
+/// The host pattern matches the host itself and any of its subdomains, so
+/// `wikipedia.org` covers `en.wikipedia.org`. The optional path pattern is a
+/// regular expression tested against the URL path. All elements matched by the
+/// content selectors are concatenated in document order, a match nested in another
+/// one being taken only once, as part of the outer one; then elements matched by
+/// the removal selectors are dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::{SelectorExtractor, SiteExtractor};
+///
+/// let extractor = SelectorExtractor::new("handbook", "docs.example.com")
+///     .path_pattern(r"^/handbook/")
+///     .content_selector("#handbook-body")
+///     .removal_selector(".edit-link");
+///
+/// assert!(extractor.matches("docs.example.com", "/handbook/intro"));
+/// assert!(!extractor.matches("docs.example.com", "/blog/post"));
+/// ```
+#[derive(Clone)]
+pub struct SelectorExtractor {
+    name: String,
+    hosts: Vec<String>,
+    path_pattern: Option<Regex>,
+    content_selectors: Vec<String>,
+    removal_selectors: Vec<String>,
+}
+
+impl SelectorExtractor {
+    /// Creates an extractor for the given host (including its subdomains).
+    pub fn new(name: impl Into<String>, host: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            hosts: vec![host.into().to_lowercase()],
+            path_pattern: None,
+            content_selectors: Vec::new(),
+            removal_selectors: Vec::new(),
+        }
+    }
+
+    /// Adds another host (including its subdomains) handled by this extractor.
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        self.hosts.push(host.into().to_lowercase());
+        self
+    }
+
+    /// Restricts the extractor to paths matching the given regular expression.
+    ///
+    /// An invalid pattern never matches, so the extractor is effectively disabled.
+    pub fn path_pattern(mut self, pattern: &str) -> Self {
+        self.path_pattern = Some(Regex::new(pattern).unwrap_or_else(|_| never_matching_regex()));
+        self
+    }
+
+    /// Adds a CSS selector whose matches make up the page's main content.
+    pub fn content_selector(mut self, selector: impl Into<String>) -> Self {
+        self.content_selectors.push(selector.into());
+        self
+    }
+
+    /// Adds a CSS selector whose matches are removed from the extracted content.
+    pub fn removal_selector(mut self, selector: impl Into<String>) -> Self {
+        self.removal_selectors.push(selector.into());
+        self
+    }
+}
+
+impl SiteExtractor for SelectorExtractor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn matches(&self, host: &str, path: &str) -> bool {
+        let host = host.to_lowercase();
+        let host_matches = self.hosts.iter().any(|pattern| {
+            host == *pattern
+                || host
+                    .strip_suffix(pattern.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        });
+
+        host_matches
+            && self
+                .path_pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(path))
+    }
+
+    fn extract(&self, document: &Html) -> Option<String> {
+        if self.content_selectors.is_empty() {
+            return None;
+        }
+
+        // A single selector list keeps matches in document order
+        let selector = Selector::parse(&self.content_selectors.join(", ")).ok()?;
+
+        let mut selected = HashSet::new();
+        let mut extracted_html = String::new();
+        for element in document.root_element().select(&selector) {
+            // Matches come in document order, so an outer match is seen first
+            if element
+                .ancestors()
+                .any(|ancestor| selected.contains(&ancestor.id()))
+            {
+                continue;
+            }
+            selected.insert(element.id());
+            extracted_html.push_str(&element.html());
+            extracted_html.push('\n');
+        }
+
+        if extracted_html.trim().is_empty() {
+            return None;
+        }
+
+        if self.removal_selectors.is_empty() {
+            return Some(extracted_html);
+        }
+
+        let mut fragment = Html::parse_fragment(&extracted_html);
+        remove_elements(&mut fragment, &self.removal_selectors, &mut None);
+
+        Some(fragment.root_element().inner_html())
+    }
+}
+
+impl fmt::Debug for SelectorExtractor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SelectorExtractor")
+            .field("name", &self.name)
+            .field("hosts", &self.hosts)
+            .field("path_pattern", &self.path_pattern.as_ref().map(Regex::as_str))
+            .field("content_selectors", &self.content_selectors)
+            .field("removal_selectors", &self.removal_selectors)
+            .finish()
+    }
+}
+
+/// Ordered collection of [`SiteExtractor`]s.
+///
+/// Extractors registered at runtime take precedence over the ones registered
+/// before them, so a custom extractor can override a built-in one for the same site.
+/// The default registry contains the built-in extractors returned by
+/// [`SiteExtractorRegistry::builtin`].
+#[derive(Clone)]
+pub struct SiteExtractorRegistry {
+    extractors: Vec<Arc<dyn SiteExtractor>>,
+}
+
+impl Default for SiteExtractorRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl SiteExtractorRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self {
+            extractors: Vec::new(),
+        }
+    }
+
+    /// Creates a registry containing the built-in extractors for Wikipedia, GitHub
+    /// READMEs and issues, Stack Overflow, Medium, docs.rs and MDN.
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        for extractor in builtin_extractors() {
+            registry.register(extractor);
+        }
+        registry
+    }
+
+    /// Registers an extractor, giving it precedence over all previously registered ones.
+    pub fn register(&mut self, extractor: impl SiteExtractor + 'static) {
+        self.extractors.insert(0, Arc::new(extractor));
+    }
+
+    /// Returns the first extractor that handles the given host and path.
+    pub fn find(&self, host: &str, path: &str) -> Option<&dyn SiteExtractor> {
+        self.extractors
+            .iter()
+            .find(|extractor| extractor.matches(host, path))
+            .map(|extractor| extractor.as_ref())
+    }
+
+    /// Returns the first extractor that handles the given URL, if the URL can be parsed.
+    pub fn find_for_url(&self, url: &str) -> Option<&dyn SiteExtractor> {
+        let parsed = reqwest::Url::parse(url).ok()?;
+        self.find(parsed.host_str()?, parsed.path())
+    }
+
+    /// Returns the names of the registered extractors in lookup order.
+    pub fn names(&self) -> Vec<&str> {
+        self.extractors
+            .iter()
+            .map(|extractor| extractor.name())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.extractors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.extractors.is_empty()
+    }
+}
+
+impl fmt::Debug for SiteExtractorRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SiteExtractorRegistry")
+            .field("extractors", &self.names())
+            .finish()
+    }
+}
+
+/// Returns the built-in extractors, from lowest to highest precedence.
+fn builtin_extractors() -> Vec<SelectorExtractor> {
+    vec![
+        SelectorExtractor::new("wikipedia", "wikipedia.org")
+            .path_pattern(r"^/wiki/")
+            .content_selector("#firstHeading")
+            .content_selector("#mw-content-text .mw-parser-output")
+            .removal_selector(".mw-editsection")
+            .removal_selector(".navbox, .vertical-navbox, .sidebar")
+            .removal_selector(".hatnote, .ambox, .metadata, .noprint")
+            .removal_selector("#toc, .toc, .mw-empty-elt")
+            .removal_selector("sup.reference, .reflist, .references"),
+        SelectorExtractor::new("github-readme", "github.com")
+            .path_pattern(
+                r"(?i)^/[^/]+/[^/]+(/tree/[^/]+)?/?$|^/[^/]+/[^/]+/blob/.+/readme(\.[a-z]+)?$",
+            )
+            .content_selector("article.markdown-body"),
+        SelectorExtractor::new("github-issue", "github.com")
+            .path_pattern(r"^/[^/]+/[^/]+/(issues|pull)/\d+")
+            .content_selector(".js-issue-title, [data-testid='issue-title']")
+            .content_selector(".js-comment-body, [data-testid='markdown-body']"),
+        SelectorExtractor::new("stackoverflow", "stackoverflow.com")
+            .host("stackexchange.com")
+            .path_pattern(r"^/questions/\d+")
+            .content_selector("#question-header h1")
+            .content_selector("#question .s-prose, #answers .answer .s-prose")
+            .removal_selector(".js-post-menu, .post-signature, .comments"),
+        SelectorExtractor::new("medium", "medium.com")
+            .content_selector("article")
+            .removal_selector("button, .speechify-ignore")
+            .removal_selector("[data-testid='headerClapButton'], [data-testid='audioPlayButton']"),
+        SelectorExtractor::new("docs.rs", "docs.rs")
+            .content_selector("#main-content")
+            .removal_selector(".out-of-band, .anchor, .src, .srclink, #copy-path"),
+        SelectorExtractor::new("mdn", "developer.mozilla.org")
+            .content_selector("main#content .main-page-content, article.main-page-content")
+            .removal_selector(".metadata, .prev-next, .baseline-indicator"),
+    ]
+}
+
+pub(crate) fn never_matching_regex() -> Regex {
+    Regex::new(r"[^\s\S]").unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedExtractor;
+
+    impl SiteExtractor for FixedExtractor {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        fn matches(&self, host: &str, _path: &str) -> bool {
+            host == "en.wikipedia.org"
+        }
+
+        fn extract(&self, _document: &Html) -> Option<String> {
+            Some("<p>Fixed content</p>".to_string())
+        }
+    }
+
+    #[test]
+    fn test_selector_extractor_host_matching() {
+        let extractor = SelectorExtractor::new("wiki", "wikipedia.org");
+
+        assert!(extractor.matches("wikipedia.org", "/"));
+        assert!(extractor.matches("en.wikipedia.org", "/wiki/Rust"));
+        assert!(extractor.matches("EN.Wikipedia.org", "/wiki/Rust"));
+        assert!(!extractor.matches("notwikipedia.org", "/wiki/Rust"));
+        assert!(!extractor.matches("wikipedia.org.evil.com", "/wiki/Rust"));
+    }
+
+    #[test]
+    fn test_selector_extractor_path_pattern() {
+        let extractor = SelectorExtractor::new("issues", "github.com")
+            .path_pattern(r"^/[^/]+/[^/]+/issues/\d+");
+
+        assert!(extractor.matches("github.com", "/rust-lang/rust/issues/1"));
+        assert!(!extractor.matches("github.com", "/rust-lang/rust"));
+
+        let invalid = SelectorExtractor::new("invalid", "github.com").path_pattern("(");
+        assert!(!invalid.matches("github.com", "/anything"));
+    }
+
+    #[test]
+    fn test_selector_extractor_extract_with_removals() {
+        let extractor = SelectorExtractor::new("wiki", "wikipedia.org")
+            .content_selector("#firstHeading")
+            .content_selector(".mw-parser-output")
+            .removal_selector(".mw-editsection");
+
+        let document = Html::parse_document(
+            r#"<html><body>
+                <h1 id="firstHeading">Rust</h1>
+                <div class="mw-parser-output">
+                    <h2>History<span class="mw-editsection">[edit]</span></h2>
+                    <p>Rust began as a personal project.</p>
+                </div>
+                <div class="footer">Footer links</div>
+            </body></html>"#,
+        );
+
+        let extracted = extractor.extract(&document).unwrap();
+
+        assert!(extracted.find("Rust</h1>").unwrap() < extracted.find("History").unwrap());
+        assert!(extracted.contains("Rust began as a personal project."));
+        assert!(!extracted.contains("[edit]"));
+        assert!(!extracted.contains("Footer links"));
+    }
+
+    #[test]
+    fn test_selector_extractor_extract_nested_matches_once() {
+        let extractor = SelectorExtractor::new("blog", "example.com")
+            .content_selector("article")
+            .content_selector("article .content")
+            .content_selector("aside .content");
+
+        let document = Html::parse_document(
+            r#"<html><body>
+                <article><div class="content"><p>Only once.</p></div></article>
+                <aside><div class="content"><p>Sidebar note.</p></div></aside>
+            </body></html>"#,
+        );
+
+        let extracted = extractor.extract(&document).unwrap();
+        assert_eq!(extracted.matches("Only once.").count(), 1);
+        assert!(extracted.find("Only once.").unwrap() < extracted.find("Sidebar note.").unwrap());
+    }
+
+    #[test]
+    fn test_selector_extractor_extract_without_match() {
+        let extractor = SelectorExtractor::new("docs", "docs.rs").content_selector("#main-content");
+        let document = Html::parse_document("<html><body><p>Other layout</p></body></html>");
+        assert!(extractor.extract(&document).is_none());
+
+        let no_selectors = SelectorExtractor::new("empty", "docs.rs");
+        assert!(no_selectors.extract(&document).is_none());
+    }
+
+    #[test]
+    fn test_registry_builtin_lookup() {
+        let registry = SiteExtractorRegistry::builtin();
+
+        let lookups = [
+            ("https://en.wikipedia.org/wiki/Rust_(programming_language)", "wikipedia"),
+            ("https://github.com/rust-lang/rust", "github-readme"),
+            ("https://github.com/rust-lang/rust/issues/12345", "github-issue"),
+            ("https://github.com/rust-lang/rust/pull/678", "github-issue"),
+            ("https://stackoverflow.com/questions/123/how-to", "stackoverflow"),
+            ("https://medium.com/@someone/a-story-123", "medium"),
+            ("https://docs.rs/regex/latest/regex/", "docs.rs"),
+            ("https://developer.mozilla.org/en-US/docs/Web/HTML", "mdn"),
+        ];
+
+        for (url, expected) in lookups {
+            let extractor = registry.find_for_url(url);
+            assert_eq!(extractor.map(|e| e.name()), Some(expected), "{}", url);
+        }
+
+        assert!(registry.find_for_url("https://example.com/article").is_none());
+        assert!(registry.find_for_url("not a url").is_none());
+    }
+
+    #[test]
+    fn test_registry_github_readme_only_on_readme_pages() {
+        let registry = SiteExtractorRegistry::builtin();
+
+        for readme in [
+            "https://github.com/rust-lang/rust/",
+            "https://github.com/rust-lang/rust/tree/master",
+            "https://github.com/rust-lang/rust/blob/master/README.md",
+            "https://github.com/rust-lang/rust/blob/main/docs/readme",
+        ] {
+            let extractor = registry.find_for_url(readme);
+            assert_eq!(
+                extractor.map(|e| e.name()),
+                Some("github-readme"),
+                "{}",
+                readme
+            );
+        }
+        for other in [
+            "https://github.com/rust-lang/rust/blob/master/src/main.rs",
+            "https://github.com/rust-lang/rust/tree/master/library",
+            "https://github.com/rust-lang/rust/issues",
+            "https://github.com/rust-lang/rust/commits/master",
+        ] {
+            assert!(registry.find_for_url(other).is_none(), "{}", other);
+        }
+    }
+
+    #[test]
+    fn test_registry_runtime_registration_takes_precedence() {
+        let mut registry = SiteExtractorRegistry::builtin();
+        let builtin_count = registry.len();
+
+        registry.register(FixedExtractor);
+
+        assert_eq!(registry.len(), builtin_count + 1);
+        assert_eq!(registry.names()[0], "fixed");
+        assert_eq!(
+            registry
+                .find("en.wikipedia.org", "/wiki/Rust")
+                .map(|e| e.name()),
+            Some("fixed")
+        );
+        assert_eq!(
+            registry
+                .find("de.wikipedia.org", "/wiki/Rust")
+                .map(|e| e.name()),
+            Some("wikipedia")
+        );
+    }
+
+    #[test]
+    fn test_registry_new_is_empty() {
+        let registry = SiteExtractorRegistry::new();
+        assert!(registry.is_empty());
+        assert!(registry.find("en.wikipedia.org", "/wiki/Rust").is_none());
+        assert!(!SiteExtractorRegistry::default().is_empty());
+    }
+}
@@ -0,0 +1,711 @@
+use std::collections::HashSet;
+use std::io::Read;
+
+use flate2::read::GzDecoder;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::Url;
+use roxmltree::{Document, Node};
+
+use crate::feed::normalize_date;
+use crate::harvest_error::HarvestError;
+use crate::site_extractor::never_matching_regex;
+
+/// Sitemaps fetched in a single discovery at most, so sitemap indexes that
+/// reference each other cannot loop.
+const MAX_SITEMAPS: usize = 500;
+
+/// W3C datetimes, from a year alone to a full datetime with fractional
+/// seconds and a `Z` or numeric zone.
+static W3C_DATETIME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(\d{4})(?:-(\d{1,2})(?:-(\d{1,2})(?:[T ](\d{1,2}):(\d{2})(?::(\d{2})(?:\.\d+)?)?\s*(Z|[+-]\d{2}:?\d{2})?)?)?)?$",
+    )
+    .unwrap()
+});
+
+/// A page listed in a sitemap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SitemapUrl {
+    /// The URL of the page.
+    pub loc: String,
+    /// When the page last changed, as a W3C datetime such as `2025-06-01` or
+    /// `2025-06-01T10:00:00+00:00`.
+    pub lastmod: Option<String>,
+}
+
+/// Which sitemap URLs are harvested by
+/// [`MarkdownHarvester::get_sitemap_documents`](crate::MarkdownHarvester::get_sitemap_documents).
+///
+/// Path patterns are regular expressions matched against the URL path. An
+/// invalid pattern never matches, so an invalid include pattern harvests
+/// nothing and an invalid exclude pattern excludes nothing.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::SitemapConfig;
+///
+/// let config = SitemapConfig::builder()
+///     .include_path(r"^/docs/")
+///     .exclude_path(r"/v1/")
+///     .modified_since("2025-01-01")
+///     .max_urls(200)
+///     .build();
+///
+/// assert_eq!(config.include_paths(), ["^/docs/"]);
+/// assert_eq!(config.modified_since(), Some("2025-01-01"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SitemapConfig {
+    include_paths: Vec<Regex>,
+    exclude_paths: Vec<Regex>,
+    modified_since: Option<String>,
+    max_urls: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SitemapConfigBuilder {
+    include_paths: Vec<Regex>,
+    exclude_paths: Vec<Regex>,
+    modified_since: Option<String>,
+    max_urls: Option<usize>,
+}
+
+impl SitemapConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only harvests URLs whose path matches one of the include patterns.
+    pub fn include_path(mut self, pattern: &str) -> Self {
+        self.include_paths.push(compile(pattern));
+        self
+    }
+
+    /// Skips URLs whose path matches this pattern.
+    pub fn exclude_path(mut self, pattern: &str) -> Self {
+        self.exclude_paths.push(compile(pattern));
+        self
+    }
+
+    /// Only harvests URLs whose `lastmod` is on or after this W3C datetime,
+    /// e.g. `2025-01-01`, comparing instants so that zone offsets and partial
+    /// dates are honoured. Dates without a zone are taken as UTC. URLs without
+    /// a readable `lastmod` are skipped, and so are sitemaps of an index that
+    /// did not change since.
+    pub fn modified_since(mut self, date: &str) -> Self {
+        self.modified_since = Some(date.trim().to_string());
+        self
+    }
+
+    /// Harvests at most this many URLs, in sitemap order.
+    pub fn max_urls(mut self, max_urls: usize) -> Self {
+        self.max_urls = Some(max_urls);
+        self
+    }
+
+    pub fn build(self) -> SitemapConfig {
+        SitemapConfig {
+            include_paths: self.include_paths,
+            exclude_paths: self.exclude_paths,
+            modified_since: self.modified_since,
+            max_urls: self.max_urls,
+        }
+    }
+}
+
+impl SitemapConfig {
+    pub fn builder() -> SitemapConfigBuilder {
+        SitemapConfigBuilder::new()
+    }
+
+    pub fn include_paths(&self) -> Vec<&str> {
+        self.include_paths.iter().map(Regex::as_str).collect()
+    }
+
+    pub fn exclude_paths(&self) -> Vec<&str> {
+        self.exclude_paths.iter().map(Regex::as_str).collect()
+    }
+
+    pub fn modified_since(&self) -> Option<&str> {
+        self.modified_since.as_deref()
+    }
+
+    pub fn max_urls(&self) -> Option<usize> {
+        self.max_urls
+    }
+
+    /// Returns `true` when a sitemap URL passes the path and date filters.
+    pub fn accepts(&self, url: &SitemapUrl) -> bool {
+        let Some(path) = Url::parse(&url.loc).ok().map(|url| url.path().to_string()) else {
+            return false;
+        };
+        let included = self.include_paths.is_empty()
+            || self
+                .include_paths
+                .iter()
+                .any(|pattern| pattern.is_match(&path));
+        included
+            && !self
+                .exclude_paths
+                .iter()
+                .any(|pattern| pattern.is_match(&path))
+            && self.is_recent(url.lastmod.as_deref())
+    }
+
+    /// Compares instants, falling back to comparing strings when `since` is
+    /// not a W3C datetime. RFC 822 dates, which some sitemaps use, are read
+    /// too.
+    fn is_recent(&self, lastmod: Option<&str>) -> bool {
+        let Some(since) = &self.modified_since else {
+            return true;
+        };
+        let Some(lastmod) = lastmod.map(str::trim) else {
+            return false;
+        };
+        match w3c_timestamp(since) {
+            Some(since) => w3c_timestamp(lastmod).is_some_and(|lastmod| lastmod >= since),
+            None => lastmod >= since.as_str(),
+        }
+    }
+}
+
+/// Seconds since the Unix epoch of a W3C datetime (or RFC 822 date), taking
+/// missing parts as the start of the period and a missing zone as UTC.
+fn w3c_timestamp(date: &str) -> Option<i64> {
+    let date = normalize_date(date);
+    let caps = W3C_DATETIME_REGEX.captures(&date)?;
+    let field = |index: usize, default: i64| {
+        caps.get(index)
+            .map_or(Some(default), |field| field.as_str().parse().ok())
+    };
+    let (year, month, day) = (field(1, 0)?, field(2, 1)?, field(3, 1)?);
+    let (hour, minute, second) = (field(4, 0)?, field(5, 0)?, field(6, 0)?);
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return None;
+    }
+    let offset = match caps.get(7).map(|zone| zone.as_str()) {
+        None | Some("Z") => 0,
+        Some(zone) => {
+            let digits = zone[1..].replace(':', "");
+            let minutes =
+                digits[..2].parse::<i64>().ok()? * 60 + digits[2..].parse::<i64>().ok()?;
+            if zone.starts_with('-') {
+                -minutes
+            } else {
+                minutes
+            }
+        }
+    };
+
+    // Days since the epoch of a civil date (Howard Hinnant's algorithm)
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    Some(days * 86_400 + hour * 3_600 + minute * 60 + second - offset * 60)
+}
+
+fn compile(pattern: &str) -> Regex {
+    Regex::new(pattern).unwrap_or_else(|_| never_matching_regex())
+}
+
+/// The contents of a sitemap file.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Sitemap {
+    /// A `urlset`, or a plain-text list of URLs.
+    Urls(Vec<SitemapUrl>),
+    /// A `sitemapindex` listing further sitemaps.
+    Index(Vec<SitemapUrl>),
+}
+
+/// Parses a sitemap, decompressing it first when it is gzipped.
+pub(crate) fn parse_sitemap(bytes: &[u8]) -> Result<Sitemap, String> {
+    let text = if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut text = String::new();
+        GzDecoder::new(bytes)
+            .read_to_string(&mut text)
+            .map_err(|error| format!("invalid gzip sitemap: {}", error))?;
+        text
+    } else {
+        String::from_utf8_lossy(bytes).into_owned()
+    };
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+
+    if !text.starts_with('<') {
+        // Text sitemaps list one URL per line
+        let urls = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with("http://") || line.starts_with("https://"))
+            .map(|loc| SitemapUrl {
+                loc: loc.to_string(),
+                lastmod: None,
+            })
+            .collect::<Vec<_>>();
+        if urls.is_empty() {
+            return Err("not a sitemap".to_string());
+        }
+        return Ok(Sitemap::Urls(urls));
+    }
+
+    let document = Document::parse(text).map_err(|error| error.to_string())?;
+    let root = document.root_element();
+    let entries = |name: &str| {
+        root.children()
+            .filter(|node| node.is_element() && node.tag_name().name() == name)
+            .filter_map(|node| {
+                Some(SitemapUrl {
+                    loc: child_text(node, "loc")?,
+                    lastmod: child_text(node, "lastmod"),
+                })
+            })
+            .collect()
+    };
+    match root.tag_name().name() {
+        "urlset" => Ok(Sitemap::Urls(entries("url"))),
+        "sitemapindex" => Ok(Sitemap::Index(entries("sitemap"))),
+        name => Err(format!("not a sitemap: <{}>", name)),
+    }
+}
+
+fn child_text(node: Node, name: &str) -> Option<String> {
+    node.children()
+        .find(|child| child.is_element() && child.tag_name().name() == name)
+        .and_then(|child| child.text())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+/// Returns the sitemaps declared with `Sitemap:` lines in a robots.txt file.
+pub(crate) fn robots_sitemaps(robots: &str) -> Vec<String> {
+    robots
+        .lines()
+        .filter_map(|line| {
+            let (field, value) = line.split_once(':')?;
+            field
+                .trim()
+                .eq_ignore_ascii_case("sitemap")
+                .then(|| value.trim().to_string())
+        })
+        .filter(|url| !url.is_empty())
+        .collect()
+}
+
+/// Finds the page URLs of a site, following sitemap indexes, and filters them.
+///
+/// `url` may be a sitemap, a robots.txt file, or any page of a site. For a
+/// page, the sitemaps listed in the site's robots.txt are used, or else its
+/// `/sitemap.xml`. Sitemaps of an index that cannot be fetched or parsed are
+/// skipped; the URLs are returned once each, in sitemap order.
+pub(crate) fn discover_sitemap_urls(
+    url: &str,
+    config: &SitemapConfig,
+    fetch: impl Fn(&str) -> Result<Vec<u8>, HarvestError>,
+) -> Result<Vec<SitemapUrl>, HarvestError> {
+    let not_found = |message: String| HarvestError::Body {
+        url: url.to_string(),
+        message,
+    };
+    let parsed = Url::parse(url).map_err(|error| not_found(error.to_string()))?;
+    let site_root = parsed
+        .join("/")
+        .map_err(|error| not_found(error.to_string()))?;
+    let robots_url = site_root.join("robots.txt").map(String::from).ok();
+
+    let roots = if parsed.path() == "/" || Some(url) == robots_url.as_deref() {
+        site_sitemaps(&site_root, robots_url.as_deref(), &fetch)
+    } else {
+        match fetch(url).map(|bytes| parse_sitemap(&bytes)) {
+            Ok(Ok(sitemap)) => vec![(url.to_string(), sitemap)],
+            // The URL is a page of the site rather than a sitemap
+            Ok(Err(_)) => site_sitemaps(&site_root, robots_url.as_deref(), &fetch),
+            Err(error) => return Err(error),
+        }
+    };
+    if roots.is_empty() {
+        return Err(not_found("No sitemap found".to_string()));
+    }
+
+    let mut visited: HashSet<String> = roots.iter().map(|(url, _)| url.clone()).collect();
+    let mut seen = HashSet::new();
+    let mut urls = Vec::new();
+    let mut pending: Vec<Sitemap> = roots
+        .into_iter()
+        .rev()
+        .map(|(_, sitemap)| sitemap)
+        .collect();
+
+    while let Some(sitemap) = pending.pop() {
+        match sitemap {
+            Sitemap::Urls(entries) => {
+                for entry in entries {
+                    if config.accepts(&entry) && seen.insert(entry.loc.clone()) {
+                        urls.push(entry);
+                    }
+                }
+            }
+            Sitemap::Index(children) => {
+                let children = children
+                    .into_iter()
+                    .filter(|child| {
+                        // Sitemaps whose lastmod cannot be read may still list recent URLs
+                        let unreadable = child
+                            .lastmod
+                            .as_deref()
+                            .is_none_or(|lastmod| w3c_timestamp(lastmod).is_none());
+                        unreadable || config.is_recent(child.lastmod.as_deref())
+                    })
+                    .filter(|child| {
+                        visited.len() < MAX_SITEMAPS && visited.insert(child.loc.clone())
+                    })
+                    .filter_map(|child| {
+                        fetch(&child.loc)
+                            .ok()
+                            .and_then(|bytes| parse_sitemap(&bytes).ok())
+                    })
+                    .collect::<Vec<_>>();
+                pending.extend(children.into_iter().rev());
+            }
+        }
+        if config
+            .max_urls
+            .is_some_and(|max_urls| urls.len() >= max_urls)
+        {
+            break;
+        }
+    }
+
+    if let Some(max_urls) = config.max_urls {
+        urls.truncate(max_urls);
+    }
+    Ok(urls)
+}
+
+/// The sitemaps of a site: those listed in its robots.txt, or its `/sitemap.xml`.
+fn site_sitemaps(
+    site_root: &Url,
+    robots_url: Option<&str>,
+    fetch: &impl Fn(&str) -> Result<Vec<u8>, HarvestError>,
+) -> Vec<(String, Sitemap)> {
+    let mut candidates = robots_url
+        .and_then(|robots_url| fetch(robots_url).ok())
+        .map(|robots| robots_sitemaps(&String::from_utf8_lossy(&robots)))
+        .unwrap_or_default();
+    if candidates.is_empty() {
+        candidates.extend(site_root.join("sitemap.xml").map(String::from));
+    }
+    candidates
+        .into_iter()
+        .filter_map(|url| {
+            let sitemap = parse_sitemap(&fetch(&url).ok()?).ok()?;
+            Some((url, sitemap))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::collections::HashMap;
+    use std::io::Write;
+
+    fn urlset(urls: &[(&str, Option<&str>)]) -> Vec<u8> {
+        let entries: String = urls
+            .iter()
+            .map(|(loc, lastmod)| match lastmod {
+                Some(lastmod) => format!("<url><loc>{loc}</loc><lastmod>{lastmod}</lastmod></url>"),
+                None => format!("<url><loc>{loc}</loc></url>"),
+            })
+            .collect();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">{entries}</urlset>"#
+        )
+        .into_bytes()
+    }
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    /// Serves a small documentation site: robots.txt points to an index
+    /// listing a plain and a gzipped sitemap, plus one that is missing.
+    fn site() -> HashMap<String, Vec<u8>> {
+        let index = r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+            <sitemap><loc>https://docs.example.com/sitemap-guides.xml</loc><lastmod>2025-03-01</lastmod></sitemap>
+            <sitemap><loc>https://docs.example.com/sitemap-api.xml.gz</loc></sitemap>
+            <sitemap><loc>https://docs.example.com/sitemap-missing.xml</loc></sitemap>
+            <sitemap><loc>https://docs.example.com/sitemap-index.xml</loc></sitemap>
+        </sitemapindex>"#;
+        HashMap::from([
+            (
+                "https://docs.example.com/robots.txt".to_string(),
+                b"User-agent: *\nDisallow: /admin\nSitemap: https://docs.example.com/sitemap-index.xml\n"
+                    .to_vec(),
+            ),
+            (
+                "https://docs.example.com/sitemap-index.xml".to_string(),
+                index.as_bytes().to_vec(),
+            ),
+            (
+                "https://docs.example.com/sitemap-guides.xml".to_string(),
+                urlset(&[
+                    ("https://docs.example.com/guides/start", Some("2025-02-10")),
+                    ("https://docs.example.com/guides/deploy", Some("2024-11-30")),
+                    ("https://docs.example.com/blog/launch", Some("2025-02-20")),
+                ]),
+            ),
+            (
+                "https://docs.example.com/sitemap-api.xml.gz".to_string(),
+                gzip(&urlset(&[
+                    ("https://docs.example.com/api/client", Some("2025-01-15T08:00:00+00:00")),
+                    ("https://docs.example.com/guides/start", Some("2025-02-10")),
+                    ("https://docs.example.com/api/legacy", None),
+                ])),
+            ),
+        ])
+    }
+
+    fn fetcher(
+        site: &HashMap<String, Vec<u8>>,
+    ) -> impl Fn(&str) -> Result<Vec<u8>, HarvestError> + '_ {
+        |url| {
+            site.get(url).cloned().ok_or_else(|| HarvestError::Request {
+                url: url.to_string(),
+                message: "404 Not Found".to_string(),
+            })
+        }
+    }
+
+    fn locs(urls: &[SitemapUrl]) -> Vec<&str> {
+        urls.iter().map(|url| url.loc.as_str()).collect()
+    }
+
+    #[test]
+    fn test_discover_sitemap_urls_from_site() {
+        let site = site();
+        let urls = discover_sitemap_urls(
+            "https://docs.example.com/",
+            &SitemapConfig::default(),
+            fetcher(&site),
+        )
+        .unwrap();
+        assert_eq!(
+            locs(&urls),
+            [
+                "https://docs.example.com/guides/start",
+                "https://docs.example.com/guides/deploy",
+                "https://docs.example.com/blog/launch",
+                "https://docs.example.com/api/client",
+                "https://docs.example.com/api/legacy",
+            ]
+        );
+
+        // A page of the site is not a sitemap, so robots.txt is used
+        let mut site = site;
+        site.insert(
+            "https://docs.example.com/guides/start".to_string(),
+            b"<!DOCTYPE html><html><body><p>Start here</p></body></html>".to_vec(),
+        );
+        let from_page = discover_sitemap_urls(
+            "https://docs.example.com/guides/start",
+            &SitemapConfig::default(),
+            fetcher(&site),
+        );
+        assert_eq!(from_page, Ok(urls));
+    }
+
+    #[test]
+    fn test_discover_sitemap_urls_filters() {
+        let site = site();
+        let config = SitemapConfig::builder()
+            .include_path(r"^/(guides|api)/")
+            .exclude_path(r"/deploy$")
+            .modified_since("2025-01-01")
+            .build();
+        let urls = discover_sitemap_urls(
+            "https://docs.example.com/sitemap-index.xml",
+            &config,
+            fetcher(&site),
+        )
+        .unwrap();
+        assert_eq!(
+            locs(&urls),
+            [
+                "https://docs.example.com/guides/start",
+                "https://docs.example.com/api/client",
+            ]
+        );
+        assert_eq!(urls[0].lastmod.as_deref(), Some("2025-02-10"));
+
+        let config = SitemapConfig::builder().max_urls(2).build();
+        let urls = discover_sitemap_urls(
+            "https://docs.example.com/sitemap-api.xml.gz",
+            &config,
+            fetcher(&site),
+        )
+        .unwrap();
+        assert_eq!(
+            locs(&urls),
+            [
+                "https://docs.example.com/api/client",
+                "https://docs.example.com/guides/start",
+            ]
+        );
+
+        let missing = discover_sitemap_urls(
+            "https://other.example.com/",
+            &SitemapConfig::default(),
+            fetcher(&site),
+        );
+        assert_eq!(
+            missing.unwrap_err().to_string(),
+            "Error reading content from https://other.example.com/: No sitemap found"
+        );
+    }
+
+    #[test]
+    fn test_parse_sitemap_and_robots() {
+        let text = b"https://example.com/a\n\nhttps://example.com/b\n";
+        assert_eq!(
+            parse_sitemap(text),
+            Ok(Sitemap::Urls(vec![
+                SitemapUrl {
+                    loc: "https://example.com/a".to_string(),
+                    lastmod: None
+                },
+                SitemapUrl {
+                    loc: "https://example.com/b".to_string(),
+                    lastmod: None
+                },
+            ]))
+        );
+        assert!(parse_sitemap(b"<html><body></body></html>").is_err());
+        assert_eq!(
+            robots_sitemaps(
+                "sitemap: https://example.com/a.xml\nSITEMAP:https://example.com/b.xml"
+            ),
+            ["https://example.com/a.xml", "https://example.com/b.xml"]
+        );
+    }
+
+    #[test]
+    fn test_parse_sitemap_empty_and_corrupt() {
+        let compressed = gzip(&urlset(&[("https://example.com/a", None)]));
+        for corrupt in [&compressed[..compressed.len() / 2], b"\x1f\x8bnot gzip"] {
+            let error = parse_sitemap(corrupt).unwrap_err();
+            assert!(error.starts_with("invalid gzip sitemap: "), "{error}");
+        }
+        assert_eq!(parse_sitemap(b""), Err("not a sitemap".to_string()));
+        assert_eq!(
+            parse_sitemap(b"\xef\xbb\xbf \n"),
+            Err("not a sitemap".to_string())
+        );
+        assert!(parse_sitemap(b"<urlset>").is_err());
+        assert_eq!(parse_sitemap(b"<urlset/>"), Ok(Sitemap::Urls(Vec::new())));
+
+        assert!(robots_sitemaps("").is_empty());
+        assert!(robots_sitemaps("User-agent: *\nSitemap:\n").is_empty());
+    }
+
+    #[test]
+    fn test_sitemap_config_is_recent() {
+        assert_eq!(w3c_timestamp("1970-01-01"), Some(0));
+        assert_eq!(w3c_timestamp("2000-03-01T00:00:00Z"), Some(951_868_800));
+        assert_eq!(w3c_timestamp("2000-03-01T02:00+0200"), Some(951_868_800));
+        assert_eq!(
+            w3c_timestamp("Wed, 01 Mar 2000 00:00:00 GMT"),
+            Some(951_868_800)
+        );
+
+        let config = SitemapConfig::builder()
+            .modified_since("2025-01-01")
+            .build();
+        for (lastmod, recent) in [
+            (Some("2025-01-01"), true),
+            (Some(" 2025-03-01 "), true),
+            (Some("2025-02"), true),
+            (Some("2025-01-15T08:00:00.250Z"), true),
+            (Some("2024-12-31T23:30:00-01:00"), true),
+            (Some("2025-01-01T00:30:00+02:00"), false),
+            (Some("2024"), false),
+            (Some("Wed, 15 Jan 2025 08:00:00 GMT"), true),
+            (Some("Tue, 31 Dec 2024 23:00:00 GMT"), false),
+            (Some("2025-13-01"), false),
+            (Some("last week"), false),
+            (Some(""), false),
+            (None, false),
+        ] {
+            assert_eq!(config.is_recent(lastmod), recent, "{lastmod:?}");
+        }
+
+        let config = SitemapConfig::builder()
+            .modified_since("2025-06-01T12:00:00+02:00")
+            .build();
+        assert!(config.is_recent(Some("2025-06-01T10:00:00Z")));
+        assert!(!config.is_recent(Some("2025-06-01")));
+
+        // Dates that are not W3C datetimes are compared as strings
+        let config = SitemapConfig::builder()
+            .modified_since("2025/01/01")
+            .build();
+        assert!(config.is_recent(Some("2025/02/01")));
+        assert!(!config.is_recent(Some("2024/12/01")));
+        assert!(SitemapConfig::default().is_recent(None));
+    }
+
+    #[test]
+    fn test_discover_sitemap_urls_skips_corrupt_sitemaps() {
+        let index = r#"<sitemapindex>
+            <sitemap><loc>https://example.com/sitemap-old.xml.gz</loc><lastmod>2025-04-01</lastmod></sitemap>
+            <sitemap><loc>https://example.com/sitemap-new.xml</loc><lastmod>last week</lastmod></sitemap>
+            <sitemap><loc>https://example.com/sitemap-stale.xml</loc><lastmod>2024-06-01</lastmod></sitemap>
+        </sitemapindex>"#;
+        let compressed = gzip(&urlset(&[("https://example.com/old", Some("2025-04-01"))]));
+        let site = HashMap::from([
+            (
+                "https://example.com/sitemap.xml".to_string(),
+                index.as_bytes().to_vec(),
+            ),
+            (
+                "https://example.com/sitemap-old.xml.gz".to_string(),
+                compressed[..compressed.len() - 8].to_vec(),
+            ),
+            (
+                "https://example.com/sitemap-new.xml".to_string(),
+                urlset(&[(
+                    "https://example.com/new",
+                    Some("Mon, 03 Mar 2025 10:00:00 +0000"),
+                )]),
+            ),
+            (
+                "https://example.com/sitemap-stale.xml".to_string(),
+                urlset(&[("https://example.com/stale", Some("2025-05-01"))]),
+            ),
+        ]);
+        let config = SitemapConfig::builder()
+            .modified_since("2025-01-01")
+            .build();
+
+        let urls =
+            discover_sitemap_urls("https://example.com/sitemap.xml", &config, fetcher(&site))
+                .unwrap();
+        assert_eq!(locs(&urls), ["https://example.com/new"]);
+    }
+}
@@ -0,0 +1,531 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use scraper::node::Text;
+use scraper::{ElementRef, Html, Node, Selector};
+
+/// Prefix of the placeholder that stands in for a converted table until the
+/// rest of the page has been turned into Markdown by html2md.
+const PLACEHOLDER_PREFIX: &str = "MDHARVESTTABLE";
+
+static PLACEHOLDER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(&format!(r"{}(\d+)", PLACEHOLDER_PREFIX)).unwrap());
+
+static WHITESPACE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
+
+/// Elements that make a cell impossible to render on a single GFM table line.
+const BLOCK_CONTENT_SELECTOR: &str = "ul, ol, pre, blockquote, h1, h2, h3, h4, h5, h6, dl";
+
+/// Upper bound for `colspan`/`rowspan` values, guarding against absurd spans.
+const MAX_SPAN: usize = 64;
+
+/// A table row as seen in the markup, before spans are expanded.
+struct SourceRow<'a> {
+    cells: Vec<ElementRef<'a>>,
+    in_head: bool,
+}
+
+/// A table laid out on a rectangular grid with spans expanded.
+struct TableGrid {
+    caption: Option<String>,
+    rows: Vec<Vec<String>>,
+    header_rows: usize,
+    complex: bool,
+}
+
+/// Converts the tables in `html` to Markdown ahead of html2md.
+///
+/// Layout tables are unwrapped so their cells become ordinary blocks. Every
+/// remaining table is rendered to Markdown — a GFM table for simple grids when
+/// `gfm_tables` is set, a key-value list for tables using spans or block
+/// content or when GFM tables are not available — and replaced by a
+/// placeholder paragraph. Returns the rewritten HTML and the rendered tables,
+/// to be put back with [`restore_tables`] once html2md has run.
+pub(crate) fn convert_tables(html: &str, gfm_tables: bool) -> (String, Vec<String>) {
+    let mut document = Html::parse_fragment(html);
+    flatten_layout_tables(&mut document);
+
+    let table_selector = Selector::parse("table").unwrap();
+    let table_ids: Vec<_> = document
+        .root_element()
+        .select(&table_selector)
+        .map(|table| table.id())
+        .collect();
+
+    let mut tables = Vec::with_capacity(table_ids.len());
+    for table_id in table_ids {
+        let Some(table) = document.tree.get(table_id).and_then(ElementRef::wrap) else {
+            continue;
+        };
+        let markdown = render_table(&build_grid(table), gfm_tables);
+        let children: Vec<_> = table.children().map(|child| child.id()).collect();
+
+        for child_id in children {
+            if let Some(mut child) = document.tree.get_mut(child_id) {
+                child.detach();
+            }
+        }
+        if let Some(mut node) = document.tree.get_mut(table_id) {
+            rename_element(node.value(), "p");
+            let placeholder = format!("{}{}", PLACEHOLDER_PREFIX, tables.len());
+            node.append(Node::Text(Text {
+                text: placeholder.as_str().into(),
+            }));
+        }
+        tables.push(markdown);
+    }
+
+    (document.root_element().inner_html(), tables)
+}
+
+/// Substitutes the table placeholders in `markdown` with the rendered tables.
+pub(crate) fn restore_tables(markdown: &str, tables: &[String]) -> String {
+    if tables.is_empty() {
+        return markdown.to_string();
+    }
+
+    PLACEHOLDER_REGEX
+        .replace_all(markdown, |caps: &Captures| {
+            caps[1]
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| tables.get(index))
+                .map(|table| format!("\n\n{}\n\n", table))
+                .unwrap_or_default()
+        })
+        .to_string()
+}
+
+/// Applies `rewrite` to the text around the table placeholders in `markdown`
+/// and to the tables they stand for, in document order. The placeholders are
+/// kept.
+pub(crate) fn rewrite_around_tables(
+    markdown: &str,
+    tables: &mut [String],
+    mut rewrite: impl FnMut(&str) -> String,
+) -> String {
+    let mut rewritten = String::with_capacity(markdown.len());
+    let mut last = 0;
+
+    for caps in PLACEHOLDER_REGEX.captures_iter(markdown) {
+        let placeholder = caps.get(0).unwrap();
+        rewritten.push_str(&rewrite(&markdown[last..placeholder.start()]));
+        if let Some(table) = caps[1]
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| tables.get_mut(index))
+        {
+            *table = rewrite(table);
+        }
+        rewritten.push_str(placeholder.as_str());
+        last = placeholder.end();
+    }
+    rewritten.push_str(&rewrite(&markdown[last..]));
+
+    rewritten
+}
+
+/// Unwraps layout tables, outermost first, until only data tables remain.
+///
+/// The cells of a layout table are turned into `<div>`s and moved in front of the
+/// table, which is then removed.
+fn flatten_layout_tables(document: &mut Html) {
+    let table_selector = Selector::parse("table").unwrap();
+
+    while let Some((table_id, cell_ids)) = document
+        .root_element()
+        .select(&table_selector)
+        .find(|table| is_layout_table(*table))
+        .map(|table| {
+            let cell_ids: Vec<_> = table_rows(table)
+                .iter()
+                .flat_map(|row| row.cells.iter().map(|cell| cell.id()))
+                .collect();
+            (table.id(), cell_ids)
+        })
+    {
+        for cell_id in cell_ids {
+            if let Some(mut cell) = document.tree.get_mut(cell_id) {
+                rename_element(cell.value(), "div");
+            }
+            if let Some(mut table) = document.tree.get_mut(table_id) {
+                table.insert_id_before(cell_id);
+            }
+        }
+        if let Some(mut table) = document.tree.get_mut(table_id) {
+            table.detach();
+        }
+    }
+}
+
+/// Heuristics telling layout tables apart from data tables.
+///
+/// A table is treated as layout when it says so through its ARIA role, when it
+/// nests other tables, or when it has a single column or a single row and
+/// neither header cells nor a caption.
+fn is_layout_table(table: ElementRef) -> bool {
+    if matches!(table.value().attr("role"), Some("presentation" | "none")) {
+        return true;
+    }
+
+    let table_selector = Selector::parse("table").unwrap();
+    if table.select(&table_selector).next().is_some() {
+        return true;
+    }
+
+    let rows = table_rows(table);
+    let has_headers = rows
+        .iter()
+        .any(|row| row.cells.iter().any(|cell| cell.value().name() == "th"));
+    let has_caption = table
+        .children()
+        .filter_map(ElementRef::wrap)
+        .any(|child| child.value().name() == "caption");
+    let single_column = rows.iter().all(|row| row.cells.len() <= 1);
+    let single_row = rows.len() == 1;
+
+    !has_headers && !has_caption && (single_column || single_row)
+}
+
+/// Collects the rows that belong to `table` itself, skipping nested tables.
+fn table_rows(table: ElementRef<'_>) -> Vec<SourceRow<'_>> {
+    let mut rows = Vec::new();
+
+    for child in table.children().filter_map(ElementRef::wrap) {
+        match child.value().name() {
+            "tr" => rows.push(source_row(child, false)),
+            "thead" | "tbody" | "tfoot" => {
+                let in_head = child.value().name() == "thead";
+                for row in child.children().filter_map(ElementRef::wrap) {
+                    if row.value().name() == "tr" {
+                        rows.push(source_row(row, in_head));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    rows
+}
+
+fn source_row(row: ElementRef<'_>, in_head: bool) -> SourceRow<'_> {
+    SourceRow {
+        cells: row
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+            .collect(),
+        in_head,
+    }
+}
+
+/// Lays the table out on a grid, repeating the content of spanned cells.
+fn build_grid(table: ElementRef) -> TableGrid {
+    let caption_selector = Selector::parse("caption").unwrap();
+    let block_selector = Selector::parse(BLOCK_CONTENT_SELECTOR).unwrap();
+    let paragraph_selector = Selector::parse("p").unwrap();
+
+    let caption = table
+        .children()
+        .filter_map(ElementRef::wrap)
+        .find(|child| caption_selector.matches(child))
+        .map(cell_markdown)
+        .filter(|caption| !caption.is_empty());
+
+    let source_rows = table_rows(table);
+    let mut rows: Vec<Vec<Option<String>>> = vec![Vec::new(); source_rows.len()];
+    let mut complex = false;
+
+    for (row_index, source_row) in source_rows.iter().enumerate() {
+        let mut column = 0;
+        for cell in &source_row.cells {
+            while rows[row_index].get(column).is_some_and(Option::is_some) {
+                column += 1;
+            }
+
+            let colspan = span(*cell, "colspan");
+            let rowspan = span(*cell, "rowspan").min(source_rows.len() - row_index);
+            complex |= colspan > 1 || rowspan > 1;
+            complex |= cell.select(&block_selector).next().is_some()
+                || cell.select(&paragraph_selector).nth(1).is_some();
+
+            let text = cell_markdown(*cell);
+            for row in rows.iter_mut().skip(row_index).take(rowspan) {
+                if row.len() < column + colspan {
+                    row.resize(column + colspan, None);
+                }
+                for slot in &mut row[column..column + colspan] {
+                    *slot = Some(text.clone());
+                }
+            }
+            column += colspan;
+        }
+    }
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let rows: Vec<Vec<String>> = rows
+        .into_iter()
+        .map(|row| {
+            let mut row: Vec<String> = row.into_iter().map(Option::unwrap_or_default).collect();
+            row.resize(width, String::new());
+            row
+        })
+        .collect();
+
+    let header_rows = source_rows
+        .iter()
+        .take_while(|row| {
+            row.in_head
+                || (!row.cells.is_empty() && row.cells.iter().all(|c| c.value().name() == "th"))
+        })
+        .count()
+        .min(rows.len());
+    complex |= header_rows > 1;
+
+    TableGrid {
+        caption,
+        rows,
+        header_rows,
+        complex,
+    }
+}
+
+fn span(cell: ElementRef, attribute: &str) -> usize {
+    cell.value()
+        .attr(attribute)
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .unwrap_or(1)
+        .clamp(1, MAX_SPAN)
+}
+
+/// Renders a cell's inline markup as single-line Markdown safe for a table row.
+fn cell_markdown(cell: ElementRef) -> String {
+    let markdown = html2md::parse_html(&cell.inner_html());
+    WHITESPACE_REGEX
+        .replace_all(&markdown, " ")
+        .trim()
+        .replace('|', "\\|")
+}
+
+fn render_table(grid: &TableGrid, gfm_tables: bool) -> String {
+    let body = if grid.rows.is_empty() || grid.rows[0].is_empty() {
+        String::new()
+    } else if grid.complex || !gfm_tables {
+        render_key_value_list(grid)
+    } else {
+        render_gfm_table(grid)
+    };
+
+    match &grid.caption {
+        Some(caption) if !body.is_empty() => format!("**{}**\n\n{}", caption, body),
+        Some(caption) => caption.clone(),
+        None => body,
+    }
+}
+
+/// Renders a simple grid as a GFM table. Without a header row, the first row
+/// serves as the header.
+fn render_gfm_table(grid: &TableGrid) -> String {
+    let width = grid.rows[0].len();
+    let format_row = |row: &[String]| format!("| {} |", row.join(" | "));
+
+    let mut lines = Vec::with_capacity(grid.rows.len() + 1);
+    lines.push(format_row(&grid.rows[0]));
+    lines.push(format_row(&vec!["---".to_string(); width]));
+    lines.extend(grid.rows[1..].iter().map(|row| format_row(row)));
+    lines.join("\n")
+}
+
+/// Renders a table that GFM cannot express as one list item per body row,
+/// labelling each value with its column header. A table made only of header
+/// rows has nothing to label, so its rows are listed as they are.
+fn render_key_value_list(grid: &TableGrid) -> String {
+    let width = grid.rows[0].len();
+    let header_rows = if grid.header_rows < grid.rows.len() {
+        grid.header_rows
+    } else {
+        0
+    };
+    let headers: Vec<String> = (0..width)
+        .map(|column| {
+            let mut labels: Vec<&str> = Vec::new();
+            for row in &grid.rows[..header_rows] {
+                let label = row[column].as_str();
+                if !label.is_empty() && !labels.contains(&label) {
+                    labels.push(label);
+                }
+            }
+            labels.join(" / ")
+        })
+        .collect();
+
+    grid.rows[header_rows..]
+        .iter()
+        .filter_map(|row| {
+            let mut fields: Vec<String> = Vec::new();
+            for (column, value) in row.iter().enumerate() {
+                // Spanned cells repeat their value; keep it once per row
+                if value.is_empty() || (column > 0 && row[column - 1] == *value) {
+                    continue;
+                }
+                match headers[column].as_str() {
+                    "" => fields.push(value.clone()),
+                    header if header == value => fields.push(value.clone()),
+                    header => fields.push(format!("**{}**: {}", header, value)),
+                }
+            }
+            (!fields.is_empty()).then(|| format!("- {}", fields.join("; ")))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Changes an element's tag name and drops its attributes.
+pub(crate) fn rename_element(node: &mut Node, name: &str) {
+    if let Node::Element(element) = node {
+        element.name.local = name.into();
+        element.attrs.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(html: &str) -> Vec<String> {
+        convert_tables(html, true).1
+    }
+
+    #[test]
+    fn test_convert_tables_simple_grid_to_gfm() {
+        let tables = convert(
+            r#"<table>
+                <thead><tr><th>Plan</th><th>Price</th></tr></thead>
+                <tbody>
+                    <tr><td><strong>Basic</strong></td><td>$5 | month</td></tr>
+                    <tr><td>Pro</td><td><a href="/pro">$10</a></td></tr>
+                </tbody>
+            </table>"#,
+        );
+
+        assert_eq!(
+            tables,
+            vec![
+                "| Plan | Price |\n| --- | --- |\n| **Basic** | $5 \\| month |\n| Pro | [$10](/pro) |"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_convert_tables_uses_first_row_without_header() {
+        let tables =
+            convert("<table><tr><td>a</td><td>b</td></tr><tr><td>c</td><td>d</td></tr></table>");
+        assert_eq!(tables[0], "| a | b |\n| --- | --- |\n| c | d |");
+    }
+
+    #[test]
+    fn test_convert_tables_without_gfm_uses_key_value_list() {
+        let (_, tables) = convert_tables(
+            "<table><tr><th>Plan</th><th>Price</th></tr><tr><td>Pro</td><td>$10</td></tr></table>",
+            false,
+        );
+        assert_eq!(tables[0], "- **Plan**: Pro; **Price**: $10");
+    }
+
+    #[test]
+    fn test_convert_tables_spans_fall_back_to_key_value_list() {
+        let tables = convert(
+            r#"<table>
+                <caption>Limits</caption>
+                <tr><th>Tier</th><th>Requests</th><th>Storage</th></tr>
+                <tr><td rowspan="2">Free</td><td>100/day</td><td>1 GB</td></tr>
+                <tr><td colspan="2">Unlimited on weekends</td></tr>
+            </table>"#,
+        );
+
+        assert_eq!(
+            tables[0],
+            "**Limits**\n\n- **Tier**: Free; **Requests**: 100/day; **Storage**: 1 GB\n\
+             - **Tier**: Free; **Requests**: Unlimited on weekends"
+        );
+    }
+
+    #[test]
+    fn test_convert_tables_block_content_falls_back_to_key_value_list() {
+        let tables = convert(
+            r#"<table>
+                <tr><th>Option</th><th>Notes</th></tr>
+                <tr><td>timeout</td><td><ul><li>seconds</li><li>optional</li></ul></td></tr>
+            </table>"#,
+        );
+        assert!(tables[0].starts_with("- **Option**: timeout; **Notes**: "));
+        assert!(tables[0].contains("seconds"));
+        assert!(!tables[0].contains('\n'));
+    }
+
+    #[test]
+    fn test_convert_tables_flattens_layout_tables() {
+        let (html, tables) = convert_tables(
+            r#"<table role="presentation"><tr><td><p>Left column text</p></td><td>
+                <table><tr><th>Key</th><th>Value</th></tr><tr><td>a</td><td>1</td></tr></table>
+            </td></tr></table>
+            <table><tr><td><p>Single column layout</p></td></tr></table>"#,
+            true,
+        );
+
+        assert_eq!(
+            tables,
+            vec!["| Key | Value |\n| --- | --- |\n| a | 1 |".to_string()]
+        );
+        assert!(html.contains("<div><p>Left column text</p></div>"));
+        assert!(html.contains("<p>MDHARVESTTABLE0</p>"));
+        assert!(html.contains("<div><p>Single column layout</p></div>"));
+        assert!(!html.contains("<table"));
+    }
+
+    #[test]
+    fn test_convert_tables_keeps_single_column_data_tables() {
+        let (html, tables) = convert_tables(
+            r#"<table><tr><th>Supported browsers</th></tr><tr><td>Firefox</td></tr></table>
+            <table><caption>Release dates</caption><tr><td>2026-03-01</td></tr></table>"#,
+            true,
+        );
+
+        assert_eq!(
+            tables,
+            vec![
+                "| Supported browsers |\n| --- |\n| Firefox |".to_string(),
+                "**Release dates**\n\n| 2026-03-01 |\n| --- |".to_string(),
+            ]
+        );
+        assert!(!html.contains("<div>"));
+    }
+
+    #[test]
+    fn test_convert_tables_header_only_table_keeps_its_cells() {
+        let html = "<table><tr><th>Name</th><th>Email</th></tr></table>";
+        assert_eq!(convert(html)[0], "| Name | Email |\n| --- | --- |");
+
+        let (_, tables) = convert_tables(html, false);
+        assert_eq!(tables[0], "- Name; Email");
+
+        let spanned = convert(
+            r#"<table><thead>
+                <tr><th colspan="2">Contact</th></tr>
+                <tr><th>Name</th><th>Email</th></tr>
+            </thead></table>"#,
+        );
+        assert_eq!(spanned[0], "- Contact\n- Name; Email");
+    }
+
+    #[test]
+    fn test_restore_tables() {
+        let tables = vec!["| a | b |\n| --- | --- |\n| c | d |".to_string()];
+        assert_eq!(
+            restore_tables("Intro\n\nMDHARVESTTABLE0\n\nOutro MDHARVESTTABLE3", &tables),
+            "Intro\n\n\n\n| a | b |\n| --- | --- |\n| c | d |\n\n\n\nOutro "
+        );
+        assert_eq!(restore_tables("text", &[]), "text");
+    }
+}
@@ -0,0 +1,437 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::Url;
+
+/// File extensions of source files, with the language tag of their fence.
+const SOURCE_EXTENSIONS: [(&str, &str); 40] = [
+    ("rs", "rust"),
+    ("py", "python"),
+    ("js", "javascript"),
+    ("mjs", "javascript"),
+    ("cjs", "javascript"),
+    ("jsx", "jsx"),
+    ("ts", "typescript"),
+    ("tsx", "tsx"),
+    ("go", "go"),
+    ("java", "java"),
+    ("kt", "kotlin"),
+    ("scala", "scala"),
+    ("swift", "swift"),
+    ("c", "c"),
+    ("h", "c"),
+    ("cc", "cpp"),
+    ("cpp", "cpp"),
+    ("hpp", "cpp"),
+    ("cs", "csharp"),
+    ("rb", "ruby"),
+    ("php", "php"),
+    ("pl", "perl"),
+    ("lua", "lua"),
+    ("r", "r"),
+    ("sh", "bash"),
+    ("bash", "bash"),
+    ("zsh", "zsh"),
+    ("ps1", "powershell"),
+    ("sql", "sql"),
+    ("css", "css"),
+    ("scss", "scss"),
+    ("json", "json"),
+    ("yaml", "yaml"),
+    ("yml", "yaml"),
+    ("toml", "toml"),
+    ("ini", "ini"),
+    ("hs", "haskell"),
+    ("ex", "elixir"),
+    ("erl", "erlang"),
+    ("dockerfile", "dockerfile"),
+];
+
+/// Media types of source files, with the language tag of their fence.
+const SOURCE_MEDIA_TYPES: [(&str, &str); 12] = [
+    ("application/javascript", "javascript"),
+    ("text/javascript", "javascript"),
+    ("application/json", "json"),
+    ("text/css", "css"),
+    ("text/x-python", "python"),
+    ("text/x-rust", "rust"),
+    ("text/x-c", "c"),
+    ("text/x-java-source", "java"),
+    ("application/x-sh", "bash"),
+    ("text/x-shellscript", "bash"),
+    ("application/toml", "toml"),
+    ("application/yaml", "yaml"),
+];
+
+const MARKDOWN_EXTENSIONS: [&str; 4] = ["md", "markdown", "mdown", "mkd"];
+
+/// Lines that Markdown would read as the start of a heading, quote, list or
+/// thematic break.
+static BLOCK_MARKER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:(#{1,6}\s|>|[-*+]\s|[-*_]{3,}\s*$)|\d+([.)])\s)").unwrap());
+
+/// A leading YAML front-matter block.
+static FRONT_MATTER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)\A---[ \t]*\n(.*?)\n---[ \t]*(?:\n|\z)").unwrap());
+
+static FRONT_MATTER_TITLE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?m)^title:\s*["']?(.+?)["']?\s*$"#).unwrap());
+
+static ATX_HEADING_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^#\s+(.+?)\s*#*\s*$").unwrap());
+
+/// How a fetched body is turned into Markdown, chosen from its media type and
+/// the extension of its URL.
+///
+/// Only HTML goes through the extraction and cleaning pipeline. Markdown is
+/// passed through with light normalization, plain text is wrapped as
+/// paragraphs and source files are wrapped in a fenced code block, so raw
+/// READMEs, RFCs and source files keep their code blocks and links.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::BodyKind;
+///
+/// let url = "https://raw.githubusercontent.com/owner/repo/main/src/lib.rs";
+/// assert_eq!(BodyKind::detect(Some("text/plain; charset=utf-8"), url), BodyKind::Source("rust"));
+/// assert_eq!(BodyKind::detect(Some("text/markdown"), "https://example.com/notes"), BodyKind::Markdown);
+/// // A page rendering a file is still HTML
+/// assert_eq!(
+///     BodyKind::detect(Some("text/html"), "https://github.com/owner/repo/blob/main/README.md"),
+///     BodyKind::Html
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BodyKind {
+    /// An HTML page, extracted and cleaned.
+    #[default]
+    Html,
+    /// A Markdown document, passed through.
+    Markdown,
+    /// Plain text, wrapped as paragraphs.
+    PlainText,
+    /// A source file, wrapped in a code block fenced with this language tag.
+    Source(&'static str),
+}
+
+impl BodyKind {
+    /// Chooses the kind of a body from its `Content-Type` and URL.
+    ///
+    /// HTML and XHTML media types are always HTML. `text/markdown`, plain text
+    /// and known source media types are used as they are, except that plain
+    /// text or an unspecific type served from a Markdown or source file URL,
+    /// as raw file hosts do, takes the kind of its extension. Bodies without a
+    /// recognised type or extension are treated as HTML.
+    pub fn detect(content_type: Option<&str>, url: &str) -> Self {
+        let media_type = content_type
+            .and_then(|content_type| content_type.split(';').next())
+            .map(|media_type| media_type.trim().to_ascii_lowercase());
+        let from_extension = extension_kind(url);
+
+        match media_type.as_deref() {
+            Some("text/html" | "application/xhtml+xml") => BodyKind::Html,
+            Some("text/markdown" | "text/x-markdown") => BodyKind::Markdown,
+            Some("text/plain") => from_extension.unwrap_or(BodyKind::PlainText),
+            None | Some("application/octet-stream" | "binary/octet-stream") => {
+                from_extension.unwrap_or(BodyKind::Html)
+            }
+            Some(media_type) => SOURCE_MEDIA_TYPES
+                .iter()
+                .find(|(source_type, _)| *source_type == media_type)
+                .map(|(_, language)| BodyKind::Source(language))
+                .unwrap_or(BodyKind::Html),
+        }
+    }
+
+    /// Returns `true` for HTML, the only kind that goes through extraction.
+    pub fn is_html(self) -> bool {
+        self == BodyKind::Html
+    }
+}
+
+/// Kind implied by the extension of the last segment of the URL path.
+fn extension_kind(url: &str) -> Option<BodyKind> {
+    let url = Url::parse(url).ok()?;
+    let file_name = url.path_segments()?.next_back()?.to_ascii_lowercase();
+    if file_name == "dockerfile" || file_name == "makefile" {
+        let language = if file_name == "dockerfile" {
+            "dockerfile"
+        } else {
+            "makefile"
+        };
+        return Some(BodyKind::Source(language));
+    }
+    let (_, extension) = file_name.rsplit_once('.')?;
+
+    if MARKDOWN_EXTENSIONS.contains(&extension) {
+        Some(BodyKind::Markdown)
+    } else if extension == "txt" {
+        Some(BodyKind::PlainText)
+    } else {
+        SOURCE_EXTENSIONS
+            .iter()
+            .find(|(source_extension, _)| *source_extension == extension)
+            .map(|(_, language)| BodyKind::Source(language))
+    }
+}
+
+/// A non-HTML body converted to Markdown.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PassthroughContent {
+    pub(crate) markdown: String,
+    /// Title from the document's front matter or first heading, or the file
+    /// name of a source file.
+    pub(crate) title: Option<String>,
+}
+
+/// Converts a Markdown, plain-text or source body to Markdown. `url` names
+/// source files. HTML bodies are not handled here and yield empty content.
+pub(crate) fn convert_text(body: &str, kind: BodyKind, url: Option<&str>) -> PassthroughContent {
+    let body = body
+        .strip_prefix('\u{feff}')
+        .unwrap_or(body)
+        .replace("\r\n", "\n");
+    match kind {
+        BodyKind::Html => PassthroughContent {
+            markdown: String::new(),
+            title: None,
+        },
+        BodyKind::Markdown => normalize_markdown(&body),
+        BodyKind::PlainText => PassthroughContent {
+            markdown: plain_text_to_markdown(&body),
+            title: None,
+        },
+        BodyKind::Source(language) => PassthroughContent {
+            markdown: fence_source(&body, language),
+            title: url
+                .and_then(|url| Url::parse(url).ok())
+                .and_then(|url| url.path_segments()?.next_back().map(str::to_string))
+                .filter(|name| !name.is_empty()),
+        },
+    }
+}
+
+/// Drops a leading YAML front-matter block, trims trailing whitespace and
+/// collapses runs of blank lines outside code fences.
+fn normalize_markdown(markdown: &str) -> PassthroughContent {
+    let mut title = None;
+    let mut body = markdown;
+    if let Some(front_matter) = FRONT_MATTER_REGEX.captures(markdown) {
+        title = FRONT_MATTER_TITLE_REGEX
+            .captures(&front_matter[1])
+            .map(|caps| caps[1].to_string());
+        body = &markdown[front_matter[0].len()..];
+    }
+
+    let mut lines: Vec<&str> = Vec::new();
+    let mut fence: Option<&str> = None;
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        match fence {
+            Some(marker) => {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+                lines.push(line.trim_end());
+                continue;
+            }
+            None if trimmed.starts_with("```") || trimmed.starts_with("~~~") => {
+                fence = Some(&trimmed[..3]);
+            }
+            None => {}
+        }
+        let line = line.trim_end();
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+
+    let markdown = lines.join("\n").trim().to_string();
+    let title = title.or_else(|| {
+        ATX_HEADING_REGEX
+            .captures(&markdown)
+            .map(|caps| caps[1].to_string())
+    });
+    PassthroughContent { markdown, title }
+}
+
+/// Wraps plain text as paragraphs.
+///
+/// Blocks separated by blank lines or form feeds become paragraphs with their
+/// lines joined, with leading Markdown block markers escaped. Blocks laid out
+/// in columns or with nested indentation, such as tables, diagrams and page
+/// headers in RFCs, are kept as they are in a code block.
+fn plain_text_to_markdown(text: &str) -> String {
+    let text = text.replace('\u{c}', "\n\n");
+    let mut blocks: Vec<Vec<&str>> = vec![Vec::new()];
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            blocks.push(Vec::new());
+        } else if let Some(block) = blocks.last_mut() {
+            block.push(line.trim_end());
+        }
+    }
+
+    blocks
+        .into_iter()
+        .filter(|block| !block.is_empty())
+        .map(|block| {
+            let indent = |line: &&str| line.len() - line.trim_start().len();
+            let min_indent = block.iter().map(indent).min().unwrap_or(0);
+            let preformatted = block.iter().any(|line| {
+                indent(line) > min_indent || line.trim().contains("   ") || line.contains('|')
+            });
+            if preformatted {
+                let lines: Vec<&str> = block.iter().map(|line| &line[min_indent..]).collect();
+                fence_source(&lines.join("\n"), "")
+            } else {
+                let paragraph = block
+                    .iter()
+                    .map(|line| line.trim())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                escape_block_marker(paragraph)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Escapes a leading heading, quote, list or thematic break marker so the
+/// paragraph stays a paragraph; `1. Text` becomes `1\\. Text`.
+fn escape_block_marker(mut paragraph: String) -> String {
+    let Some(caps) = BLOCK_MARKER_REGEX.captures(&paragraph) else {
+        return paragraph;
+    };
+    let position = caps.get(2).map_or(0, |delimiter| delimiter.start());
+    paragraph.insert(position, '\\');
+    paragraph
+}
+
+/// Wraps code in a fence longer than any backtick run it contains.
+fn fence_source(code: &str, language: &str) -> String {
+    let code = code.trim_matches('\n').trim_end();
+    if code.is_empty() {
+        return String::new();
+    }
+    let longest_run = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{fence}{language}\n{code}\n{fence}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_body_kind_detect() {
+        let raw = "https://raw.githubusercontent.com/owner/repo/main";
+        assert_eq!(
+            BodyKind::detect(
+                Some("text/plain; charset=utf-8"),
+                &format!("{raw}/README.md")
+            ),
+            BodyKind::Markdown
+        );
+        assert_eq!(
+            BodyKind::detect(Some("text/plain"), &format!("{raw}/tools/build.py")),
+            BodyKind::Source("python")
+        );
+        assert_eq!(
+            BodyKind::detect(Some("text/plain"), "https://www.rfc-editor.org/rfc/rfc9110"),
+            BodyKind::PlainText
+        );
+        assert_eq!(
+            BodyKind::detect(Some("application/json"), "https://api.example.com/items"),
+            BodyKind::Source("json")
+        );
+        assert_eq!(
+            BodyKind::detect(None, &format!("{raw}/Dockerfile")),
+            BodyKind::Source("dockerfile")
+        );
+        assert_eq!(
+            BodyKind::detect(None, "https://example.com/post"),
+            BodyKind::Html
+        );
+        assert_eq!(
+            BodyKind::detect(Some("image/png"), "https://example.com/logo.png"),
+            BodyKind::Html
+        );
+    }
+
+    #[test]
+    fn test_convert_text_markdown_and_source() {
+        let readme = "---\r\ntitle: \"Harvest\"\r\n---\r\n# markdown-harvest   \r\n\r\n\r\n\r\n\
+                      See [the docs](https://docs.rs/).\r\n\r\n```rust\r\nlet a = 1;\r\n\r\n\r\nlet b = 2;\r\n```\r\n";
+        let content = convert_text(readme, BodyKind::Markdown, None);
+        assert_eq!(
+            content.markdown,
+            "# markdown-harvest\n\nSee [the docs](https://docs.rs/).\n\n\
+             ```rust\nlet a = 1;\n\n\nlet b = 2;\n```"
+        );
+        assert_eq!(content.title.as_deref(), Some("Harvest"));
+
+        let code = "fn main() {\n    println!(\"```\");\n}\n";
+        let content = convert_text(
+            code,
+            BodyKind::Source("rust"),
+            Some("https://example.com/src/main.rs"),
+        );
+        assert_eq!(
+            content.markdown,
+            "````rust\nfn main() {\n    println!(\"```\");\n}\n````"
+        );
+        assert_eq!(content.title.as_deref(), Some("main.rs"));
+    }
+
+    #[test]
+    fn test_convert_text_plain_text() {
+        let rfc = "Network Working Group                                          J. Postel\n\
+                   Request for Comments: 791                                            ISI\n\
+                   \n   The Internet Protocol is designed for use in interconnected systems\n   \
+                   of packet-switched computer communication networks.\n\
+                   \u{c}\n   1. Introduction\n\n   \
+                   +--------+------+\n   | Header | Data |\n   +--------+------+\n";
+        let markdown = convert_text(rfc, BodyKind::PlainText, None).markdown;
+        assert_eq!(
+            markdown,
+            "```\nNetwork Working Group                                          J. Postel\n\
+             Request for Comments: 791                                            ISI\n```\n\n\
+             The Internet Protocol is designed for use in interconnected systems \
+             of packet-switched computer communication networks.\n\n\
+             1\\. Introduction\n\n\
+             ```\n+--------+------+\n| Header | Data |\n+--------+------+\n```"
+        );
+    }
+
+    #[test]
+    fn test_convert_text_empty_bodies() {
+        for body in ["", "\u{feff}", " \r\n\r\n\t\n"] {
+            for kind in [
+                BodyKind::Markdown,
+                BodyKind::PlainText,
+                BodyKind::Source("rust"),
+            ] {
+                let content = convert_text(body, kind, Some("https://example.com/"));
+                assert_eq!(content.markdown, "", "{body:?} as {kind:?}");
+                assert_eq!(content.title, None);
+            }
+        }
+
+        let content = convert_text("---\ntitle: Draft\n---\n", BodyKind::Markdown, None);
+        assert_eq!(content.markdown, "");
+        assert_eq!(content.title.as_deref(), Some("Draft"));
+
+        // Unknown extensions and URLs without a file name fall back to the media type
+        assert_eq!(
+            BodyKind::detect(Some("text/plain"), "https://example.com/notes.xyz"),
+            BodyKind::PlainText
+        );
+        assert_eq!(
+            BodyKind::detect(None, "https://example.com/"),
+            BodyKind::Html
+        );
+        assert_eq!(BodyKind::detect(Some(""), "not a url"), BodyKind::Html);
+    }
+}
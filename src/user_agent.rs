@@ -1,3 +1,5 @@
+use std::fmt;
+
 use rand::prelude::*;
 
 /// Represents different browser user agent strings for web scraping.
@@ -57,53 +59,6 @@ pub enum UserAgent {
 }
 
 impl UserAgent {
-    /// Converts the UserAgent enum variant to its corresponding user agent string.
-    ///
-    /// Each variant returns a realistic, up-to-date user agent string that mimics
-    /// real browsers. These strings include browser version numbers, operating system
-    /// details, and rendering engine information.
-    ///
-    /// # Returns
-    ///
-    /// A `String` containing the complete user agent string for HTTP headers.
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// use markdown_harvest::UserAgent;
-    ///
-    /// let chrome = UserAgent::WindowsChrome;
-    /// let user_agent_string = chrome.to_string();
-    /// assert!(user_agent_string.contains("Chrome"));
-    /// assert!(user_agent_string.contains("Windows"));
-    ///
-    /// let firefox = UserAgent::LinuxFirefox;
-    /// let user_agent_string = firefox.to_string();
-    /// assert!(user_agent_string.contains("Firefox"));
-    /// assert!(user_agent_string.contains("Linux"));
-    /// ```
-    pub fn to_string(&self) -> String {
-        match self {
-            // Windows User Agents
-            UserAgent::WindowsChrome => "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36".to_string(),
-            UserAgent::WindowsFirefox => "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:121.0) Gecko/20100101 Firefox/121.0".to_string(),
-            UserAgent::WindowsEdge => "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.0.0".to_string(),
-            // macOS User Agents
-            UserAgent::MacOSChrome => "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36".to_string(),
-            UserAgent::MacOSSafari => "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1 Safari/605.1.15".to_string(),
-            UserAgent::MacOSFirefox => "Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:121.0) Gecko/20100101 Firefox/121.0".to_string(),
-            // Linux User Agents
-            UserAgent::LinuxChrome => "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36".to_string(),
-            UserAgent::LinuxFirefox => "Mozilla/5.0 (X11; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0".to_string(),
-            // Android User Agents
-            UserAgent::AndroidChrome => "Mozilla/5.0 (Linux; Android 14; SM-G991B) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36".to_string(),
-            UserAgent::AndroidFirefox => "Mozilla/5.0 (Mobile; rv:121.0) Gecko/121.0 Firefox/121.0".to_string(),
-            // iOS User Agents
-            UserAgent::IOSSafari => "Mozilla/5.0 (iPhone; CPU iPhone OS 17_1 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1 Mobile/15E148 Safari/604.1".to_string(),
-            UserAgent::IOSChrome => "Mozilla/5.0 (iPhone; CPU iPhone OS 17_1 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) CriOS/120.0.0.0 Mobile/15E148 Safari/604.1".to_string(),
-        }
-    }
-
     /// Returns a random user agent for better web scraping diversity.
     ///
     /// This method selects a random user agent from all available variants to help
@@ -154,3 +109,50 @@ impl UserAgent {
             .unwrap_or(&UserAgent::LinuxFirefox)
     }
 }
+
+/// Formats a UserAgent enum variant as its corresponding user agent string.
+///
+/// Each variant returns a realistic, up-to-date user agent string that mimics
+/// real browsers. These strings include browser version numbers, operating system
+/// details, and rendering engine information.
+///
+/// `to_string()` gives the complete user agent string for HTTP headers.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_harvest::UserAgent;
+///
+/// let chrome = UserAgent::WindowsChrome;
+/// let user_agent_string = chrome.to_string();
+/// assert!(user_agent_string.contains("Chrome"));
+/// assert!(user_agent_string.contains("Windows"));
+///
+/// let firefox = UserAgent::LinuxFirefox;
+/// let user_agent_string = firefox.to_string();
+/// assert!(user_agent_string.contains("Firefox"));
+/// assert!(user_agent_string.contains("Linux"));
+/// ```
+impl fmt::Display for UserAgent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            // Windows User Agents
+            UserAgent::WindowsChrome => "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+            UserAgent::WindowsFirefox => "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:121.0) Gecko/20100101 Firefox/121.0",
+            UserAgent::WindowsEdge => "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.0.0",
+            // macOS User Agents
+            UserAgent::MacOSChrome => "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+            UserAgent::MacOSSafari => "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1 Safari/605.1.15",
+            UserAgent::MacOSFirefox => "Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:121.0) Gecko/20100101 Firefox/121.0",
+            // Linux User Agents
+            UserAgent::LinuxChrome => "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+            UserAgent::LinuxFirefox => "Mozilla/5.0 (X11; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
+            // Android User Agents
+            UserAgent::AndroidChrome => "Mozilla/5.0 (Linux; Android 14; SM-G991B) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36",
+            UserAgent::AndroidFirefox => "Mozilla/5.0 (Mobile; rv:121.0) Gecko/121.0 Firefox/121.0",
+            // iOS User Agents
+            UserAgent::IOSSafari => "Mozilla/5.0 (iPhone; CPU iPhone OS 17_1 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1 Mobile/15E148 Safari/604.1",
+            UserAgent::IOSChrome => "Mozilla/5.0 (iPhone; CPU iPhone OS 17_1 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) CriOS/120.0.0.0 Mobile/15E148 Safari/604.1",
+        })
+    }
+}